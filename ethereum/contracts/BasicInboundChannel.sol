@@ -2,6 +2,7 @@
 pragma solidity ^0.8.9;
 
 import "./ParachainClient.sol";
+import "./utils/MerkleProof.sol";
 
 contract BasicInboundChannel {
     uint256 public constant MAX_GAS_PER_MESSAGE = 100000;
@@ -11,6 +12,9 @@ contract BasicInboundChannel {
 
     uint64 public nonce;
 
+    // Ids of messages which were delivered individually ahead of the bundle which commits to them
+    mapping(uint64 => bool) public delivered;
+
     ParachainClient public parachainClient;
 
     struct MessageBundle {
@@ -25,6 +29,40 @@ contract BasicInboundChannel {
         bytes payload;
     }
 
+    // Version of the commitment wire format in which messages carry an expiry
+    uint8 internal constant EXPIRING_VERSION = 2;
+
+    struct ExpiringMessageBundle {
+        uint8 sourceChannelID;
+        uint64 nonce;
+        ExpiringMessage[] messages;
+    }
+
+    struct ExpiringMessage {
+        uint64 id;
+        address target;
+        // Timestamp after which the message is not dispatched, or zero if it does not expire
+        uint64 expiresAt;
+        bytes payload;
+    }
+
+    // An ExpiringMessage as ABI-encoded in the commitment
+    struct ExpiringMessageLeaf {
+        uint8 version;
+        uint64 id;
+        address target;
+        uint64 expiresAt;
+        bytes payload;
+    }
+
+    // Proof that a message is a leaf of the Merkle tree over the messages of a bundle
+    struct LeafProof {
+        bytes32 root;
+        uint256 position;
+        uint256 width;
+        bytes32[] proof;
+    }
+
     event MessageDispatched(uint64 id, bool result);
 
     constructor(uint8 _sourceChannelID, ParachainClient _parachainClient) {
@@ -34,26 +72,163 @@ contract BasicInboundChannel {
     }
 
     function submit(MessageBundle calldata bundle,  bytes calldata proof) external {
-        bytes32 commitment = keccak256(abi.encode(bundle));
+        bytes32 commitment = keccak256(
+            abi.encode(bundle.sourceChannelID, bundle.nonce, messagesRoot(bundle.messages))
+        );
+        verifyBundle(commitment, proof, bundle.sourceChannelID, bundle.nonce, bundle.messages.length);
+        nonce++;
+        dispatch(bundle);
+    }
+
+    // Submit a bundle committed in the version in which messages carry an expiry. Messages which
+    // have expired are not dispatched, and are reported as failed.
+    function submitExpiring(ExpiringMessageBundle calldata bundle, bytes calldata proof) external {
+        bytes32 commitment = keccak256(
+            abi.encode(bundle.sourceChannelID, bundle.nonce, expiringMessagesRoot(bundle.messages))
+        );
+        verifyBundle(commitment, proof, bundle.sourceChannelID, bundle.nonce, bundle.messages.length);
+        nonce++;
+        dispatchExpiring(bundle);
+    }
+
+    // Submit a single message of the bundle with nonce `bundleNonce`, proven against the root of the
+    // bundle's messages. Messages can be delivered ahead of their bundle and in any order. The
+    // bundle itself must still be submitted to advance the nonce, and skips the messages which
+    // were already delivered.
+    function submitMessage(
+        uint64 bundleNonce,
+        Message calldata message,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) external {
+        verifyMessage(bundleNonce, message.id, keccak256(abi.encode(message)), leafProof, proof);
+        delivered[message.id] = true;
+        dispatchMessage(message);
+    }
+
+    function submitExpiringMessage(
+        uint64 bundleNonce,
+        ExpiringMessage calldata message,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) external {
+        verifyMessage(bundleNonce, message.id, expiringMessageLeaf(message), leafProof, proof);
+        delivered[message.id] = true;
+        dispatchExpiringMessage(message);
+    }
 
+    function verifyBundle(
+        bytes32 commitment,
+        bytes calldata proof,
+        uint8 bundleSourceChannelID,
+        uint64 bundleNonce,
+        uint256 messageCount
+    ) internal view {
         require(parachainClient.verifyCommitment(commitment, proof), "Invalid proof");
-        require(bundle.sourceChannelID == sourceChannelID, "Invalid source channel");
-        require(bundle.nonce == nonce + 1, "Invalid nonce");
+        require(bundleSourceChannelID == sourceChannelID, "Invalid source channel");
+        require(bundleNonce == nonce + 1, "Invalid nonce");
         require(
-            gasleft() >= (bundle.messages.length * MAX_GAS_PER_MESSAGE) + GAS_BUFFER,
+            gasleft() >= (messageCount * MAX_GAS_PER_MESSAGE) + GAS_BUFFER,
             "insufficient gas for delivery of all messages"
         );
-        nonce++;
-        dispatch(bundle);
+    }
+
+    function verifyMessage(
+        uint64 bundleNonce,
+        uint64 id,
+        bytes32 leaf,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) internal view {
+        bytes32 commitment = keccak256(abi.encode(sourceChannelID, bundleNonce, leafProof.root));
+        require(parachainClient.verifyCommitment(commitment, proof), "Invalid proof");
+        require(bundleNonce > nonce, "Invalid nonce");
+        require(!delivered[id], "Message already delivered");
+        require(
+            MerkleProof.verifyMerkleLeafAtPosition(
+                leafProof.root,
+                leaf,
+                leafProof.position,
+                leafProof.width,
+                leafProof.proof
+            ),
+            "Invalid leaf proof"
+        );
+        require(
+            gasleft() >= MAX_GAS_PER_MESSAGE + GAS_BUFFER,
+            "insufficient gas for delivery of message"
+        );
+    }
+
+    // Root of the Merkle tree over the ABI-encoded messages, as committed by the outbound channel
+    // on the parachain.
+    function messagesRoot(Message[] calldata messages) internal pure returns (bytes32) {
+        bytes32[] memory leaves = new bytes32[](messages.length);
+        for (uint256 i = 0; i < messages.length; i++) {
+            leaves[i] = keccak256(abi.encode(messages[i]));
+        }
+        return MerkleProof.computeRoot(leaves);
+    }
+
+    function expiringMessagesRoot(ExpiringMessage[] calldata messages) internal pure returns (bytes32) {
+        bytes32[] memory leaves = new bytes32[](messages.length);
+        for (uint256 i = 0; i < messages.length; i++) {
+            leaves[i] = expiringMessageLeaf(messages[i]);
+        }
+        return MerkleProof.computeRoot(leaves);
+    }
+
+    function expiringMessageLeaf(ExpiringMessage calldata message) internal pure returns (bytes32) {
+        return keccak256(
+            abi.encode(
+                ExpiringMessageLeaf(
+                    EXPIRING_VERSION,
+                    message.id,
+                    message.target,
+                    message.expiresAt,
+                    message.payload
+                )
+            )
+        );
     }
 
     function dispatch(MessageBundle calldata bundle) internal {
         for (uint256 i = 0; i < bundle.messages.length; i++) {
             Message calldata message = bundle.messages[i];
-            (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
-                message.payload
-            );
-            emit MessageDispatched(message.id, success);
+            if (delivered[message.id]) {
+                delete delivered[message.id];
+                continue;
+            }
+            dispatchMessage(message);
+        }
+    }
+
+    function dispatchExpiring(ExpiringMessageBundle calldata bundle) internal {
+        for (uint256 i = 0; i < bundle.messages.length; i++) {
+            ExpiringMessage calldata message = bundle.messages[i];
+            if (delivered[message.id]) {
+                delete delivered[message.id];
+                continue;
+            }
+            dispatchExpiringMessage(message);
         }
     }
+
+    function dispatchMessage(Message calldata message) internal {
+        (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
+            message.payload
+        );
+        emit MessageDispatched(message.id, success);
+    }
+
+    function dispatchExpiringMessage(ExpiringMessage calldata message) internal {
+        if (message.expiresAt != 0 && block.timestamp > message.expiresAt) {
+            emit MessageDispatched(message.id, false);
+            return;
+        }
+        (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
+            message.payload
+        );
+        emit MessageDispatched(message.id, success);
+    }
 }
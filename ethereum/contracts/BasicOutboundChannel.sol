@@ -5,13 +5,14 @@ import "@openzeppelin/contracts/access/AccessControl.sol";
 import "./ChannelAccess.sol";
 import "./OutboundChannel.sol";
 
-// BasicOutboundChannel is a basic channel that just sends messages with a nonce.
+// BasicOutboundChannel is a basic channel that just sends messages with a nonce per source app.
 contract BasicOutboundChannel is OutboundChannel, ChannelAccess, AccessControl {
 
     // Governance contracts will administer using this role.
     bytes32 public constant CONFIG_UPDATE_ROLE = keccak256("CONFIG_UPDATE_ROLE");
 
-    uint64 public nonce;
+    // Nonce for last submitted message, per source app
+    mapping(address => uint64) public override nonce;
 
     // Only messages originating from this account will
     // be allowed through the channel.
@@ -77,7 +78,7 @@ contract BasicOutboundChannel is OutboundChannel, ChannelAccess, AccessControl {
         if (principal != address(0x0000000000000000000000000000000000000042)) {
             require(_origin == principal, "Origin is not an authorized principal");
         }
-        nonce = nonce + 1;
-        emit Message(msg.sender, nonce, _payload);
+        nonce[msg.sender] = nonce[msg.sender] + 1;
+        emit Message(msg.sender, nonce[msg.sender], _payload);
     }
 }
@@ -31,11 +31,22 @@ contract DOTApp is FeeController, AccessControl {
         Channel incentivized
     );
 
+    event Refunded(address sender, uint256 amount);
+
     struct Channel {
         address inbound;
         address outbound;
     }
 
+    struct Burn {
+        address sender;
+        uint256 amount;
+    }
+
+    // Burns whose unlock on the parachain is not acknowledged yet, by channel ID and nonce
+    // of the message
+    mapping(uint8 => mapping(uint64 => Burn)) public unacknowledged;
+
     constructor(
         string memory _name,
         string memory _symbol,
@@ -79,6 +90,9 @@ contract DOTApp is FeeController, AccessControl {
 
         bytes memory call = encodeCall(msg.sender, _recipient, _amount);
         channel.submit(msg.sender, call);
+
+        uint64 nonce = channel.nonce(address(this));
+        unacknowledged[uint8(_channelId)][nonce] = Burn(msg.sender, _amount);
     }
 
     function mint(
@@ -89,6 +103,25 @@ contract DOTApp is FeeController, AccessControl {
         token.mint(_recipient, _amount, abi.encodePacked(_sender));
     }
 
+    // Result of the unlock for a burn, sent by the parachain. The burned amount is minted
+    // back to the sender if the unlock failed.
+    function acknowledge(
+        uint8 _channelId,
+        uint64 _nonce,
+        bool _success
+    ) external onlyRole(INBOUND_CHANNEL_ROLE) {
+        Burn memory burned = unacknowledged[_channelId][_nonce];
+        if (burned.sender == address(0)) {
+            return;
+        }
+        delete unacknowledged[_channelId][_nonce];
+
+        if (!_success) {
+            token.mint(burned.sender, burned.amount, "");
+            emit Refunded(burned.sender, burned.amount);
+        }
+    }
+
     // Incentivized channel calls this to charge (burn) fees
     function handleFee(address feePayer, uint256 _amount) external override onlyRole(FEE_BURNER_ROLE) {
         token.burn(feePayer, _amount, "");
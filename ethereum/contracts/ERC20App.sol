@@ -44,6 +44,12 @@ contract ERC20App is AccessControl {
         uint128 amount
     );
 
+    event Refunded(
+        address token,
+        address sender,
+        uint128 amount
+    );
+
     event Upgraded(
         address upgrader,
         Channel basic,
@@ -55,6 +61,16 @@ contract ERC20App is AccessControl {
         address outbound;
     }
 
+    struct Lock {
+        address token;
+        address sender;
+        uint128 amount;
+    }
+
+    // Locks whose mint on the parachain is not acknowledged yet, by channel ID and nonce
+    // of the message
+    mapping(uint8 => mapping(uint64 => Lock)) public unacknowledged;
+
     bytes32 public constant INBOUND_CHANNEL_ROLE =
         keccak256("INBOUND_CHANNEL_ROLE");
 
@@ -115,6 +131,9 @@ contract ERC20App is AccessControl {
 
         channel.submit(msg.sender, call);
 
+        uint64 nonce = channel.nonce(address(this));
+        unacknowledged[uint8(_channelId)][nonce] = Lock(_token, msg.sender, _amount);
+
         require(
             IERC20(_token).transferFrom(msg.sender, address(this), _amount),
             "Contract token allowances insufficient to complete this lock request"
@@ -138,6 +157,26 @@ contract ERC20App is AccessControl {
         emit Unlocked(_token, _sender, _recipient, _amount);
     }
 
+    // Result of the mint for a lock, sent by the parachain. The sender is refunded if the
+    // mint failed.
+    function acknowledge(
+        uint8 _channelId,
+        uint64 _nonce,
+        bool _success
+    ) external onlyRole(INBOUND_CHANNEL_ROLE) {
+        Lock memory locked = unacknowledged[_channelId][_nonce];
+        if (locked.sender == address(0)) {
+            return;
+        }
+        delete unacknowledged[_channelId][_nonce];
+
+        if (!_success) {
+            balances[locked.token] = balances[locked.token] - locked.amount;
+            IERC20(locked.token).safeTransfer(locked.sender, locked.amount);
+            emit Refunded(locked.token, locked.sender, locked.amount);
+        }
+    }
+
     // SCALE-encode payload
     function encodeCall(
         address _token,
@@ -29,6 +29,8 @@ contract ETHApp is RewardController, AccessControl {
 
     event Unlocked(bytes32 sender, address recipient, uint128 amount);
 
+    event Refunded(address sender, uint128 amount);
+
     event Upgraded(
         address upgrader,
         Channel basic,
@@ -44,6 +46,15 @@ contract ETHApp is RewardController, AccessControl {
         address outbound;
     }
 
+    struct Lock {
+        address sender;
+        uint128 amount;
+    }
+
+    // Locks whose mint on the parachain is not acknowledged yet, by channel ID and nonce
+    // of the message
+    mapping(uint8 => mapping(uint64 => Lock)) public unacknowledged;
+
     bytes32 public constant INBOUND_CHANNEL_ROLE =
         keccak256("INBOUND_CHANNEL_ROLE");
 
@@ -101,6 +112,9 @@ contract ETHApp is RewardController, AccessControl {
             channels[_channelId].outbound
         );
         channel.submit(msg.sender, call);
+
+        uint64 nonce = channel.nonce(address(this));
+        unacknowledged[uint8(_channelId)][nonce] = Lock(msg.sender, value);
     }
 
     function unlock(
@@ -115,6 +129,26 @@ contract ETHApp is RewardController, AccessControl {
         emit Unlocked(_sender, _recipient, _amount);
     }
 
+    // Result of the mint for a lock, sent by the parachain. The sender is refunded if the
+    // mint failed.
+    function acknowledge(
+        uint8 _channelId,
+        uint64 _nonce,
+        bool _success
+    ) external onlyRole(INBOUND_CHANNEL_ROLE) {
+        Lock memory locked = unacknowledged[_channelId][_nonce];
+        if (locked.sender == address(0)) {
+            return;
+        }
+        delete unacknowledged[_channelId][_nonce];
+
+        if (!_success) {
+            (bool success, ) = payable(locked.sender).call{value: locked.amount}("");
+            require(success, "Unable to send Ether");
+            emit Refunded(locked.sender, locked.amount);
+        }
+    }
+
     // SCALE-encode payload
     function encodeCall(
         address _sender,
@@ -3,12 +3,19 @@ pragma solidity ^0.8.9;
 
 import "@openzeppelin/contracts/access/AccessControl.sol";
 import "./ParachainClient.sol";
+import "./utils/MerkleProof.sol";
 import "./RewardController.sol";
 
 contract IncentivizedInboundChannel is AccessControl {
     uint8 public immutable sourceChannelID;
     uint64 public nonce;
 
+    // Ids of messages which were delivered individually ahead of the bundle which commits to them
+    mapping(uint64 => bool) public delivered;
+
+    // Part of the fee of a bundle which was paid out for its individually delivered messages
+    mapping(uint64 => uint128) public paidFees;
+
     struct MessageBundle {
         uint8 sourceChannelID;
         uint64 nonce;
@@ -22,6 +29,41 @@ contract IncentivizedInboundChannel is AccessControl {
         bytes payload;
     }
 
+    // Version of the commitment wire format in which messages carry an expiry
+    uint8 internal constant EXPIRING_VERSION = 2;
+
+    struct ExpiringMessageBundle {
+        uint8 sourceChannelID;
+        uint64 nonce;
+        uint128 fee;
+        ExpiringMessage[] messages;
+    }
+
+    struct ExpiringMessage {
+        uint64 id;
+        address target;
+        // Timestamp after which the message is not dispatched, or zero if it does not expire
+        uint64 expiresAt;
+        bytes payload;
+    }
+
+    // An ExpiringMessage as ABI-encoded in the commitment
+    struct ExpiringMessageLeaf {
+        uint8 version;
+        uint64 id;
+        address target;
+        uint64 expiresAt;
+        bytes payload;
+    }
+
+    // Proof that a message is a leaf of the Merkle tree over the messages of a bundle
+    struct LeafProof {
+        bytes32 root;
+        uint256 position;
+        uint256 width;
+        bytes32[] proof;
+    }
+
     event MessageDispatched(uint64 id, bool result);
 
     uint256 public constant MAX_GAS_PER_MESSAGE = 100000;
@@ -55,27 +97,192 @@ contract IncentivizedInboundChannel is AccessControl {
     }
 
     function submit(MessageBundle calldata bundle, bytes calldata proof) external {
-        bytes32 commitment = keccak256(abi.encode(bundle));
+        bytes32 commitment = keccak256(
+            abi.encode(bundle.sourceChannelID, bundle.nonce, bundle.fee, messagesRoot(bundle.messages))
+        );
+        verifyBundle(commitment, proof, bundle.sourceChannelID, bundle.nonce, bundle.messages.length);
+        nonce++;
+        dispatch(bundle);
+        uint128 paidFee = paidFees[bundle.nonce];
+        delete paidFees[bundle.nonce];
+        rewardController.handleReward(payable(msg.sender), bundle.fee - paidFee);
+    }
+
+    // Submit a bundle committed in the version in which messages carry an expiry. Messages which
+    // have expired are not dispatched, and are reported as failed.
+    function submitExpiring(ExpiringMessageBundle calldata bundle, bytes calldata proof) external {
+        bytes32 commitment = keccak256(
+            abi.encode(
+                bundle.sourceChannelID,
+                bundle.nonce,
+                bundle.fee,
+                expiringMessagesRoot(bundle.messages)
+            )
+        );
+        verifyBundle(commitment, proof, bundle.sourceChannelID, bundle.nonce, bundle.messages.length);
+        nonce++;
+        dispatchExpiring(bundle);
+        uint128 paidFee = paidFees[bundle.nonce];
+        delete paidFees[bundle.nonce];
+        rewardController.handleReward(payable(msg.sender), bundle.fee - paidFee);
+    }
+
+    // Submit a single message of the bundle with nonce `bundleNonce`, proven against the root of the
+    // bundle's messages. Messages can be delivered ahead of their bundle and in any order. The
+    // bundle itself must still be submitted to advance the nonce, and skips the messages which
+    // were already delivered. The relayer is rewarded with the message's share of the bundle fee.
+    function submitMessage(
+        uint64 bundleNonce,
+        uint128 fee,
+        Message calldata message,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) external {
+        verifyMessage(bundleNonce, fee, message.id, keccak256(abi.encode(message)), leafProof, proof);
+        delivered[message.id] = true;
+        dispatchMessage(message);
+        rewardMessage(bundleNonce, fee, leafProof.width);
+    }
+
+    function submitExpiringMessage(
+        uint64 bundleNonce,
+        uint128 fee,
+        ExpiringMessage calldata message,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) external {
+        verifyMessage(bundleNonce, fee, message.id, expiringMessageLeaf(message), leafProof, proof);
+        delivered[message.id] = true;
+        dispatchExpiringMessage(message);
+        rewardMessage(bundleNonce, fee, leafProof.width);
+    }
 
+    function verifyBundle(
+        bytes32 commitment,
+        bytes calldata proof,
+        uint8 bundleSourceChannelID,
+        uint64 bundleNonce,
+        uint256 messageCount
+    ) internal view {
         require(parachainClient.verifyCommitment(commitment, proof), "Invalid proof");
-        require(bundle.sourceChannelID == sourceChannelID, "Invalid source channel");
-        require(bundle.nonce == nonce + 1, "Invalid nonce");
+        require(bundleSourceChannelID == sourceChannelID, "Invalid source channel");
+        require(bundleNonce == nonce + 1, "Invalid nonce");
         require(
-            gasleft() >= (bundle.messages.length * MAX_GAS_PER_MESSAGE) + GAS_BUFFER,
+            gasleft() >= (messageCount * MAX_GAS_PER_MESSAGE) + GAS_BUFFER,
             "insufficient gas for delivery of all messages"
         );
-        nonce++;
-        dispatch(bundle);
-        rewardController.handleReward(payable(msg.sender), bundle.fee);
+    }
+
+    function verifyMessage(
+        uint64 bundleNonce,
+        uint128 fee,
+        uint64 id,
+        bytes32 leaf,
+        LeafProof calldata leafProof,
+        bytes calldata proof
+    ) internal view {
+        bytes32 commitment = keccak256(abi.encode(sourceChannelID, bundleNonce, fee, leafProof.root));
+        require(parachainClient.verifyCommitment(commitment, proof), "Invalid proof");
+        require(bundleNonce > nonce, "Invalid nonce");
+        require(!delivered[id], "Message already delivered");
+        require(
+            MerkleProof.verifyMerkleLeafAtPosition(
+                leafProof.root,
+                leaf,
+                leafProof.position,
+                leafProof.width,
+                leafProof.proof
+            ),
+            "Invalid leaf proof"
+        );
+        require(
+            gasleft() >= MAX_GAS_PER_MESSAGE + GAS_BUFFER,
+            "insufficient gas for delivery of message"
+        );
+    }
+
+    // Reward the relayer of an individually delivered message with its share of the bundle fee. The
+    // width of the leaf proof is not fully bound by the proof, so the total paid out for a bundle is
+    // capped at its fee.
+    function rewardMessage(uint64 bundleNonce, uint128 fee, uint256 width) internal {
+        uint128 paidFee = paidFees[bundleNonce];
+        uint128 reward = fee / uint128(width);
+        if (reward > fee - paidFee) {
+            reward = fee - paidFee;
+        }
+        paidFees[bundleNonce] = paidFee + reward;
+        rewardController.handleReward(payable(msg.sender), reward);
+    }
+
+    // Root of the Merkle tree over the ABI-encoded messages, as committed by the outbound channel
+    // on the parachain.
+    function messagesRoot(Message[] calldata messages) internal pure returns (bytes32) {
+        bytes32[] memory leaves = new bytes32[](messages.length);
+        for (uint256 i = 0; i < messages.length; i++) {
+            leaves[i] = keccak256(abi.encode(messages[i]));
+        }
+        return MerkleProof.computeRoot(leaves);
+    }
+
+    function expiringMessagesRoot(ExpiringMessage[] calldata messages) internal pure returns (bytes32) {
+        bytes32[] memory leaves = new bytes32[](messages.length);
+        for (uint256 i = 0; i < messages.length; i++) {
+            leaves[i] = expiringMessageLeaf(messages[i]);
+        }
+        return MerkleProof.computeRoot(leaves);
+    }
+
+    function expiringMessageLeaf(ExpiringMessage calldata message) internal pure returns (bytes32) {
+        return keccak256(
+            abi.encode(
+                ExpiringMessageLeaf(
+                    EXPIRING_VERSION,
+                    message.id,
+                    message.target,
+                    message.expiresAt,
+                    message.payload
+                )
+            )
+        );
     }
 
     function dispatch(MessageBundle calldata bundle) internal {
         for (uint256 i = 0; i < bundle.messages.length; i++) {
             Message calldata message = bundle.messages[i];
-            (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
-                message.payload
-            );
-            emit MessageDispatched(message.id, success);
+            if (delivered[message.id]) {
+                delete delivered[message.id];
+                continue;
+            }
+            dispatchMessage(message);
+        }
+    }
+
+    function dispatchExpiring(ExpiringMessageBundle calldata bundle) internal {
+        for (uint256 i = 0; i < bundle.messages.length; i++) {
+            ExpiringMessage calldata message = bundle.messages[i];
+            if (delivered[message.id]) {
+                delete delivered[message.id];
+                continue;
+            }
+            dispatchExpiringMessage(message);
+        }
+    }
+
+    function dispatchMessage(Message calldata message) internal {
+        (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
+            message.payload
+        );
+        emit MessageDispatched(message.id, success);
+    }
+
+    function dispatchExpiringMessage(ExpiringMessage calldata message) internal {
+        if (message.expiresAt != 0 && block.timestamp > message.expiresAt) {
+            emit MessageDispatched(message.id, false);
+            return;
         }
+        (bool success, ) = message.target.call{ value: 0, gas: MAX_GAS_PER_MESSAGE }(
+            message.payload
+        );
+        emit MessageDispatched(message.id, success);
     }
 }
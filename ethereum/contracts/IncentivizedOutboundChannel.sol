@@ -13,8 +13,8 @@ contract IncentivizedOutboundChannel is OutboundChannel, ChannelAccess, AccessCo
     // Governance contracts will administer using this role.
     bytes32 public constant CONFIG_UPDATE_ROLE = keccak256("CONFIG_UPDATE_ROLE");
 
-    // Nonce for last submitted message
-    uint64 public nonce;
+    // Nonce for last submitted message, per source app
+    mapping(address => uint64) public override nonce;
 
     uint256 public fee;
     FeeController public feeController;
@@ -75,7 +75,7 @@ contract IncentivizedOutboundChannel is OutboundChannel, ChannelAccess, AccessCo
     function submit(address feePayer, bytes calldata payload) external override {
         require(isOperatorFor(msg.sender, feePayer), "Caller is not an operator for fee payer");
         feeController.handleFee(feePayer, fee);
-        nonce = nonce + 1;
-        emit Message(msg.sender, nonce, fee, payload);
+        nonce[msg.sender] = nonce[msg.sender] + 1;
+        emit Message(msg.sender, nonce[msg.sender], fee, payload);
     }
 }
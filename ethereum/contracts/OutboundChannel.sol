@@ -4,4 +4,7 @@ pragma experimental ABIEncoderV2;
 
 interface OutboundChannel {
     function submit(address origin, bytes calldata payload) external;
+
+    // Nonce of the last message submitted by a source app
+    function nonce(address source) external view returns (uint64);
 }
@@ -6,7 +6,10 @@ import "../OutboundChannel.sol";
 contract MockOutboundChannel is OutboundChannel {
     event Message(address source, bytes data);
 
+    mapping(address => uint64) public override nonce;
+
     function submit(address, bytes calldata data) external override {
+        nonce[msg.sender] = nonce[msg.sender] + 1;
         emit Message(msg.sender, data);
     }
 }
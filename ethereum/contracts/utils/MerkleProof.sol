@@ -53,6 +53,32 @@ library MerkleProof {
         return node;
     }
 
+    /**
+     * @notice Compute the root of a Merkle tree in which the last node of a level with an odd number of nodes
+     * is promoted unchanged
+     *
+     * @param leaves the hashed leaves of the tree, which are overwritten with the inner nodes
+     * @return the root of the tree, or zero if there are no leaves
+     */
+    function computeRoot(bytes32[] memory leaves) internal pure returns (bytes32) {
+        uint256 width = leaves.length;
+        if (width == 0) {
+            return bytes32(0);
+        }
+
+        while (width > 1) {
+            for (uint256 i = 0; i < width / 2; i++) {
+                leaves[i] = keccak256(abi.encodePacked(leaves[2 * i], leaves[2 * i + 1]));
+            }
+            if (width % 2 == 1) {
+                leaves[width / 2] = leaves[width - 1];
+            }
+            width = (width + 1) / 2;
+        }
+
+        return leaves[0];
+    }
+
     function computeRootFromProofAtPosition(
         bytes32 leaf,
         uint256 pos,
@@ -18,12 +18,14 @@ module.exports = async ({
   const incentivizedChannelSourceID = process.env.INCENTIVIZED_CHANNEL_SOURCE_ID
 
   let parachainClient = await deployments.get("ParachainClient")
+  let merkleProofLibrary = await deployments.get("MerkleProof")
   let scaleCodecLibrary = await deployments.get("ScaleCodec")
 
   await deployments.deploy("BasicInboundChannel", {
     from: deployer,
     args: [basicChannelSourceID, parachainClient.address],
     libraries: {
+        MerkleProof: merkleProofLibrary.address,
         ScaleCodec: scaleCodecLibrary.address,
     },
     log: true,
@@ -34,6 +36,7 @@ module.exports = async ({
     from: deployer,
     args:[incentivizedChannelSourceID, parachainClient.address],
     libraries: {
+        MerkleProof: merkleProofLibrary.address,
         ScaleCodec: scaleCodecLibrary.address,
     },
     log: true,
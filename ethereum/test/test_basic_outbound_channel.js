@@ -12,6 +12,7 @@ require("chai")
 describe("BasicOutboundChannel", function () {
   let owner;
   let appAddress;
+  let otherAppAddress;
   let origin;
   const testPayload = ethers.utils.formatBytes32String("arbitrary-payload");
   const iface = new ethers.utils.Interface(BasicOutboundChannel.abi);
@@ -21,13 +22,14 @@ describe("BasicOutboundChannel", function () {
     owner = accounts[0];
     appAddress = accounts[1];
     origin = accounts[2];
+    otherAppAddress = accounts[3];
   });
 
   describe("send", function () {
     beforeEach(async function () {
       this.channel = await BasicOutboundChannel.new();
       const principal = "0x0000000000000000000000000000000000000042"
-      await this.channel.initialize(owner, principal, [appAddress]).should.be.fulfilled;
+      await this.channel.initialize(owner, principal, [appAddress, otherAppAddress]).should.be.fulfilled;
     });
 
     it("should send messages out with the correct event and fields", async function () {
@@ -70,6 +72,28 @@ describe("BasicOutboundChannel", function () {
       event.nonce.eq(ethers.BigNumber.from(3)).should.be.true;
     });
 
+    it("should track nonces per source app", async function () {
+      await this.channel.submit(
+        origin,
+        testPayload,
+        { from: appAddress, value: 0 }
+      ).should.be.fulfilled;
+
+      const tx = await this.channel.submit(
+        origin,
+        testPayload,
+        { from: otherAppAddress, value: 0 }
+      ).should.be.fulfilled;
+
+      const log = tx.receipt.rawLogs[0];
+      const event = iface.decodeEventLog('Message(address,uint64,bytes)', log.data, log.topics);
+      event.source.should.be.equal(otherAppAddress);
+      event.nonce.eq(ethers.BigNumber.from(1)).should.be.true;
+
+      (await this.channel.nonce(appAddress)).toString().should.be.equal("1");
+      (await this.channel.nonce(otherAppAddress)).toString().should.be.equal("1");
+    });
+
   });
 
 });
@@ -152,6 +152,38 @@ describe("DOTApp", function () {
       beforeTotalSupply.minus(afterTotalSupply).should.be.bignumber.equal(amountWrapped);
       beforeUserBalance.minus(afterUserBalance).should.be.bignumber.equal(amountWrapped);
     });
+
+    it("should mint burned funds back when the unlock failed", async function () {
+      const beforeUserBalance = BigNumber(await this.token.balanceOf(userOne));
+      const amountWrapped = wrapped(BigNumber("10000000000"));
+
+      await burnTokens(this.app, userOne, POLKADOT_ADDRESS, amountWrapped, ChannelId.Basic).should.be.fulfilled;
+
+      await this.app.acknowledge(ChannelId.Basic, 1, false, { from: userOne })
+        .should.be.rejectedWith(/AccessControl/);
+
+      const tx = await this.app.acknowledge(ChannelId.Basic, 1, false, { from: owner })
+        .should.be.fulfilled;
+
+      const event = tx.logs.find(e => e.event === "Refunded");
+      event.args.sender.should.be.equal(userOne);
+      BigNumber(event.args.amount).should.be.bignumber.equal(amountWrapped);
+
+      BigNumber(await this.token.balanceOf(userOne)).should.be.bignumber.equal(beforeUserBalance);
+    });
+
+    it("should not mint burned funds back when the unlock succeeded", async function () {
+      const beforeUserBalance = BigNumber(await this.token.balanceOf(userOne));
+      const amountWrapped = wrapped(BigNumber("10000000000"));
+
+      await burnTokens(this.app, userOne, POLKADOT_ADDRESS, amountWrapped, ChannelId.Basic).should.be.fulfilled;
+
+      await this.app.acknowledge(ChannelId.Basic, 1, true, { from: owner }).should.be.fulfilled;
+      await this.app.acknowledge(ChannelId.Basic, 1, false, { from: owner }).should.be.fulfilled;
+
+      BigNumber(await this.token.balanceOf(userOne))
+        .should.be.bignumber.equal(beforeUserBalance.minus(amountWrapped));
+    });
   });
 
   describe("upgradeability", function () {
@@ -210,6 +210,57 @@ describe("ERC20App", function () {
     });
 
   });
+
+  describe("acknowledgements", function () {
+    beforeEach(async function () {
+      let outboundChannel = await MockOutboundChannel.new()
+      this.app = await deployAppWithMockChannels(owner, [inboundChannel, outboundChannel.address], ERC20App);
+      this.token = await TestToken.new("Test Token", "TEST");
+
+      await this.token.mint(userOne, "10000").should.be.fulfilled;
+      await approveFunds(this.token, this.app, userOne, 200).should.be.fulfilled;
+    });
+
+    it("should refund the sender when the mint failed", async function () {
+      // The first lock of a token also sends a message to create it, with nonce 1
+      await lockupFunds(this.app, this.token, userOne, POLKADOT_ADDRESS, 200, ChannelId.Basic, 0, 0)
+        .should.be.fulfilled;
+      BigNumber(await this.token.balanceOf(userOne)).should.be.bignumber.equal(9800);
+
+      const creation = await this.app.acknowledge(ChannelId.Basic, 1, false, { from: inboundChannel })
+        .should.be.fulfilled;
+      creation.logs.length.should.be.equal(0);
+
+      const tx = await this.app.acknowledge(ChannelId.Basic, 2, false, { from: inboundChannel })
+        .should.be.fulfilled;
+
+      const event = tx.logs.find(e => e.event === "Refunded");
+      event.args.token.should.be.equal(this.token.address);
+      event.args.sender.should.be.equal(userOne);
+      BigNumber(event.args.amount).should.be.bignumber.equal(200);
+
+      BigNumber(await this.token.balanceOf(userOne)).should.be.bignumber.equal(10000);
+      BigNumber(await this.app.balances(this.token.address)).should.be.bignumber.equal(0);
+    });
+
+    it("should keep the funds locked when the mint succeeded", async function () {
+      await lockupFunds(this.app, this.token, userOne, POLKADOT_ADDRESS, 200, ChannelId.Basic, 0, 0)
+        .should.be.fulfilled;
+
+      await this.app.acknowledge(ChannelId.Basic, 2, true, { from: userOne })
+        .should.be.rejectedWith(/AccessControl/);
+
+      const { logs } = await this.app.acknowledge(ChannelId.Basic, 2, true, { from: inboundChannel })
+        .should.be.fulfilled;
+      logs.length.should.be.equal(0);
+
+      // Later acknowledgements of the message have no effect
+      await this.app.acknowledge(ChannelId.Basic, 2, false, { from: inboundChannel })
+        .should.be.fulfilled;
+      BigNumber(await this.token.balanceOf(userOne)).should.be.bignumber.equal(9800);
+      BigNumber(await this.app.balances(this.token.address)).should.be.bignumber.equal(200);
+    });
+  });
   describe("upgradeability", function () {
     beforeEach(async function () {
       this.outboundChannel = await MockOutboundChannel.new()
@@ -173,6 +173,53 @@ describe("ETHApp", function () {
     });
   });
 
+  describe("acknowledgements", function () {
+    beforeEach(async function () {
+      let outboundChannel = await MockOutboundChannel.new()
+      this.app = await deployAppWithMockChannels(owner, [inboundChannel, outboundChannel.address], ETHApp, inboundChannel);
+    });
+
+    it("should refund the sender when the mint failed", async function () {
+      const amount = BigNumber(web3.utils.toWei("1", "ether"));
+      await lockupFunds(this.app, userOne, POLKADOT_ADDRESS, amount, ChannelId.Basic, 0, 0)
+        .should.be.fulfilled;
+
+      await this.app.acknowledge(ChannelId.Basic, 1, false, { from: userOne })
+        .should.be.rejectedWith(/AccessControl/);
+
+      const beforeBalance = BigNumber(await web3.eth.getBalance(this.app.address));
+      const tx = await this.app.acknowledge(ChannelId.Basic, 1, false, { from: inboundChannel })
+        .should.be.fulfilled;
+
+      const event = tx.logs.find(e => e.event === "Refunded");
+      event.args.sender.should.be.equal(userOne);
+      BigNumber(event.args.amount).should.be.bignumber.equal(amount);
+
+      const afterBalance = BigNumber(await web3.eth.getBalance(this.app.address));
+      afterBalance.should.be.bignumber.equal(beforeBalance.minus(amount));
+
+      // A lock is only refunded once
+      const { logs } = await this.app.acknowledge(ChannelId.Basic, 1, false, { from: inboundChannel })
+        .should.be.fulfilled;
+      logs.length.should.be.equal(0);
+      BigNumber(await web3.eth.getBalance(this.app.address)).should.be.bignumber.equal(afterBalance);
+    });
+
+    it("should keep the funds locked when the mint succeeded", async function () {
+      const amount = BigNumber(web3.utils.toWei("1", "ether"));
+      await lockupFunds(this.app, userOne, POLKADOT_ADDRESS, amount, ChannelId.Incentivized, 0, 0)
+        .should.be.fulfilled;
+
+      const { logs } = await this.app.acknowledge(ChannelId.Incentivized, 1, true, { from: inboundChannel })
+        .should.be.fulfilled;
+      logs.length.should.be.equal(0);
+
+      const locked = await this.app.unacknowledged(ChannelId.Incentivized, 1);
+      locked.sender.should.be.equal(ethers.constants.AddressZero);
+      BigNumber(await web3.eth.getBalance(this.app.address)).should.be.bignumber.equal(amount);
+    });
+  });
+
   describe("upgradeability", function () {
     beforeEach(async function () {
       this.outboundChannel = await MockOutboundChannel.new()
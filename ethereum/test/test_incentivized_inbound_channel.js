@@ -35,6 +35,7 @@ describe("IncentivizedInboundChannel", function () {
     const scaleCodec = await ScaleCodec.new();
     await ParachainClient.link(merkleProof);
     await ParachainClient.link(scaleCodec);
+    await IncentivizedInboundChannel.link(merkleProof);
     this.parachainClient = await ParachainClient.new(this.beefyClient.address, 1000);
 
     await runBeefyClientFlow(fixture, this.beefyClient, validatorFixture, numberOfSignatures, numberOfValidators)
@@ -88,4 +89,122 @@ describe("IncentivizedInboundChannel", function () {
     });
 
   });
+
+  describe("submitMessage", function () {
+    beforeEach(async function () {
+      const accounts = await web3.eth.getAccounts();
+      const rewardSource = await MockRewardSource.new();
+      this.channel = await IncentivizedInboundChannel.new(1, this.parachainClient.address,
+        { from: accounts[0] }
+      );
+      await this.channel.initialize(accounts[0], rewardSource.address);
+    });
+
+    const bundle = submitInput.params.bundle;
+    const message = bundle.messages[0];
+    // The bundle has a single message, so its leaf is the root of the messages
+    const leafProof = {
+      root: ethers.utils.keccak256(
+        ethers.utils.defaultAbiCoder.encode(
+          ["tuple(uint64 id, address target, bytes payload)"],
+          [message]
+        )
+      ),
+      position: 0,
+      width: 1,
+      proof: [],
+    };
+
+    it("should accept a proven message and dispatch it", async function () {
+      const { receipt } = await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        leafProof,
+        submitInput.params.proof,
+      ).should.be.fulfilled;
+
+      const event = interface.decodeEventLog(
+        'MessageDispatched(uint64,bool)',
+        receipt.rawLogs[0].data,
+        receipt.rawLogs[0].topics
+      );
+      event.id.eq(ethers.BigNumber.from(message.id)).should.be.true;
+
+      (await this.channel.delivered(message.id)).should.be.true;
+      BigNumber(await this.channel.nonce()).should.be.bignumber.equal(0);
+    });
+
+    it("should refuse to deliver a message twice", async function () {
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        leafProof,
+        submitInput.params.proof,
+      ).should.be.fulfilled;
+
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        leafProof,
+        submitInput.params.proof,
+      ).should.not.be.fulfilled;
+    });
+
+    it("should refuse a message which is not in the committed bundle", async function () {
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        { ...leafProof, position: 1, width: 2 },
+        submitInput.params.proof,
+      ).should.not.be.fulfilled;
+
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        { ...message, id: 1 },
+        leafProof,
+        submitInput.params.proof,
+      ).should.not.be.fulfilled;
+    });
+
+    it("should skip delivered messages when the bundle is submitted", async function () {
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        leafProof,
+        submitInput.params.proof,
+      ).should.be.fulfilled;
+      BigNumber(await this.channel.paidFees(bundle.nonce)).should.be.bignumber.equal(bundle.fee);
+
+      const { receipt } = await this.channel.submit(
+        bundle,
+        submitInput.params.proof,
+      ).should.be.fulfilled;
+
+      receipt.rawLogs.length.should.be.equal(0);
+      (await this.channel.delivered(message.id)).should.be.false;
+      BigNumber(await this.channel.paidFees(bundle.nonce)).should.be.bignumber.equal(0);
+      BigNumber(await this.channel.nonce()).should.be.bignumber.equal(1);
+    });
+
+    it("should refuse messages of a bundle which was already submitted", async function () {
+      await this.channel.submit(
+        bundle,
+        submitInput.params.proof,
+      ).should.be.fulfilled;
+
+      await this.channel.submitMessage(
+        bundle.nonce,
+        bundle.fee,
+        message,
+        leafProof,
+        submitInput.params.proof,
+      ).should.not.be.fulfilled;
+    });
+  });
 });
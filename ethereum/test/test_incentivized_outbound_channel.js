@@ -13,6 +13,7 @@ describe("IncentivizedOutboundChannel", function () {
   let accounts;
   let owner;
   let appAddress;
+  let otherAppAddress;
   let origin;
   const testPayload = ethers.utils.formatBytes32String("arbitrary-payload");
   const iface = new ethers.utils.Interface(IncentivizedOutboundChannel.abi);
@@ -22,13 +23,14 @@ describe("IncentivizedOutboundChannel", function () {
     owner = accounts[0];
     appAddress = accounts[1];
     origin = accounts[2];
+    otherAppAddress = accounts[3];
   });
 
   describe("send", function () {
     beforeEach(async function () {
       this.channel = await IncentivizedOutboundChannel.new();
       const feeSource = await MockFeeSource.new();
-      await this.channel.initialize(owner, feeSource.address, [appAddress]).should.be.fulfilled;
+      await this.channel.initialize(owner, feeSource.address, [appAddress, otherAppAddress]).should.be.fulfilled;
     });
 
     it("should send messages out with the correct event and fields", async function () {
@@ -73,6 +75,25 @@ describe("IncentivizedOutboundChannel", function () {
       event.nonce.eq(ethers.BigNumber.from(3)).should.be.true;
     });
 
+    it("should track nonces per source app", async function () {
+      await this.channel.submit(
+        origin,
+        testPayload,
+        { from: appAddress, value: 0 }
+      ).should.be.fulfilled;
+
+      const { receipt } = await this.channel.submit(
+        origin,
+        testPayload,
+        { from: otherAppAddress, value: 0 }
+      ).should.be.fulfilled;
+
+      const log = receipt.rawLogs[0];
+      const event = iface.decodeEventLog('Message(address,uint64,uint256,bytes)', log.data, log.topics);
+      event.source.should.be.equal(otherAppAddress);
+      event.nonce.eq(ethers.BigNumber.from(1)).should.be.true;
+    });
+
     it("should not send message if user cannot pay fee", async function () {
 
       // Trigger our mock fee source to revert in burnFee.
@@ -0,0 +1,76 @@
+//! RPC interface for the basic outbound channel.
+//!
+//! Committed message bundles are read back from offchain indexing storage, so the node must
+//! be started with `--enable-offchain-indexing=true`.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use parking_lot::RwLock;
+use sp_core::{offchain::OffchainStorage, Bytes, ConstU32, H256};
+use sp_runtime::traits::Keccak256;
+
+use snowbridge_basic_channel::outbound::{offchain_key, MessageBundle};
+use snowbridge_core::merkle::merkle_proof;
+
+/// Bundles are decoded without the runtime limits, which were already enforced on commit.
+type UnboundedMessageBundle = MessageBundle<ConstU32<{ u32::MAX }>, ConstU32<{ u32::MAX }>>;
+
+#[rpc]
+pub trait BasicChannelApi {
+	/// Get the SCALE-encoded message bundle for a commitment.
+	#[rpc(name = "basicOutboundChannel_getCommitment")]
+	fn get_commitment(&self, commitment_hash: H256) -> Result<Option<Bytes>>;
+
+	/// Get a SCALE-encoded Merkle proof for the message at `leaf_index` in a commitment.
+	///
+	/// The proof is against the Merkle root of the bundle, which is hashed together with the
+	/// source channel and nonce of the bundle to form the commitment hash.
+	#[rpc(name = "basicOutboundChannel_getMerkleProof")]
+	fn get_merkle_proof(&self, commitment_hash: H256, leaf_index: u64) -> Result<Option<Bytes>>;
+}
+
+pub struct BasicChannel<T: OffchainStorage> {
+	storage: Arc<RwLock<T>>,
+}
+
+impl<T: OffchainStorage> BasicChannel<T> {
+	pub fn new(storage: T) -> Self {
+		Self { storage: Arc::new(RwLock::new(storage)) }
+	}
+
+	fn bundle(&self, commitment_hash: H256) -> Option<Vec<u8>> {
+		self.storage
+			.read()
+			.get(sp_offchain::STORAGE_PREFIX, &offchain_key(commitment_hash))
+	}
+}
+
+impl<T> BasicChannelApi for BasicChannel<T>
+where
+	T: OffchainStorage + 'static,
+{
+	fn get_commitment(&self, commitment_hash: H256) -> Result<Option<Bytes>> {
+		Ok(self.bundle(commitment_hash).map(Into::into))
+	}
+
+	fn get_merkle_proof(&self, commitment_hash: H256, leaf_index: u64) -> Result<Option<Bytes>> {
+		let encoded = match self.bundle(commitment_hash) {
+			Some(encoded) => encoded,
+			None => return Ok(None),
+		};
+		let bundle =
+			UnboundedMessageBundle::decode(&mut encoded.as_slice()).map_err(|err| Error {
+				code: ErrorCode::InternalError,
+				message: "Unable to decode message bundle".into(),
+				data: Some(format!("{:?}", err).into()),
+			})?;
+
+		// Bundles are stored with the version they were committed in, which determines the
+		// encoding of their leaves.
+		Ok(merkle_proof::<Keccak256, _>(bundle.leaves(), leaf_index)
+			.map(|proof| proof.encode().into()))
+	}
+}
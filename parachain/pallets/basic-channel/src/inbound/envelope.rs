@@ -22,7 +22,7 @@ pub struct Envelope {
 	pub channel: H160,
 	/// The application on Ethereum where the message originated from.
 	pub source: H160,
-	/// A nonce for enforcing replay protection and ordering.
+	/// A nonce for enforcing replay protection and ordering of messages from `source`.
 	pub nonce: u64,
 	/// The inner payload generated from the source application.
 	pub payload: Vec<u8>,
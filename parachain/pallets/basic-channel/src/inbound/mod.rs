@@ -8,14 +8,22 @@ pub mod weights;
 #[cfg(test)]
 mod test;
 
+use frame_support::weights::Weight;
 use frame_system::ensure_signed;
-use snowbridge_core::{ChannelId, Message, MessageDispatch, MessageId, Verifier};
-use sp_core::H160;
+use snowbridge_core::{
+	BridgeComponent, ChannelId, ChannelSource, CircuitBreaker, EthereumTime, Message,
+	MessageDispatch, MessageId, Verifier,
+};
+use snowbridge_ethereum::Log;
+use sp_core::{H160, H256};
 use sp_std::convert::TryFrom;
 
 use envelope::Envelope;
 pub use weights::WeightInfo;
 
+/// Weight of verifying and accepting a message, excluding its dispatch.
+const SUBMIT_WEIGHT: Weight = 100_000_000;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -23,11 +31,18 @@ pub mod pallet {
 
 	use super::*;
 
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{unhashed, StoragePrefixedMap},
+	};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -40,15 +55,55 @@ pub mod pallet {
 		/// Verifier module for message verification.
 		type MessageDispatch: MessageDispatch<Self, MessageId>;
 
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
+		/// Outbound channel contract on Ethereum which messages on the basic channel are sent
+		/// from.
+		type ChannelSource: ChannelSource;
+
+		/// Time on Ethereum, used to reject messages which may have been accepted before nonces
+		/// were tracked per source application.
+		type EthereumTime: EthereumTime;
+
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				// Nonces used to be a single value, stored where the map prefix now is.
+				// Sources start from zero on a freshly deployed outbound channel contract.
+				unhashed::kill(&<Nonce<T>>::final_prefix());
+				// Messages accepted with the single nonce are all in finalized blocks. They are
+				// rejected from now on, so that they cannot be replayed with per-source nonces.
+				<LegacyMessagesUntil<T>>::set(T::EthereumTime::finalized_timestamp());
+				STORAGE_VERSION.put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(2, 3)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
 
 	#[pallet::event]
-	pub enum Event<T> {}
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Message has been verified and accepted for dispatch.
+		MessageReceived {
+			channel: ChannelId,
+			/// The application on Ethereum which sent the message.
+			source: H160,
+			nonce: u64,
+			relayer: T::AccountId,
+			/// Hash of the Ethereum block containing the message.
+			block_hash: H256,
+			/// Index of the transaction within that block.
+			tx_index: u32,
+		},
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -58,54 +113,49 @@ pub mod pallet {
 		InvalidEnvelope,
 		/// Message has an unexpected nonce.
 		InvalidNonce,
+		/// Message is in an Ethereum block from before nonces were tracked per source application.
+		LegacyMessage,
 	}
 
-	/// Source channel on the ethereum side
+	/// Source channel on the ethereum side, from before channels were registered in the channel
+	/// registry. Only read when the registry is seeded.
 	#[pallet::storage]
-	#[pallet::getter(fn source_channel)]
 	pub type SourceChannel<T: Config> = StorageValue<_, H160, ValueQuery>;
 
+	/// Nonce of the last message received from each source application on Ethereum.
 	#[pallet::storage]
-	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
-
-	#[pallet::genesis_config]
-	pub struct GenesisConfig {
-		pub source_channel: H160,
-	}
+	pub type Nonce<T: Config> = StorageMap<_, Blake2_128Concat, H160, u64, ValueQuery>;
 
-	#[cfg(feature = "std")]
-	impl Default for GenesisConfig {
-		fn default() -> Self {
-			Self { source_channel: Default::default() }
-		}
-	}
-
-	#[pallet::genesis_build]
-	impl<T: Config> GenesisBuild<T> for GenesisConfig {
-		fn build(&self) {
-			<SourceChannel<T>>::put(self.source_channel);
-		}
-	}
+	/// Timestamp of the latest finalized Ethereum block when nonces started to be tracked per
+	/// source application. Messages in blocks up to it are rejected.
+	#[pallet::storage]
+	pub type LegacyMessagesUntil<T: Config> = StorageValue<_, u64, OptionQuery>;
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(100_000_000)]
-		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResult {
-			ensure_signed(origin)?;
+		#[pallet::weight(SUBMIT_WEIGHT.saturating_add(Pallet::<T>::dispatch_weight(message)))]
+		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResultWithPostInfo {
+			let relayer = ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::InboundChannel(ChannelId::BASIC))?;
+
 			// submit message to verifier for verification
-			let log = T::Verifier::verify(&message)?;
+			let (log, info) = T::Verifier::verify(&message)?;
 
 			// Decode log into an Envelope
 			let envelope = Envelope::try_from(log).map_err(|_| Error::<T>::InvalidEnvelope)?;
 
-			// Verify that the message was submitted to us from a known
-			// outbound channel on the ethereum side
-			if envelope.channel != <SourceChannel<T>>::get() {
+			// Verify that the message was submitted to us from the outbound channel
+			// registered for the basic channel on the ethereum side
+			if T::ChannelSource::source(ChannelId::BASIC) != Some(envelope.channel) {
 				return Err(Error::<T>::InvalidSourceChannel.into())
 			}
 
+			if let Some(until) = <LegacyMessagesUntil<T>>::get() {
+				ensure!(info.timestamp > until, Error::<T>::LegacyMessage);
+			}
+
 			// Verify message nonce
-			<Nonce<T>>::try_mutate(|nonce| -> DispatchResult {
+			<Nonce<T>>::try_mutate(envelope.source, |nonce| -> DispatchResult {
 				if envelope.nonce != *nonce + 1 {
 					Err(Error::<T>::InvalidNonce.into())
 				} else {
@@ -114,10 +164,33 @@ pub mod pallet {
 				}
 			})?;
 
-			let message_id = MessageId::new(ChannelId::Basic, envelope.nonce);
-			T::MessageDispatch::dispatch(envelope.source, message_id, &envelope.payload);
+			Self::deposit_event(Event::MessageReceived {
+				channel: ChannelId::BASIC,
+				source: envelope.source,
+				nonce: envelope.nonce,
+				relayer,
+				block_hash: info.block_hash,
+				tx_index: message.proof.receipt_proof().tx_index,
+			});
+
+			let message_id = MessageId::new(ChannelId::BASIC, envelope.source, envelope.nonce);
+			let dispatch_weight =
+				T::MessageDispatch::dispatch(envelope.source, message_id, &envelope.payload);
+
+			Ok(Some(SUBMIT_WEIGHT.saturating_add(dispatch_weight)).into())
+		}
+	}
 
-			Ok(())
+	impl<T: Config> Pallet<T> {
+		/// Weight declared by the call carried in `message`.
+		///
+		/// Messages which cannot be decoded get no extra weight, as they will fail verification.
+		fn dispatch_weight(message: &Message) -> Weight {
+			rlp::decode::<Log>(&message.data)
+				.ok()
+				.and_then(|log| Envelope::try_from(log).ok())
+				.map(|envelope| T::MessageDispatch::dispatch_weight(&envelope.payload))
+				.unwrap_or(0)
 		}
 	}
 }
@@ -2,9 +2,10 @@ use super::*;
 
 use frame_support::{
 	assert_noop, assert_ok,
-	dispatch::DispatchError,
+	dispatch::{DispatchError, DispatchResult},
 	parameter_types,
-	traits::{Everything, GenesisBuild},
+	traits::{Everything, OnRuntimeUpgrade, StorageVersion},
+	weights::GetDispatchInfo,
 };
 use sp_core::{H160, H256};
 use sp_keyring::AccountKeyring as Keyring;
@@ -15,8 +16,8 @@ use sp_runtime::{
 };
 use sp_std::convert::From;
 
-use snowbridge_core::{Message, MessageDispatch, Proof};
-use snowbridge_ethereum::{Header as EthereumHeader, Log, U256};
+use snowbridge_core::{Message, MessageDispatch, Proof, ReceiptProof, VerificationInfo};
+use snowbridge_ethereum::Log;
 
 use hex_literal::hex;
 
@@ -69,25 +70,49 @@ impl frame_system::Config for Test {
 	type OnSetCode = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
-// Mock verifier
+parameter_types! {
+	pub static MessageTimestamp: u64 = 0;
+	pub static EthereumTimestamp: Option<u64> = None;
+}
+
+// Mock verifier, which places every message in a block with timestamp `MessageTimestamp`
 pub struct MockVerifier;
 
 impl Verifier for MockVerifier {
-	fn verify(message: &Message) -> Result<Log, DispatchError> {
+	fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
 		let log: Log = rlp::decode(&message.data).unwrap();
-		Ok(log)
+		let info = VerificationInfo {
+			block_number: 0,
+			block_hash: message.proof.receipt_proof().block_hash,
+			timestamp: MessageTimestamp::get(),
+			log_index: 0,
+		};
+		Ok((log, info))
 	}
+}
 
-	fn initialize_storage(_: Vec<EthereumHeader>, _: U256, _: u8) -> Result<(), &'static str> {
-		Ok(())
+pub struct MockEthereumTime;
+
+impl EthereumTime for MockEthereumTime {
+	fn finalized_timestamp() -> Option<u64> {
+		EthereumTimestamp::get()
 	}
 }
 
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
+const DECLARED_DISPATCH_WEIGHT: Weight = 1_000_000;
+const ACTUAL_DISPATCH_WEIGHT: Weight = 400_000;
+
 impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
-	fn dispatch(_: H160, _: MessageId, _: &[u8]) {}
+	fn dispatch(_: H160, _: MessageId, _: &[u8]) -> Weight {
+		ACTUAL_DISPATCH_WEIGHT
+	}
+
+	fn dispatch_weight(_: &[u8]) -> Weight {
+		DECLARED_DISPATCH_WEIGHT
+	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn successful_dispatch_event(_: MessageId) -> Option<<Test as frame_system::Config>::Event> {
@@ -95,29 +120,64 @@ impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
 	}
 }
 
+parameter_types! {
+	pub static InboundPaused: bool = false;
+}
+
+// Pauses inbound submission on the basic channel when `InboundPaused` is set
+pub struct MockCircuitBreaker;
+
+impl CircuitBreaker for MockCircuitBreaker {
+	fn ensure_active(component: BridgeComponent) -> DispatchResult {
+		if component == BridgeComponent::InboundChannel(ChannelId::BASIC) && InboundPaused::get() {
+			return Err(DispatchError::Other("paused"))
+		}
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub static RegisteredSource: Option<H160> = None;
+}
+
+// Registers `RegisteredSource` for the basic channel only
+pub struct MockChannelSource;
+
+impl ChannelSource for MockChannelSource {
+	fn source(channel_id: ChannelId) -> Option<H160> {
+		if channel_id == ChannelId::BASIC {
+			RegisteredSource::get()
+		} else {
+			None
+		}
+	}
+}
+
 impl basic_inbound_channel::Config for Test {
 	type Event = Event;
 	type Verifier = MockVerifier;
 	type MessageDispatch = MockMessageDispatch;
+	type CircuitBreaker = MockCircuitBreaker;
+	type ChannelSource = MockChannelSource;
+	type EthereumTime = MockEthereumTime;
 	type WeightInfo = ();
 }
 
 pub fn new_tester(source_channel: H160) -> sp_io::TestExternalities {
-	new_tester_with_config(basic_inbound_channel::GenesisConfig { source_channel })
-}
-
-pub fn new_tester_with_config(
-	config: basic_inbound_channel::GenesisConfig,
-) -> sp_io::TestExternalities {
-	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-
-	GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+	RegisteredSource::set(Some(source_channel));
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	let mut ext: sp_io::TestExternalities = storage.into();
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
 
+// The source application address for MESSAGE_DATA_0 and MESSAGE_DATA_1
+const SOURCE_APP_ADDR: [u8; 20] = hex!["0a42cba2b7960a0ce216ade5d6a82574257023d8"];
+
+// The source application address for MESSAGE_DATA_2
+const OTHER_SOURCE_APP_ADDR: [u8; 20] = hex!["89b4ab1ef20763630df9743acf155865600daff2"];
+
 // The originating channel address for the messages below
 const SOURCE_CHANNEL_ADDR: [u8; 20] = hex!["2d02f2234d0B6e35D8d8fD77705f535ACe681327"];
 
@@ -163,6 +223,27 @@ const MESSAGE_DATA_1: [u8; 284] = hex!(
 "
 );
 
+// Ethereum Log:
+//   address: 0xe4ab635d0bdc5668b3fcb4eaee1dec587998f4af (outbound channel contract)
+//   topics: ...
+//   data:
+//     source: 0x89b4ab1ef20763630df9743acf155865600daff2  (another app contract)
+//     nonce: 1
+//     payload ...
+const MESSAGE_DATA_2: [u8; 284] = hex!(
+	"
+	f90119942d02f2234d0b6e35d8d8fd77705f535ace681327e1a0779b38144a38
+	cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000
+	000000000000000089b4ab1ef20763630df9743acf155865600daff200000000
+	0000000000000000000000000000000000000000000000000000000100000000
+	0000000000000000000000000000000000000000000000000000006000000000
+	000000000000000000000000000000000000000000000000000000570c018213
+	dae5f9c236beab905c8305cb159c5fa1aae500d43593c715fdd31c61141abd04
+	a99fd6822c8558854ccde39a5684e7a56da27d0000d9e9ac2d78030000000000
+	00000000000000000000000000000000000000000000000000000000
+"
+);
+
 #[test]
 fn test_submit_with_invalid_source_channel() {
 	new_tester(H160::zero()).execute_with(|| {
@@ -172,11 +253,11 @@ fn test_submit_with_invalid_source_channel() {
 		// Submit message
 		let message = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_noop!(
 			BasicInboundChannel::submit(origin.clone(), message.clone()),
@@ -185,6 +266,55 @@ fn test_submit_with_invalid_source_channel() {
 	});
 }
 
+#[test]
+fn test_submit_on_unregistered_channel() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		RegisteredSource::set(None);
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+		assert_noop!(
+			BasicInboundChannel::submit(origin, message),
+			Error::<Test>::InvalidSourceChannel
+		);
+	});
+}
+
+#[test]
+fn test_submit_when_paused() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+
+		InboundPaused::set(true);
+		assert_noop!(
+			BasicInboundChannel::submit(origin.clone(), message.clone()),
+			DispatchError::Other("paused")
+		);
+
+		InboundPaused::set(false);
+		assert_ok!(BasicInboundChannel::submit(origin, message));
+		assert_eq!(<Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR)), 1);
+	});
+}
+
 #[test]
 fn test_submit() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
@@ -194,31 +324,59 @@ fn test_submit() {
 		// Submit message 1
 		let message_1 = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_1));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 1);
 
 		// Submit message 2
 		let message_2 = Message {
 			data: MESSAGE_DATA_1.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_2));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 2);
 	});
 }
 
+#[test]
+fn test_submit_emits_message_received() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer.clone());
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: H256::repeat_byte(1),
+				tx_index: 3,
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(BasicInboundChannel::submit(origin, message));
+
+		let expected = Event::BasicInboundChannel(basic_inbound_channel::Event::MessageReceived {
+			channel: ChannelId::BASIC,
+			source: SOURCE_APP_ADDR.into(),
+			nonce: 1,
+			relayer: relayer.clone(),
+			block_hash: H256::repeat_byte(1),
+			tx_index: 3,
+		});
+		assert!(System::events().iter().any(|record| record.event == expected));
+	});
+}
+
 #[test]
 fn test_submit_with_invalid_nonce() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
@@ -228,14 +386,14 @@ fn test_submit_with_invalid_nonce() {
 		// Submit message
 		let message = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(BasicInboundChannel::submit(origin.clone(), message.clone()));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 1);
 
 		// Submit the same again
@@ -245,3 +403,97 @@ fn test_submit_with_invalid_nonce() {
 		);
 	});
 }
+
+#[test]
+fn test_submit_with_independent_source_nonces() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message_1 = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_1));
+
+		// Nonce 1 is accepted again, since it comes from a different source
+		let message_2 = Message {
+			data: MESSAGE_DATA_2.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(BasicInboundChannel::submit(origin.clone(), message_2.clone()));
+
+		assert_eq!(<Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR)), 1);
+		assert_eq!(<Nonce<Test>>::get(H160::from(OTHER_SOURCE_APP_ADDR)), 1);
+
+		assert_noop!(
+			BasicInboundChannel::submit(origin.clone(), message_2),
+			Error::<Test>::InvalidNonce
+		);
+	});
+}
+
+#[test]
+fn test_submit_charges_dispatch_weight() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+
+		let call = crate::inbound::Call::<Test>::submit { message: message.clone() };
+		assert_eq!(call.get_dispatch_info().weight, SUBMIT_WEIGHT + DECLARED_DISPATCH_WEIGHT);
+
+		let post_info = BasicInboundChannel::submit(origin, message).unwrap();
+		assert_eq!(post_info.actual_weight, Some(SUBMIT_WEIGHT + ACTUAL_DISPATCH_WEIGHT));
+	});
+}
+
+#[test]
+fn test_submit_rejects_messages_from_before_migration() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		// Storage from before nonces were tracked per source application
+		StorageVersion::new(0).put::<BasicInboundChannel>();
+		EthereumTimestamp::set(Some(100));
+		BasicInboundChannel::on_runtime_upgrade();
+		assert_eq!(<LegacyMessagesUntil<Test>>::get(), Some(100));
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+
+		// The message may have been accepted with the single nonce used before
+		MessageTimestamp::set(100);
+		assert_noop!(
+			BasicInboundChannel::submit(origin.clone(), message.clone()),
+			Error::<Test>::LegacyMessage
+		);
+
+		MessageTimestamp::set(101);
+		assert_ok!(BasicInboundChannel::submit(origin, message));
+		assert_eq!(<Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR)), 1);
+	});
+}
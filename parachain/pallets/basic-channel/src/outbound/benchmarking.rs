@@ -21,6 +21,7 @@ benchmarks! {
 			<MessageQueue<T>>::try_append(Message {
 				id: 0u64,
 				target: H160::zero(),
+				expires_at: None,
 				payload: payload.try_into().unwrap(),
 			}).unwrap();
 		}
@@ -38,6 +39,7 @@ benchmarks! {
 		<MessageQueue<T>>::try_append(Message {
 			id: 0u64,
 			target: H160::zero(),
+			expires_at: None,
 			payload: vec![1u8; T::MaxMessagePayloadSize::get() as usize].try_into().unwrap(),
 		}).unwrap();
 
@@ -58,7 +60,7 @@ benchmarks! {
 
 	}: { BasicOutboundChannel::<T>::on_initialize(block_number) }
 
-	set_principal {
+	add_principal {
 		let authorized_origin = match T::SetPrincipalOrigin::successful_origin().into() {
 			Ok(raw) => raw,
 			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
@@ -66,7 +68,33 @@ benchmarks! {
 		let alice = T::Lookup::unlookup(account("alice", 0, SEED));
 	}: _(authorized_origin, alice)
 	verify {
-		assert_eq!(<Principal<T>>::get(), Some(account("alice", 0, SEED)));
+		assert!(<Principals<T>>::contains_key(account::<T::AccountId>("alice", 0, SEED)));
+	}
+
+	remove_principal {
+		let authorized_origin = match T::SetPrincipalOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+		<Principals<T>>::insert(account::<T::AccountId>("alice", 0, SEED), ());
+		let alice = T::Lookup::unlookup(account("alice", 0, SEED));
+	}: _(authorized_origin, alice)
+	verify {
+		assert!(!<Principals<T>>::contains_key(account::<T::AccountId>("alice", 0, SEED)));
+	}
+
+	set_commitment_versions {
+		let authorized_origin = match T::SetCommitmentVersionsOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+		let versions = vec![CommitmentVersion::V2, CommitmentVersion::V0];
+	}: _(authorized_origin, versions)
+	verify {
+		assert_eq!(
+			<CommitmentVersions<T>>::get().into_inner(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		);
 	}
 }
 
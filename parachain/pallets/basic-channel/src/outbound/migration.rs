@@ -0,0 +1,41 @@
+//! Storage migrations for the basic outbound channel pallet.
+
+pub mod v1 {
+	use crate::outbound::{Config, Pallet, Principals};
+	use frame_support::{
+		log,
+		traits::{Get, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	use sp_std::{marker::PhantomData, prelude::*};
+
+	/// Authorize the accounts in `Accounts`, such as the accounts of app pallets, as principals
+	/// on chains which were running before the channel accepted more than one principal.
+	///
+	/// Without it, apps cannot submit messages to the channel after the upgrade until they are
+	/// added. It must run before the pallet's own upgrade hook, which moves the single principal
+	/// into [`Principals`] and bumps the storage version.
+	pub struct AddPrincipals<T, Accounts>(PhantomData<(T, Accounts)>);
+
+	impl<T, Accounts> OnRuntimeUpgrade for AddPrincipals<T, Accounts>
+	where
+		T: Config,
+		Accounts: Get<Vec<T::AccountId>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let accounts = Accounts::get();
+			let added = accounts.len() as Weight;
+			for account in accounts {
+				<Principals<T>>::insert(account, ());
+			}
+
+			log::info!(target: "basic-outbound-channel", "Added {} principals", added);
+
+			T::DbWeight::get().reads_writes(1, added)
+		}
+	}
+}
@@ -1,3 +1,4 @@
+pub mod migration;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -10,20 +11,31 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use ethabi::{self, Token};
 use frame_support::{
 	dispatch::DispatchResult,
-	ensure,
+	ensure, log,
 	traits::{EnsureOrigin, Get},
+	weights::Weight,
 	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::TypeInfo;
-use sp_core::{H160, H256};
-use sp_runtime::traits::{Hash, StaticLookup, Zero};
-
-use sp_std::prelude::*;
-
-use snowbridge_core::{types::AuxiliaryDigestItem, ChannelId};
+use sp_core::{ConstU32, H160, H256};
+use sp_io::offchain_index;
+use sp_runtime::traits::{Hash, One, Saturating, StaticLookup, Zero};
+
+use sp_std::{convert::TryFrom, prelude::*};
+
+use snowbridge_core::{
+	dispatched::MessageDispatched,
+	merkle::{merkle_proof, merkle_root, MerkleProof},
+	types::AuxiliaryDigestItem,
+	BridgeComponent, ChannelId, CircuitBreaker, CommitmentVersion, EthereumTime, MessageOutcome,
+	OutboundChannelState, QueuedMessage, Verifier,
+};
 
 pub use weights::WeightInfo;
 
+/// Weight of verifying and recording a report of the dispatch of a message.
+const REPORT_DISPATCH_WEIGHT: Weight = 100_000_000;
+
 /// Wire-format for committed messages
 #[derive(
 	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
@@ -31,11 +43,13 @@ pub use weights::WeightInfo;
 #[scale_info(skip_type_params(M, N))]
 #[codec(mel_bound())]
 pub struct MessageBundle<M: Get<u32>, N: Get<u32>> {
-	source_channel_id: u8,
+	/// Version of the wire format in which the messages are committed
+	pub version: CommitmentVersion,
+	pub source_channel_id: u8,
 	/// Unique nonce for to prevent replaying bundles
 	#[codec(compact)]
-	nonce: u64,
-	messages: BoundedVec<Message<M>, N>,
+	pub nonce: u64,
+	pub messages: BoundedVec<Message<M>, N>,
 }
 
 #[derive(
@@ -46,16 +60,142 @@ pub struct MessageBundle<M: Get<u32>, N: Get<u32>> {
 pub struct Message<M: Get<u32>> {
 	/// Unique message ID
 	#[codec(compact)]
-	id: u64,
+	pub id: u64,
 	/// Target application on the Ethereum side.
-	target: H160,
+	pub target: H160,
+	/// Ethereum timestamp after which the message can no longer be delivered, if any.
+	pub expires_at: Option<u64>,
 	/// Payload for target application.
-	payload: BoundedVec<u8, M>,
+	pub payload: BoundedVec<u8, M>,
+}
+
+/// Wire-format for messages committed in [`CommitmentVersion::V0`].
+///
+/// The layout predates commitment versions, so that relayers which do not know about versions
+/// can still decode V0 commitments.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(M, N))]
+#[codec(mel_bound())]
+pub struct LegacyMessageBundle<M: Get<u32>, N: Get<u32>> {
+	pub source_channel_id: u8,
+	/// Unique nonce for to prevent replaying bundles
+	#[codec(compact)]
+	pub nonce: u64,
+	pub messages: BoundedVec<LegacyMessage<M>, N>,
+}
+
+/// Message format before commitments were versioned, which is also how messages were queued
+/// before they could expire.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(M))]
+#[codec(mel_bound())]
+pub struct LegacyMessage<M: Get<u32>> {
+	/// Unique message ID
+	#[codec(compact)]
+	pub id: u64,
+	/// Target application on the Ethereum side.
+	pub target: H160,
+	/// Payload for target application.
+	pub payload: BoundedVec<u8, M>,
+}
+
+impl<M: Get<u32>, N: Get<u32>> From<LegacyMessageBundle<M, N>> for MessageBundle<M, N> {
+	fn from(bundle: LegacyMessageBundle<M, N>) -> Self {
+		let messages: Vec<_> = bundle
+			.messages
+			.into_iter()
+			.map(|message| Message {
+				id: message.id,
+				target: message.target,
+				expires_at: None,
+				payload: message.payload,
+			})
+			.collect();
+		MessageBundle {
+			version: CommitmentVersion::V0,
+			source_channel_id: bundle.source_channel_id,
+			nonce: bundle.nonce,
+			messages: messages.try_into().expect("the number of messages is unchanged; qed"),
+		}
+	}
+}
+
+impl<M: Get<u32>, N: Get<u32>> MessageBundle<M, N> {
+	/// The bundle in the wire-format of [`CommitmentVersion::V0`], which does not include the
+	/// version or the expiry of messages.
+	pub fn to_legacy(&self) -> LegacyMessageBundle<M, N> {
+		let messages: Vec<_> = self
+			.messages
+			.iter()
+			.map(|message| LegacyMessage {
+				id: message.id,
+				target: message.target,
+				payload: message.payload.clone(),
+			})
+			.collect();
+		LegacyMessageBundle {
+			source_channel_id: self.source_channel_id,
+			nonce: self.nonce,
+			messages: messages.try_into().expect("the number of messages is unchanged; qed"),
+		}
+	}
+
+	/// ABI-encoded messages, which form the leaves of the Merkle tree in the commitment.
+	pub fn leaves(&self) -> Vec<Vec<u8>> {
+		self.messages
+			.iter()
+			.map(|message| {
+				let mut fields = match self.version {
+					CommitmentVersion::V0 => vec![],
+					version => vec![Token::Uint((version as u8).into())],
+				};
+				fields.extend([Token::Uint(message.id.into()), Token::Address(message.target)]);
+				if self.version.encodes_expiry() {
+					fields.push(Token::Uint(message.expires_at.unwrap_or(0).into()));
+				}
+				fields.push(Token::Bytes(message.payload.to_vec()));
+				ethabi::encode(&[Token::Tuple(fields)])
+			})
+			.collect()
+	}
+
+	/// Hash of the ABI-encoded source channel and nonce of the bundle, followed by the root of the
+	/// Merkle tree over its messages.
+	pub fn commitment_hash<H: Hash<Output = H256>>(&self) -> H256 {
+		let root = merkle_root::<H, _>(self.leaves());
+		H::hash(&ethabi::encode(&[
+			Token::Uint(self.source_channel_id.into()),
+			Token::Uint(self.nonce.into()),
+			Token::FixedBytes(root.as_bytes().to_vec()),
+		]))
+	}
 }
 
+type LegacyMessageOf<T> = LegacyMessage<<T as Config>::MaxMessagePayloadSize>;
+
+/// Prefix for the offchain indexing keys of committed bundles.
+///
+/// Bundles are indexed in the layout of [`MessageBundle`] whatever their version, so that they
+/// can be decoded without knowing it.
+pub const INDEXING_PREFIX: &[u8] = b"basic_outbound_channel::commitment";
+
+/// Offchain indexing key under which the bundle for `commitment_hash` is stored.
+pub fn offchain_key(commitment_hash: H256) -> Vec<u8> {
+	(INDEXING_PREFIX, commitment_hash).encode()
+}
+
+/// Max number of versions in which a bundle is committed at once
+pub type MaxCommitmentVersions = ConstU32<2>;
+
 pub type MessageBundleOf<T> =
 	MessageBundle<<T as Config>::MaxMessagePayloadSize, <T as Config>::MaxMessagesPerCommit>;
 pub type MessageOf<T> = Message<<T as Config>::MaxMessagePayloadSize>;
+pub type LegacyMessageBundleOf<T> =
+	LegacyMessageBundle<<T as Config>::MaxMessagePayloadSize, <T as Config>::MaxMessagesPerCommit>;
 
 pub use pallet::*;
 
@@ -67,8 +207,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -85,8 +228,26 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxMessagesPerCommit: Get<u32>;
 
+		/// Max number of messages waiting to be committed, including those carried over from
+		/// previous intervals
+		#[pallet::constant]
+		type MaxQueuedMessages: Get<u32>;
+
+		/// The origin which may add and remove principals
 		type SetPrincipalOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which may change the versions in which commitments are emitted
+		type SetCommitmentVersionsOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Verifier module for reports of the dispatch of messages on Ethereum.
+		type Verifier: Verifier;
+
+		/// Time on Ethereum, against which the expiry of messages is checked
+		type EthereumTime: EthereumTime;
+
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -95,19 +256,51 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		MessageAccepted(u64),
-		Committed { hash: H256, data: MessageBundleOf<T> },
+		/// Messages were committed in [`CommitmentVersion::V0`].
+		Committed {
+			hash: H256,
+			data: LegacyMessageBundleOf<T>,
+		},
+		/// Account has been authorized to submit messages.
+		PrincipalAdded(T::AccountId),
+		/// Account is no longer authorized to submit messages.
+		PrincipalRemoved(T::AccountId),
+		/// Commitments are now emitted in these versions.
+		CommitmentVersionsSet(Vec<CommitmentVersion>),
+		/// The outcome of a message with an expiry is known, either because it expired before
+		/// it was committed, or from a report of its dispatch on Ethereum.
+		OutcomeRecorded(u64, MessageOutcome),
+		/// Messages were committed in a version after [`CommitmentVersion::V0`].
+		VersionedCommitted {
+			hash: H256,
+			data: MessageBundleOf<T>,
+		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The message payload exceeds byte limit.
 		PayloadTooLarge,
-		/// No more messages can be queued for the channel during this commit cycle.
+		/// No more messages can be queued for the channel until the backlog is committed.
 		QueueSizeLimitReached,
 		/// Cannot increment nonce
 		Overflow,
 		/// Not authorized to send message
 		NotAuthorized,
+		/// The account is not a principal
+		UnknownPrincipal,
+		/// At least one, and at most [`MaxCommitmentVersions`], versions must be given.
+		InvalidCommitmentVersions,
+		/// Messages with an expiry require every commitment version to encode it.
+		ExpiryNotSupported,
+		/// The message has already expired.
+		MessageExpired,
+		/// Report came from an invalid inbound channel on the Ethereum side.
+		InvalidTargetChannel,
+		/// Report is not a `MessageDispatched` log.
+		InvalidDispatchLog,
+		/// No outcome is awaited for the message.
+		UnknownMessage,
 	}
 
 	/// Interval between commitments
@@ -118,12 +311,15 @@ pub mod pallet {
 	/// Messages waiting to be committed.
 	#[pallet::storage]
 	pub(super) type MessageQueue<T: Config> =
-		StorageValue<_, BoundedVec<MessageOf<T>, T::MaxMessagesPerCommit>, ValueQuery>;
+		StorageValue<_, BoundedVec<MessageOf<T>, T::MaxQueuedMessages>, ValueQuery>;
+
+	/// Single authorized account, replaced by [`Principals`] in storage version 1
+	#[pallet::storage]
+	pub(super) type Principal<T: Config> = StorageValue<_, Option<T::AccountId>, ValueQuery>;
 
-	/// Fee for accepting a message
+	/// Accounts authorized to submit messages, such as the accounts of app pallets
 	#[pallet::storage]
-	#[pallet::getter(fn principal)]
-	pub type Principal<T: Config> = StorageValue<_, Option<T::AccountId>, ValueQuery>;
+	pub type Principals<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
 
 	#[pallet::storage]
 	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -131,16 +327,61 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextId<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	#[pallet::type_value]
+	pub(super) fn DefaultCommitmentVersions<T: Config>(
+	) -> BoundedVec<CommitmentVersion, MaxCommitmentVersions> {
+		vec![CommitmentVersion::V0]
+			.try_into()
+			.expect("a single version is within bounds; qed")
+	}
+
+	/// Versions in which each bundle is committed, in ascending order.
+	///
+	/// More than one version is only set while the peer contracts on Ethereum are upgraded from
+	/// the oldest to the newest.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment_versions)]
+	pub type CommitmentVersions<T: Config> = StorageValue<
+		_,
+		BoundedVec<CommitmentVersion, MaxCommitmentVersions>,
+		ValueQuery,
+		DefaultCommitmentVersions<T>,
+	>;
+
+	/// Hash of the most recent commitment in each version it was committed in
+	#[pallet::storage]
+	pub type LatestCommitmentHashes<T: Config> =
+		StorageValue<_, BoundedVec<(CommitmentVersion, H256), MaxCommitmentVersions>, ValueQuery>;
+
+	/// Inbound channel on the ethereum side, which reports the dispatch of messages
+	#[pallet::storage]
+	#[pallet::getter(fn target_channel)]
+	pub type TargetChannel<T: Config> = StorageValue<_, H160, ValueQuery>;
+
+	/// Expiry of committed messages whose outcome is not yet known, by message ID
+	#[pallet::storage]
+	pub type PendingOutcomes<T: Config> = StorageMap<_, Twox64Concat, u64, u64, OptionQuery>;
+
+	/// Known outcomes of messages with an expiry, until they are taken by the sending app
+	#[pallet::storage]
+	#[pallet::getter(fn outcome)]
+	pub type Outcomes<T: Config> = StorageMap<_, Twox64Concat, u64, MessageOutcome, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub interval: T::BlockNumber,
-		pub principal: Option<T::AccountId>,
+		pub principals: Vec<T::AccountId>,
+		pub target_channel: H160,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { interval: Default::default(), principal: Default::default() }
+			Self {
+				interval: Default::default(),
+				principals: Default::default(),
+				target_channel: Default::default(),
+			}
 		}
 	}
 
@@ -148,7 +389,10 @@ pub mod pallet {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			<Interval<T>>::put(self.interval);
-			<Principal<T>>::put(self.principal.clone());
+			<TargetChannel<T>>::put(self.target_channel);
+			for principal in &self.principals {
+				<Principals<T>>::insert(principal, ());
+			}
 		}
 	}
 
@@ -156,8 +400,10 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		// Generate a message commitment every [`Interval`] blocks.
 		//
-		// The commitment hash is included in an [`AuxiliaryDigestItem`] in the block header,
-		// with the corresponding commitment is persisted offchain.
+		// The commitment hash binds the source channel and nonce of the bundle to the root of a
+		// binary Merkle tree over the ABI-encoded messages.
+		// It is included in an [`AuxiliaryDigestItem`] in the block header, and the encoded
+		// bundle is written to offchain indexing storage under [`offchain_key`].
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			if (now % Self::interval()).is_zero() {
 				Self::commit()
@@ -165,37 +411,182 @@ pub mod pallet {
 				T::WeightInfo::on_initialize_non_interval()
 			}
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let version = StorageVersion::get::<Pallet<T>>();
+			if version >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			if version < 1 {
+				if let Some(principal) = <Principal<T>>::take() {
+					<Principals<T>>::insert(principal, ());
+				}
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+			}
+			if version < 2 {
+				// Messages queued before the upgrade do not expire.
+				Self::migrate_queue(|message: LegacyMessageOf<T>| Message {
+					id: message.id,
+					target: message.target,
+					expires_at: None,
+					payload: message.payload,
+				});
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			}
+			STORAGE_VERSION.put::<Pallet<T>>();
+			weight
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(T::WeightInfo::set_principal())]
-		pub fn set_principal(
+		#[pallet::weight(T::WeightInfo::add_principal())]
+		pub fn add_principal(
 			origin: OriginFor<T>,
 			principal: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
 			T::SetPrincipalOrigin::ensure_origin(origin)?;
 			let principal = T::Lookup::lookup(principal)?;
-			<Principal<T>>::put(Some(principal));
+			<Principals<T>>::insert(&principal, ());
+			Self::deposit_event(Event::PrincipalAdded(principal));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_principal())]
+		pub fn remove_principal(
+			origin: OriginFor<T>,
+			principal: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::SetPrincipalOrigin::ensure_origin(origin)?;
+			let principal = T::Lookup::lookup(principal)?;
+			ensure!(Self::is_principal(&principal), Error::<T>::UnknownPrincipal);
+			<Principals<T>>::remove(&principal);
+			Self::deposit_event(Event::PrincipalRemoved(principal));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_commitment_versions())]
+		pub fn set_commitment_versions(
+			origin: OriginFor<T>,
+			mut versions: Vec<CommitmentVersion>,
+		) -> DispatchResult {
+			T::SetCommitmentVersionsOrigin::ensure_origin(origin)?;
+			versions.sort();
+			versions.dedup();
+			let bounded = BoundedVec::<_, MaxCommitmentVersions>::try_from(versions.clone())
+				.map_err(|_| Error::<T>::InvalidCommitmentVersions)?;
+			ensure!(!bounded.is_empty(), Error::<T>::InvalidCommitmentVersions);
+			// Queued messages must not be committed without their expiry.
+			ensure!(
+				bounded.iter().all(|version| version.encodes_expiry()) ||
+					<MessageQueue<T>>::get().iter().all(|message| message.expires_at.is_none()),
+				Error::<T>::ExpiryNotSupported,
+			);
+			<CommitmentVersions<T>>::put(bounded);
+			Self::deposit_event(Event::CommitmentVersionsSet(versions));
+			Ok(())
+		}
+
+		/// Record the outcome of a message with an expiry, from a `MessageDispatched` log of the
+		/// inbound channel on Ethereum.
+		#[pallet::weight(REPORT_DISPATCH_WEIGHT)]
+		pub fn report_dispatch(
+			origin: OriginFor<T>,
+			message: snowbridge_core::Message,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let (log, _) = T::Verifier::verify(&message)?;
+			let dispatched =
+				MessageDispatched::try_from(log).map_err(|_| Error::<T>::InvalidDispatchLog)?;
+			ensure!(
+				dispatched.channel == <TargetChannel<T>>::get(),
+				Error::<T>::InvalidTargetChannel,
+			);
+			<PendingOutcomes<T>>::take(dispatched.id).ok_or(Error::<T>::UnknownMessage)?;
+
+			let outcome = if dispatched.result {
+				MessageOutcome::Delivered
+			} else {
+				MessageOutcome::Undelivered
+			};
+			Self::record_outcome(dispatched.id, outcome);
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
-		/// Submit message on the outbound channel
-		pub fn submit(who: &T::AccountId, target: H160, payload: &[u8]) -> DispatchResult {
-			let principal = Self::principal();
-			ensure!(principal.is_some(), Error::<T>::NotAuthorized,);
-			ensure!(*who == principal.unwrap(), Error::<T>::NotAuthorized,);
+		/// Convert each message in the queue from an older layout with `f`.
+		///
+		/// Messages over the bound of the queue are dropped, as is the whole queue if it cannot
+		/// be decoded. Dropped messages are logged, as they are never delivered.
+		fn migrate_queue<O: Decode>(f: impl Fn(O) -> MessageOf<T>) {
+			let mut dropped = Vec::new();
+			let translated = <MessageQueue<T>>::translate::<Vec<O>, _>(|queue| {
+				let mut messages: Vec<_> = queue?.into_iter().map(&f).collect();
+				let bound = T::MaxQueuedMessages::get() as usize;
+				dropped = messages.split_off(bound.min(messages.len()));
+				Some(
+					BoundedVec::try_from(messages)
+						.expect("messages are truncated to the bound of the queue; qed"),
+				)
+			});
+
+			match translated {
+				Ok(queue) => log::info!(
+					target: "basic-outbound-channel",
+					"Migrated {} queued messages",
+					queue.map_or(0, |queue| queue.len())
+				),
+				Err(_) => {
+					<MessageQueue<T>>::kill();
+					log::error!(
+						target: "basic-outbound-channel",
+						"Dropped the message queue, which could not be decoded"
+					);
+				},
+			}
+			if !dropped.is_empty() {
+				log::error!(
+					target: "basic-outbound-channel",
+					"Dropped queued messages over the bound of the queue: {:?}",
+					dropped.iter().map(|message| message.id).collect::<Vec<_>>()
+				);
+			}
+		}
+
+		/// Submit message on the outbound channel, returning its ID.
+		///
+		/// A message with an `expires_at` timestamp is dropped if it expires before it is
+		/// committed, and is not dispatched on Ethereum after that time.
+		pub fn submit(
+			who: &T::AccountId,
+			target: H160,
+			payload: &[u8],
+			expires_at: Option<u64>,
+		) -> Result<u64, DispatchError> {
+			T::CircuitBreaker::ensure_active(BridgeComponent::OutboundChannel(ChannelId::BASIC))?;
+			ensure!(Self::is_principal(who), Error::<T>::NotAuthorized,);
 			ensure!(
-				<MessageQueue<T>>::decode_len().unwrap_or(0)
-					< T::MaxMessagesPerCommit::get() as usize,
+				Self::backlog_depth() < T::MaxQueuedMessages::get(),
 				Error::<T>::QueueSizeLimitReached,
 			);
 			ensure!(
 				payload.len() <= T::MaxMessagePayloadSize::get() as usize,
 				Error::<T>::PayloadTooLarge,
 			);
+			if expires_at.is_some() {
+				ensure!(
+					Self::commitment_versions().iter().all(|version| version.encodes_expiry()),
+					Error::<T>::ExpiryNotSupported,
+				);
+				ensure!(
+					!Self::is_expired(expires_at, T::EthereumTime::finalized_timestamp()),
+					Error::<T>::MessageExpired,
+				);
+			}
 
 			let next_id = <NextId<T>>::get();
 			if next_id.checked_add(1).is_none() {
@@ -205,6 +596,7 @@ pub mod pallet {
 			<MessageQueue<T>>::try_append(Message {
 				id: next_id,
 				target,
+				expires_at,
 				payload: payload.to_vec().try_into().map_err(|_| Error::<T>::PayloadTooLarge)?,
 			})
 			.map_err(|_| Error::<T>::QueueSizeLimitReached)?;
@@ -212,55 +604,161 @@ pub mod pallet {
 
 			<NextId<T>>::put(next_id + 1);
 
-			Ok(())
+			Ok(next_id)
+		}
+
+		/// Take the outcome of a message with an expiry, once it is known.
+		///
+		/// A committed message is only known to be undelivered from a report of the inbound
+		/// channel on Ethereum, which does not dispatch expired messages. Until then it may have
+		/// been delivered, however long ago it expired.
+		pub fn take_outcome(id: u64) -> Option<MessageOutcome> {
+			<Outcomes<T>>::take(id)
+		}
+
+		/// Whether `who` is authorized to submit messages.
+		pub fn is_principal(who: &T::AccountId) -> bool {
+			<Principals<T>>::contains_key(who)
+		}
+
+		/// Number of messages waiting to be committed.
+		pub fn backlog_depth() -> u32 {
+			<MessageQueue<T>>::decode_len().unwrap_or(0) as u32
 		}
 
 		fn commit() -> Weight {
-			let messages = <MessageQueue<T>>::take();
-			if messages.is_empty() {
+			let mut queue = <MessageQueue<T>>::take().into_inner();
+			if queue.is_empty() {
 				return T::WeightInfo::on_initialize_no_messages();
 			}
 
+			// Messages which expired while queued are dropped, as they cannot be delivered.
+			let now = T::EthereumTime::finalized_timestamp();
+			let queued = queue.len();
+			queue.retain(|message| {
+				if Self::is_expired(message.expires_at, now) {
+					Self::record_outcome(message.id, MessageOutcome::Undelivered);
+					false
+				} else {
+					true
+				}
+			});
+			let expired_weight = T::DbWeight::get().writes((queued - queue.len()) as Weight);
+			if queue.is_empty() {
+				return T::WeightInfo::on_initialize_no_messages().saturating_add(expired_weight);
+			}
+
+			// Messages which do not fit in this bundle are carried over to the next interval.
+			let backlog = queue.split_off(queue.len().min(T::MaxMessagesPerCommit::get() as usize));
+			if !backlog.is_empty() {
+				<MessageQueue<T>>::put(
+					BoundedVec::<_, T::MaxQueuedMessages>::try_from(backlog)
+						.expect("backlog is a subset of the bounded queue; qed"),
+				);
+			}
+			let messages = BoundedVec::<_, T::MaxMessagesPerCommit>::try_from(queue)
+				.expect("bundle is truncated to MaxMessagesPerCommit; qed");
+
+			// The outcome of committed messages with an expiry is awaited from Ethereum.
+			let mut expiring = 0;
+			for message in messages.iter() {
+				if let Some(expires_at) = message.expires_at {
+					<PendingOutcomes<T>>::insert(message.id, expires_at);
+					expiring += 1;
+				}
+			}
+
 			let nonce = <Nonce<T>>::get();
 			let next_nonce = nonce.saturating_add(1);
 			<Nonce<T>>::put(next_nonce);
 
-			let bundle = MessageBundle {
-				source_channel_id: ChannelId::Basic as u8,
-				nonce: next_nonce,
-				messages: messages.clone(),
-			};
-
-			let commitment_hash = Self::make_commitment_hash(&bundle);
-			let digest_item =
-				AuxiliaryDigestItem::Commitment(ChannelId::Basic, commitment_hash.clone()).into();
-			<frame_system::Pallet<T>>::deposit_log(digest_item);
-			Self::deposit_event(Event::Committed { hash: commitment_hash, data: bundle });
+			// The same messages are committed once in each version, so that peer contracts on
+			// either side of an upgrade can verify them.
+			let versions = <CommitmentVersions<T>>::get();
+			let mut hashes = BoundedVec::<_, MaxCommitmentVersions>::default();
+			for version in versions.iter().copied() {
+				let bundle = MessageBundle {
+					version,
+					source_channel_id: ChannelId::BASIC.0,
+					nonce: next_nonce,
+					messages: messages.clone(),
+				};
+
+				let commitment_hash = Self::make_commitment_hash(&bundle);
+				let digest_item =
+					AuxiliaryDigestItem::commitment(ChannelId::BASIC, version, commitment_hash)
+						.into();
+				<frame_system::Pallet<T>>::deposit_log(digest_item);
+				hashes
+					.try_push((version, commitment_hash))
+					.expect("one hash per commitment version is within bounds; qed");
+				offchain_index::set(&offchain_key(commitment_hash), &bundle.encode());
+				let event = match version {
+					CommitmentVersion::V0 =>
+						Event::Committed { hash: commitment_hash, data: bundle.to_legacy() },
+					_ => Event::VersionedCommitted { hash: commitment_hash, data: bundle },
+				};
+				Self::deposit_event(event);
+			}
+			<LatestCommitmentHashes<T>>::put(hashes);
 
 			T::WeightInfo::on_initialize(
 				messages.len() as u32,
 				Self::average_payload_size(&messages),
 			)
+			.saturating_mul(versions.len() as Weight)
+			.saturating_add(expired_weight)
+			.saturating_add(T::DbWeight::get().writes(expiring))
 		}
 
-		fn make_commitment_hash(bundle: &MessageBundleOf<T>) -> H256 {
-			let messages: Vec<Token> = bundle
-				.messages
-				.iter()
-				.map(|message| {
-					Token::Tuple(vec![
-						Token::Uint(message.id.into()),
-						Token::Address(message.target),
-						Token::Bytes(message.payload.to_vec()),
-					])
+		/// Whether a message expiring at `expires_at` can no longer be delivered on Ethereum,
+		/// where `now` is the timestamp of the latest finalized Ethereum block.
+		fn is_expired(expires_at: Option<u64>, now: Option<u64>) -> bool {
+			match (expires_at, now) {
+				(Some(expires_at), Some(now)) => now > expires_at,
+				_ => false,
+			}
+		}
+
+		fn record_outcome(id: u64, outcome: MessageOutcome) {
+			<Outcomes<T>>::insert(id, outcome);
+			Self::deposit_event(Event::OutcomeRecorded(id, outcome));
+		}
+
+		/// Messages waiting to be committed, in the order they will be committed.
+		pub fn queued_messages() -> Vec<QueuedMessage> {
+			<MessageQueue<T>>::get()
+				.into_iter()
+				.map(|message| QueuedMessage {
+					id: message.id,
+					target: message.target,
+					fee: 0,
+					expires_at: message.expires_at,
+					payload: message.payload.into_inner(),
 				})
-				.collect();
-			let commitment = ethabi::encode(&vec![Token::Tuple(vec![
-				Token::Uint(bundle.source_channel_id.into()),
-				Token::Uint(bundle.nonce.into()),
-				Token::Array(messages),
-			])]);
-			<T as Config>::Hashing::hash(&commitment)
+				.collect()
+		}
+
+		/// Commitment state of the channel as of the current block.
+		pub fn channel_state() -> OutboundChannelState<T::BlockNumber> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let interval = Self::interval().max(One::one());
+			OutboundChannelState {
+				next_commitment_block: now.saturating_sub(now % interval).saturating_add(interval),
+				nonce: <Nonce<T>>::get(),
+				latest_commitment_hashes: <LatestCommitmentHashes<T>>::get().into_inner(),
+				next_message_id: <NextId<T>>::get(),
+			}
+		}
+
+		fn make_commitment_hash(bundle: &MessageBundleOf<T>) -> H256 {
+			bundle.commitment_hash::<<T as Config>::Hashing>()
+		}
+
+		/// Construct a proof that the message at `index` is part of the Merkle tree committed to
+		/// for `bundle`.
+		pub fn make_message_proof(bundle: &MessageBundleOf<T>, index: u64) -> Option<MerkleProof> {
+			merkle_proof::<<T as Config>::Hashing, _>(bundle.leaves(), index)
 		}
 
 		fn average_payload_size(messages: &[MessageOf<T>]) -> u32 {
@@ -1,17 +1,22 @@
 use super::*;
 
+use codec::{Compact, Decode, Encode};
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::DispatchError,
 	parameter_types,
-	traits::{Everything, GenesisBuild, OnInitialize},
+	storage::unhashed,
+	traits::{Everything, GenesisBuild, OnInitialize, OnRuntimeUpgrade, StorageVersion},
 };
+use hex_literal::hex;
+use snowbridge_core::{Proof, ReceiptProof, VerificationInfo};
+use snowbridge_ethereum::Log;
 use sp_core::{H160, H256};
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify},
-	MultiSignature,
+	DigestItem, MultiSignature,
 };
 use sp_std::convert::From;
 
@@ -68,6 +73,34 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub const MaxMessagePayloadSize: u32 = 128;
 	pub const MaxMessagesPerCommit: u32 = 5;
+	pub const MaxQueuedMessages: u32 = 10;
+	pub static EthereumTimestamp: Option<u64> = None;
+}
+
+// Mock verifier, which accepts any SCALE-encoded log
+pub struct MockVerifier;
+
+impl Verifier for MockVerifier {
+	fn verify(
+		message: &snowbridge_core::Message,
+	) -> Result<(Log, VerificationInfo), DispatchError> {
+		let log = Log::decode(&mut message.data.as_slice()).unwrap();
+		let info = VerificationInfo {
+			block_number: 0,
+			block_hash: message.proof.receipt_proof().block_hash,
+			timestamp: 0,
+			log_index: 0,
+		};
+		Ok((log, info))
+	}
+}
+
+pub struct MockEthereumTime;
+
+impl EthereumTime for MockEthereumTime {
+	fn finalized_timestamp() -> Option<u64> {
+		EthereumTimestamp::get()
+	}
 }
 
 impl basic_outbound_channel::Config for Test {
@@ -75,17 +108,25 @@ impl basic_outbound_channel::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type Verifier = MockVerifier;
+	type EthereumTime = MockEthereumTime;
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
+const TARGET_CHANNEL: [u8; 20] = [9; 20];
+
 pub fn new_tester() -> sp_io::TestExternalities {
 	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	let config: basic_outbound_channel::GenesisConfig<Test> =
 		basic_outbound_channel::GenesisConfig {
-			principal: Some(Keyring::Bob.into()),
+			principals: vec![Keyring::Bob.into()],
 			interval: 1u64,
+			target_channel: TARGET_CHANNEL.into(),
 		};
 	config.assimilate_storage(&mut storage).unwrap();
 
@@ -108,7 +149,7 @@ fn test_submit() {
 		let target = H160::zero();
 		let who: AccountId = Keyring::Bob.into();
 
-		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
 		assert_eq!(<NextId<Test>>::get(), 1);
 		assert_eq!(<Nonce<Test>>::get(), 0);
 
@@ -123,17 +164,40 @@ fn test_submit_exceeds_queue_limit() {
 		let target = H160::zero();
 		let who: AccountId = Keyring::Bob.into();
 
-		let max_messages = MaxMessagesPerCommit::get();
-		(0..max_messages)
-			.for_each(|_| BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]).unwrap());
+		let max_messages = MaxQueuedMessages::get();
+		(0..max_messages).for_each(|_| {
+			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None).unwrap();
+		});
 
 		assert_noop!(
-			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]),
+			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None),
 			Error::<Test>::QueueSizeLimitReached,
 		);
 	})
 }
 
+#[test]
+fn test_commit_carries_over_backlog() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		let max_messages = MaxMessagesPerCommit::get();
+		(0..max_messages + 2).for_each(|_| {
+			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None).unwrap();
+		});
+		assert_eq!(BasicOutboundChannel::backlog_depth(), max_messages + 2);
+
+		run_to_block(2);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+		assert_eq!(BasicOutboundChannel::backlog_depth(), 2);
+
+		run_to_block(3);
+		assert_eq!(<Nonce<Test>>::get(), 2);
+		assert_eq!(BasicOutboundChannel::backlog_depth(), 0);
+	})
+}
+
 #[test]
 fn test_submit_exceeds_payload_limit() {
 	new_tester().execute_with(|| {
@@ -144,7 +208,7 @@ fn test_submit_exceeds_payload_limit() {
 		let payload: Vec<u8> = (0..).take(max_payload_bytes as usize + 1).collect();
 
 		assert_noop!(
-			BasicOutboundChannel::submit(&who, target, payload.as_slice()),
+			BasicOutboundChannel::submit(&who, target, payload.as_slice(), None),
 			Error::<Test>::PayloadTooLarge,
 		);
 	})
@@ -157,30 +221,554 @@ fn test_submit_fails_not_authorized() {
 		let who: AccountId = Keyring::Charlie.into();
 
 		assert_noop!(
-			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2]),
+			BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None),
 			Error::<Test>::NotAuthorized,
 		);
 	});
 }
 
 #[test]
-fn test_set_principal_unauthorized() {
+fn test_add_principal_unauthorized() {
 	new_tester().execute_with(|| {
 		let dave: AccountId = Keyring::Dave.into();
 
 		assert_noop!(
-			BasicOutboundChannel::set_principal(Origin::signed(dave), Keyring::Alice.into()),
+			BasicOutboundChannel::add_principal(Origin::signed(dave), Keyring::Alice.into()),
 			DispatchError::BadOrigin
 		);
 	});
 }
 
 #[test]
-fn test_set_principal() {
+fn test_add_and_remove_principals() {
 	new_tester().execute_with(|| {
 		let alice: AccountId = Keyring::Alice.into();
+		let bob: AccountId = Keyring::Bob.into();
+		let target = H160::zero();
+
+		assert_ok!(BasicOutboundChannel::add_principal(Origin::root(), alice.clone()));
+		assert!(BasicOutboundChannel::is_principal(&alice));
+		assert!(BasicOutboundChannel::is_principal(&bob));
+		assert_ok!(BasicOutboundChannel::submit(&alice, target, &vec![0, 1, 2], None));
+		assert_ok!(BasicOutboundChannel::submit(&bob, target, &vec![0, 1, 2], None));
+
+		assert_ok!(BasicOutboundChannel::remove_principal(Origin::root(), bob.clone()));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::BasicOutboundChannel(crate::outbound::Event::PrincipalRemoved(bob.clone()))
+		);
+		assert_noop!(
+			BasicOutboundChannel::submit(&bob, target, &vec![0, 1, 2], None),
+			Error::<Test>::NotAuthorized,
+		);
+		assert_noop!(
+			BasicOutboundChannel::remove_principal(Origin::root(), bob),
+			Error::<Test>::UnknownPrincipal,
+		);
+	});
+}
+
+#[test]
+fn test_migrate_principal() {
+	new_tester().execute_with(|| {
+		let alice: AccountId = Keyring::Alice.into();
+		StorageVersion::new(0).put::<BasicOutboundChannel>();
+		<Principal<Test>>::put(Some(alice.clone()));
+
+		BasicOutboundChannel::on_runtime_upgrade();
+
+		assert!(BasicOutboundChannel::is_principal(&alice));
+		assert_eq!(<Principal<Test>>::get(), None);
+		assert_eq!(StorageVersion::get::<BasicOutboundChannel>(), 2);
+	});
+}
+
+parameter_types! {
+	pub AppAccounts: Vec<AccountId> = vec![Keyring::Charlie.into(), Keyring::Dave.into()];
+}
+
+#[test]
+fn test_migrate_adds_app_principals() {
+	new_tester().execute_with(|| {
+		let alice: AccountId = Keyring::Alice.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+		StorageVersion::new(0).put::<BasicOutboundChannel>();
+		<Principal<Test>>::put(Some(alice.clone()));
+
+		migration::v1::AddPrincipals::<Test, AppAccounts>::on_runtime_upgrade();
+		BasicOutboundChannel::on_runtime_upgrade();
+
+		assert!(BasicOutboundChannel::is_principal(&alice));
+		for account in AppAccounts::get() {
+			assert!(BasicOutboundChannel::is_principal(&account));
+		}
+
+		// Principals removed after the upgrade are not added again
+		assert_ok!(BasicOutboundChannel::remove_principal(Origin::root(), charlie.clone()));
+		migration::v1::AddPrincipals::<Test, AppAccounts>::on_runtime_upgrade();
+		assert!(!BasicOutboundChannel::is_principal(&charlie));
+	});
+}
+
+#[test]
+fn test_commitment_hash_matches_inbound_channel() {
+	let bundle = MessageBundleOf::<Test> {
+		version: CommitmentVersion::V0,
+		source_channel_id: 0,
+		nonce: 1,
+		messages: vec![Message {
+			id: 7,
+			target: H160::repeat_byte(1),
+			expires_at: None,
+			payload: vec![1, 2, 3].try_into().unwrap(),
+		}]
+		.try_into()
+		.unwrap(),
+	};
+
+	// As computed by `BasicInboundChannel.submit` on Ethereum.
+	assert_eq!(
+		bundle.commitment_hash::<Keccak256>(),
+		H256(hex!("e8258a92c9bc87626f4598b84794437220ae33db4574e5a6b1cbc7f1f5fd4152")),
+	);
+}
+
+#[test]
+fn test_legacy_bundle_layout() {
+	let bundle = MessageBundleOf::<Test> {
+		version: CommitmentVersion::V0,
+		source_channel_id: 0,
+		nonce: 1,
+		messages: vec![Message {
+			id: 7,
+			target: H160::repeat_byte(1),
+			expires_at: None,
+			payload: vec![1, 2, 3].try_into().unwrap(),
+		}]
+		.try_into()
+		.unwrap(),
+	};
+
+	// V0 bundles keep the layout from before commitments were versioned.
+	let expected =
+		(0u8, Compact(1u64), vec![(Compact(7u64), H160::repeat_byte(1), vec![1u8, 2, 3])]).encode();
+	assert_eq!(bundle.to_legacy().encode(), expected);
+	assert_eq!(MessageBundleOf::<Test>::from(bundle.to_legacy()), bundle);
+}
+
+#[test]
+fn test_commitment_binds_merkle_root_of_messages() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		for i in 0..3u8 {
+			assert_ok!(BasicOutboundChannel::submit(&who, H160::repeat_byte(i), &vec![i; 3], None));
+		}
+		run_to_block(2);
+
+		let (hash, bundle) = commitments().pop().expect("commitment event");
+
+		let root = merkle_root::<Keccak256, _>(bundle.leaves());
+		assert_eq!(hash, bundle.commitment_hash::<Keccak256>());
+		assert_ne!(hash, root);
+
+		// The commitment differs for the same messages in a bundle with another nonce.
+		let mut replayed = bundle.clone();
+		replayed.nonce += 1;
+		assert_ne!(replayed.commitment_hash::<Keccak256>(), hash);
+
+		for index in 0..3 {
+			let proof = BasicOutboundChannel::make_message_proof(&bundle, index).unwrap();
+			assert_eq!(proof.root, root);
+			assert!(snowbridge_core::merkle::verify_proof::<Keccak256>(
+				root,
+				&proof.proof,
+				proof.number_of_leaves,
+				proof.leaf_index,
+				Keccak256::hash(&proof.leaf),
+			));
+		}
+		assert!(BasicOutboundChannel::make_message_proof(&bundle, 3).is_none());
+	});
+}
+
+#[test]
+fn test_commit_indexes_bundle_offchain() {
+	let mut ext = new_tester();
+	let (hash, bundle) = ext.execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], None));
+		run_to_block(2);
+
+		commitments().pop().expect("commitment event")
+	});
+	ext.persist_offchain_overlay();
+
+	let stored = ext.offchain_db().get(&offchain_key(hash)).expect("indexed bundle");
+	assert_eq!(MessageBundleOf::<Test>::decode(&mut stored.as_slice()).unwrap(), bundle);
+}
+
+#[test]
+fn test_channel_state() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(1);
+		let who: AccountId = Keyring::Bob.into();
+		<Interval<Test>>::put(4);
+
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
+		assert_ok!(BasicOutboundChannel::submit(&who, target, &vec![3], None));
+
+		assert_eq!(
+			BasicOutboundChannel::queued_messages(),
+			vec![
+				QueuedMessage { id: 0, target, fee: 0, expires_at: None, payload: vec![0, 1, 2] },
+				QueuedMessage { id: 1, target, fee: 0, expires_at: None, payload: vec![3] },
+			]
+		);
+		assert_eq!(
+			BasicOutboundChannel::channel_state(),
+			OutboundChannelState {
+				next_commitment_block: 4,
+				nonce: 0,
+				latest_commitment_hashes: vec![],
+				next_message_id: 2,
+			}
+		);
+
+		run_to_block(4);
+		assert!(BasicOutboundChannel::queued_messages().is_empty());
+
+		let state = BasicOutboundChannel::channel_state();
+		assert_eq!(state.next_commitment_block, 8);
+		assert_eq!(state.nonce, 1);
+		assert_eq!(
+			state.latest_commitment_hashes,
+			vec![(CommitmentVersion::V0, commitments()[0].0)]
+		);
+	});
+}
+
+#[test]
+fn test_set_commitment_versions() {
+	new_tester().execute_with(|| {
+		let dave: AccountId = Keyring::Dave.into();
+		assert_eq!(
+			BasicOutboundChannel::commitment_versions().into_inner(),
+			vec![CommitmentVersion::V0]
+		);
+
+		assert_noop!(
+			BasicOutboundChannel::set_commitment_versions(
+				Origin::signed(dave),
+				vec![CommitmentVersion::V2]
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			BasicOutboundChannel::set_commitment_versions(Origin::root(), vec![]),
+			Error::<Test>::InvalidCommitmentVersions
+		);
+		// Relayers cannot submit commitments with a version field but no expiry, so there is
+		// no such version to set
+		let call = basic_outbound_channel::Call::<Test>::set_commitment_versions {
+			versions: vec![CommitmentVersion::V0, CommitmentVersion::V2],
+		};
+		let mut encoded = call.encode();
+		*encoded.last_mut().unwrap() = 1;
+		assert!(basic_outbound_channel::Call::<Test>::decode(&mut &encoded[..]).is_err());
+
+		assert_ok!(BasicOutboundChannel::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V2, CommitmentVersion::V0, CommitmentVersion::V2]
+		));
+		assert_eq!(
+			BasicOutboundChannel::commitment_versions().into_inner(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		);
+	});
+}
+
+#[test]
+fn test_commit_in_each_version() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+		assert_ok!(BasicOutboundChannel::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		));
+
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], None));
+		run_to_block(2);
+
+		let commitments = commitments();
+		assert_eq!(commitments.len(), 2);
+
+		let (legacy_hash, legacy) = &commitments[0];
+		let (hash, bundle) = &commitments[1];
+		assert_eq!(legacy.version, CommitmentVersion::V0);
+		assert_eq!(bundle.version, CommitmentVersion::V2);
+		assert_eq!(legacy.messages, bundle.messages);
+		assert_ne!(legacy_hash, hash);
+
+		let logs = System::digest().logs;
+		let expected: Vec<DigestItem> = vec![
+			AuxiliaryDigestItem::LegacyCommitment(ChannelId::BASIC, *legacy_hash).into(),
+			AuxiliaryDigestItem::Commitment(ChannelId::BASIC, CommitmentVersion::V2, *hash).into(),
+		];
+		assert_eq!(logs, expected);
+		assert_eq!(
+			<LatestCommitmentHashes<Test>>::get().into_inner(),
+			vec![(CommitmentVersion::V0, *legacy_hash), (CommitmentVersion::V2, *hash)]
+		);
+	});
+}
+
+fn report(channel: H160, id: u64, result: bool) -> snowbridge_core::Message {
+	let log = Log {
+		address: channel,
+		topics: vec![H256(sp_io::hashing::keccak_256(b"MessageDispatched(uint64,bool)"))],
+		data: ethabi::encode(&[Token::Uint(id.into()), Token::Bool(result)]),
+	};
+	snowbridge_core::Message {
+		data: log.encode(),
+		proof: Proof::PoW(ReceiptProof {
+			block_hash: Default::default(),
+			tx_index: Default::default(),
+			data: Default::default(),
+		}),
+	}
+}
+
+/// Commitments in the events emitted so far, with V0 bundles converted to the versioned layout.
+fn commitments() -> Vec<(H256, MessageBundleOf<Test>)> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::BasicOutboundChannel(crate::outbound::Event::Committed { hash, data }) =>
+				Some((hash, data.into())),
+			Event::BasicOutboundChannel(crate::outbound::Event::VersionedCommitted {
+				hash,
+				data,
+			}) => Some((hash, data)),
+			_ => None,
+		})
+		.collect()
+}
+
+fn committed_bundle() -> MessageBundleOf<Test> {
+	let (_, bundle) = commitments().into_iter().next().expect("commitment event");
+	bundle
+}
+
+fn submit_expiring(expires_at: u64) -> u64 {
+	let who: AccountId = Keyring::Bob.into();
+	BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(expires_at)).unwrap()
+}
+
+fn set_expiring_versions() {
+	assert_ok!(BasicOutboundChannel::set_commitment_versions(
+		Origin::root(),
+		vec![CommitmentVersion::V2]
+	));
+}
+
+#[test]
+fn test_submit_with_expiry_requires_version_encoding_it() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		assert_noop!(
+			BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(100)),
+			Error::<Test>::ExpiryNotSupported,
+		);
+
+		set_expiring_versions();
+		assert_eq!(submit_expiring(100), 0);
+
+		// Expiring messages in the queue must be committed with their expiry
+		assert_noop!(
+			BasicOutboundChannel::set_commitment_versions(
+				Origin::root(),
+				vec![CommitmentVersion::V0, CommitmentVersion::V2]
+			),
+			Error::<Test>::ExpiryNotSupported,
+		);
+	});
+}
+
+#[test]
+fn test_submit_expired_message() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+		set_expiring_versions();
+		EthereumTimestamp::set(Some(100));
+
+		assert_noop!(
+			BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(99)),
+			Error::<Test>::MessageExpired,
+		);
+		assert_ok!(BasicOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(100)));
+	});
+}
+
+#[test]
+fn test_commit_drops_expired_messages() {
+	new_tester().execute_with(|| {
+		set_expiring_versions();
+		EthereumTimestamp::set(Some(100));
+		let expired = submit_expiring(150);
+		let pending = submit_expiring(250);
+
+		EthereumTimestamp::set(Some(200));
+		run_to_block(2);
+
+		assert_eq!(BasicOutboundChannel::outcome(expired), Some(MessageOutcome::Undelivered));
+		System::assert_has_event(Event::BasicOutboundChannel(
+			crate::outbound::Event::OutcomeRecorded(expired, MessageOutcome::Undelivered),
+		));
+		assert_eq!(<PendingOutcomes<Test>>::get(pending), Some(250));
+		assert_eq!(<PendingOutcomes<Test>>::get(expired), None);
+
+		let bundle = committed_bundle();
+		assert_eq!(bundle.messages.len(), 1);
+		assert_eq!(bundle.messages[0].id, pending);
+	});
+}
+
+#[test]
+fn test_commit_encodes_expiry() {
+	new_tester().execute_with(|| {
+		set_expiring_versions();
+		let id = submit_expiring(250);
+		run_to_block(2);
+
+		let bundle = committed_bundle();
+		assert_eq!(
+			bundle.leaves(),
+			vec![ethabi::encode(&[Token::Tuple(vec![
+				Token::Uint(2u8.into()),
+				Token::Uint(id.into()),
+				Token::Address(H160::zero()),
+				Token::Uint(250u64.into()),
+				Token::Bytes(vec![0, 1, 2]),
+			])])]
+		);
+	});
+}
+
+#[test]
+fn test_report_dispatch() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Ferdie.into();
+		set_expiring_versions();
+		let delivered = submit_expiring(250);
+		let undelivered = submit_expiring(250);
+
+		// Outcomes are only awaited for committed messages
+		assert_noop!(
+			BasicOutboundChannel::report_dispatch(
+				Origin::signed(relayer.clone()),
+				report(TARGET_CHANNEL.into(), delivered, true)
+			),
+			Error::<Test>::UnknownMessage,
+		);
+
+		run_to_block(2);
+
+		assert_noop!(
+			BasicOutboundChannel::report_dispatch(
+				Origin::signed(relayer.clone()),
+				report(H160::repeat_byte(1), delivered, false)
+			),
+			Error::<Test>::InvalidTargetChannel,
+		);
+
+		assert_ok!(BasicOutboundChannel::report_dispatch(
+			Origin::signed(relayer.clone()),
+			report(TARGET_CHANNEL.into(), delivered, true)
+		));
+		assert_ok!(BasicOutboundChannel::report_dispatch(
+			Origin::signed(relayer.clone()),
+			report(TARGET_CHANNEL.into(), undelivered, false)
+		));
+		assert_noop!(
+			BasicOutboundChannel::report_dispatch(
+				Origin::signed(relayer),
+				report(TARGET_CHANNEL.into(), delivered, true)
+			),
+			Error::<Test>::UnknownMessage,
+		);
+
+		assert_eq!(BasicOutboundChannel::take_outcome(delivered), Some(MessageOutcome::Delivered));
+		assert_eq!(BasicOutboundChannel::take_outcome(delivered), None);
+		assert_eq!(
+			BasicOutboundChannel::take_outcome(undelivered),
+			Some(MessageOutcome::Undelivered)
+		);
+	});
+}
+
+#[test]
+fn test_no_outcome_without_report() {
+	new_tester().execute_with(|| {
+		set_expiring_versions();
+		let id = submit_expiring(250);
+		run_to_block(2);
+
+		// The message may have been delivered however long ago it expired
+		EthereumTimestamp::set(Some(u64::MAX));
+		assert_eq!(BasicOutboundChannel::take_outcome(id), None);
+		assert_eq!(<PendingOutcomes<Test>>::get(id), Some(250));
+	});
+}
+
+#[test]
+fn test_migrate_queue_without_expiry() {
+	new_tester().execute_with(|| {
+		StorageVersion::new(1).put::<BasicOutboundChannel>();
+		let queue: Vec<LegacyMessageOf<Test>> = vec![LegacyMessage {
+			id: 3,
+			target: H160::repeat_byte(1),
+			payload: vec![0, 1, 2].try_into().unwrap(),
+		}];
+		unhashed::put(&<MessageQueue<Test>>::hashed_key(), &queue);
+
+		BasicOutboundChannel::on_runtime_upgrade();
+
+		assert_eq!(
+			BasicOutboundChannel::queued_messages(),
+			vec![QueuedMessage {
+				id: 3,
+				target: H160::repeat_byte(1),
+				fee: 0,
+				expires_at: None,
+				payload: vec![0, 1, 2],
+			}]
+		);
+		assert_eq!(StorageVersion::get::<BasicOutboundChannel>(), 2);
+	});
+}
+
+#[test]
+fn test_migrate_queue_over_bound() {
+	new_tester().execute_with(|| {
+		StorageVersion::new(1).put::<BasicOutboundChannel>();
+		let max_messages = MaxQueuedMessages::get() as u64;
+		let queue: Vec<LegacyMessageOf<Test>> = (0..max_messages + 2)
+			.map(|id| LegacyMessage {
+				id,
+				target: H160::repeat_byte(1),
+				payload: vec![0, 1, 2].try_into().unwrap(),
+			})
+			.collect();
+		unhashed::put(&<MessageQueue<Test>>::hashed_key(), &queue);
+
+		BasicOutboundChannel::on_runtime_upgrade();
 
-		assert_ok!(BasicOutboundChannel::set_principal(Origin::root(), alice.clone()));
-		assert_eq!(<Principal<Test>>::get(), Some(alice));
+		// The oldest messages are kept
+		let queued = BasicOutboundChannel::queued_messages();
+		assert_eq!(queued.len() as u64, max_messages);
+		assert_eq!(queued.last().unwrap().id, max_messages - 1);
 	});
 }
@@ -3,6 +3,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-11-25, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("spec.json"), DB CACHE: 128
+//!
+//! Entries marked ESTIMATE are not output of the benchmark CLI. They must be replaced by
+//! re-running the benchmarks before this pallet is deployed to a production chain.
 
 // Executed Command:
 // target/release/snowbridge
@@ -40,12 +43,16 @@ pub trait WeightInfo {
 	fn on_initialize(m: u32, p: u32, ) -> Weight;
 	fn on_initialize_non_interval() -> Weight;
 	fn on_initialize_no_messages() -> Weight;
-	fn set_principal() -> Weight;
+	fn add_principal() -> Weight;
+	fn remove_principal() -> Weight;
+	fn set_commitment_versions() -> Weight;
 }
 
 /// Weights for basic_channel::outbound using the Snowbridge node and recommended hardware.
 pub struct SnowbridgeWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
+	// ESTIMATE: the benchmarked weight, plus a write of the latest commitment hashes.
+	// Replace with the output of the `on_initialize` benchmark.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 31_000
@@ -53,7 +60,7 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 			// Standard Error: 1_000
 			.saturating_add((388_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn on_initialize_non_interval() -> Weight {
 		(3_294_000 as Weight)
@@ -63,14 +70,30 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 		(5_228_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 	}
-	fn set_principal() -> Weight {
+	fn add_principal() -> Weight {
 		(2_544_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: the benchmarked `add_principal`, plus a read to check that the account is a
+	// principal. Replace with the output of the `remove_principal` benchmark.
+	fn remove_principal() -> Weight {
+		(3_720_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: a single write, plus a read of the message queue when a version without
+	// expiry is set. Replace with the output of the `set_commitment_versions` benchmark.
+	fn set_commitment_versions() -> Weight {
+		(2_761_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// ESTIMATE: the benchmarked weight, plus a write of the latest commitment hashes.
+	// Replace with the output of the `on_initialize` benchmark.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 31_000
@@ -78,7 +101,7 @@ impl WeightInfo for () {
 			// Standard Error: 1_000
 			.saturating_add((388_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn on_initialize_non_interval() -> Weight {
 		(3_294_000 as Weight)
@@ -88,8 +111,22 @@ impl WeightInfo for () {
 		(5_228_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 	}
-	fn set_principal() -> Weight {
+	fn add_principal() -> Weight {
 		(2_544_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: the benchmarked `add_principal`, plus a read to check that the account is a
+	// principal. Replace with the output of the `remove_principal` benchmark.
+	fn remove_principal() -> Weight {
+		(3_720_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: a single write, plus a read of the message queue when a version without
+	// expiry is set. Replace with the output of the `set_commitment_versions` benchmark.
+	fn set_commitment_versions() -> Weight {
+		(2_761_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,44 @@
+//! ChannelRegistry pallet benchmarking
+use super::*;
+
+use frame_benchmarking::{benchmarks, BenchmarkError};
+use frame_support::traits::{Get, UnfilteredDispatchable};
+
+#[allow(unused_imports)]
+use crate::Pallet as ChannelRegistry;
+
+fn initial_channel<T: Config>() -> Result<(ChannelId, ChannelInfo<T::Outbound>), BenchmarkError> {
+	let (channel_id, outbound, source) = T::InitialChannels::get()
+		.pop()
+		.ok_or(BenchmarkError::Stop("No initial channels to register"))?;
+	Ok((channel_id, ChannelInfo { outbound, source }))
+}
+
+benchmarks! {
+	register_channel {
+		let origin = T::RegisterOrigin::successful_origin();
+		let (_, info) = initial_channel::<T>()?;
+		let channel_id = ChannelId(u8::MAX);
+		let call = Call::<T>::register_channel {
+			channel_id,
+			outbound: info.outbound,
+			source: info.source,
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(ChannelRegistry::<T>::channel(channel_id), Some(info));
+	}
+
+	deregister_channel {
+		let origin = T::RegisterOrigin::successful_origin();
+		let (_, info) = initial_channel::<T>()?;
+		let channel_id = ChannelId(u8::MAX);
+		<Channels<T>>::insert(channel_id, info);
+		let call = Call::<T>::deregister_channel { channel_id };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(ChannelRegistry::<T>::channel(channel_id), None);
+	}
+
+	impl_benchmark_test_suite!(ChannelRegistry, crate::mock::new_tester(), crate::mock::Test,);
+}
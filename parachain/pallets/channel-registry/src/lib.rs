@@ -0,0 +1,195 @@
+//! # Channel Registry
+//!
+//! Registry of the message channels of the bridge.
+//!
+//! ## Overview
+//!
+//! Each channel is identified by a [`ChannelId`], and registered with the outbound channel
+//! pallet which messages on the channel are submitted to, and the address of the channel
+//! contract on Ethereum. Apps submit messages on any registered channel, so adding a channel
+//! does not require changes to apps.
+//!
+//! The outbound channel pallets which channels can be registered with are fixed by the runtime
+//! in [`Config::Outbound`], so a channel with a new outbound channel pallet still requires a
+//! runtime upgrade. Inbound channels only accept messages sent from the contract registered for
+//! their channel, which they look up through [`ChannelSource`].
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Calls
+//!
+//! - `register_channel`: Register a channel, or update a registered channel.
+//! - `deregister_channel`: Remove a registered channel.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, traits::EnsureOrigin};
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H160};
+use sp_std::prelude::*;
+
+use snowbridge_core::{ChannelId, ChannelSource};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// A registered channel.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ChannelInfo<Outbound> {
+	/// Outbound channel pallet which messages on the channel are submitted to.
+	pub outbound: Outbound,
+	/// Address of the outbound channel contract on Ethereum, which the inbound channel accepts
+	/// messages from.
+	pub source: H160,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Outbound channel pallets which channels can be registered with. Messages are routed to
+		/// them by the runtime.
+		type Outbound: Parameter + Copy + MaxEncodedLen + MaybeSerializeDeserialize;
+
+		/// Channels registered when the pallet is added to a running chain.
+		type InitialChannels: Get<Vec<(ChannelId, Self::Outbound, H160)>>;
+
+		/// Origin which can register and deregister channels.
+		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Channel was registered or updated.
+		ChannelRegistered(ChannelId, T::Outbound, H160),
+		/// Channel was deregistered.
+		ChannelDeregistered(ChannelId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Channel is not registered.
+		UnknownChannel,
+	}
+
+	/// Registered channels.
+	#[pallet::storage]
+	#[pallet::getter(fn channel)]
+	pub type Channels<T: Config> =
+		StorageMap<_, Twox64Concat, ChannelId, ChannelInfo<T::Outbound>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub channels: Vec<(ChannelId, T::Outbound, H160)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { channels: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (channel_id, outbound, source) in &self.channels {
+				<Channels<T>>::insert(
+					channel_id,
+					ChannelInfo { outbound: *outbound, source: *source },
+				);
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				let channels = T::InitialChannels::get();
+				let writes = channels.len() as Weight + 1;
+				for (channel_id, outbound, source) in channels {
+					<Channels<T>>::insert(channel_id, ChannelInfo { outbound, source });
+				}
+				STORAGE_VERSION.put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(1, writes)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::register_channel())]
+		pub fn register_channel(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+			outbound: T::Outbound,
+			source: H160,
+		) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			<Channels<T>>::insert(channel_id, ChannelInfo { outbound, source });
+			Self::deposit_event(Event::ChannelRegistered(channel_id, outbound, source));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::deregister_channel())]
+		pub fn deregister_channel(origin: OriginFor<T>, channel_id: ChannelId) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			ensure!(<Channels<T>>::contains_key(channel_id), Error::<T>::UnknownChannel);
+			<Channels<T>>::remove(channel_id);
+			Self::deposit_event(Event::ChannelDeregistered(channel_id));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Outbound channel pallet for a registered channel.
+		pub fn outbound(channel_id: ChannelId) -> Result<T::Outbound, DispatchError> {
+			<Channels<T>>::get(channel_id)
+				.map(|info| info.outbound)
+				.ok_or_else(|| Error::<T>::UnknownChannel.into())
+		}
+
+		pub fn channels() -> Vec<(ChannelId, ChannelInfo<T::Outbound>)> {
+			<Channels<T>>::iter().collect()
+		}
+	}
+}
+
+impl<T: Config> ChannelSource for Pallet<T> {
+	fn source(channel_id: ChannelId) -> Option<H160> {
+		<Channels<T>>::get(channel_id).map(|info| info.source)
+	}
+}
@@ -0,0 +1,112 @@
+// Mock runtime
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Everything, GenesisBuild},
+};
+use frame_system::EnsureRoot;
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{RuntimeDebug, H160, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_std::prelude::*;
+
+use snowbridge_core::ChannelId;
+
+use crate as channel_registry;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Event<T>},
+		ChannelRegistry: channel_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+pub type AccountId = u64;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+#[derive(
+	Encode,
+	Decode,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub enum Outbound {
+	Basic,
+	Incentivized,
+}
+
+parameter_types! {
+	pub InitialChannels: Vec<(ChannelId, Outbound, H160)> = vec![
+		(ChannelId::BASIC, Outbound::Basic, H160::repeat_byte(1)),
+		(ChannelId::INCENTIVIZED, Outbound::Incentivized, H160::repeat_byte(2)),
+	];
+}
+
+impl channel_registry::Config for Test {
+	type Event = Event;
+	type Outbound = Outbound;
+	type InitialChannels = InitialChannels;
+	type RegisterOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+pub fn new_tester() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	let config = channel_registry::GenesisConfig::<Test> {
+		channels: vec![(ChannelId::BASIC, Outbound::Basic, H160::repeat_byte(1))],
+	};
+	GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,91 @@
+use crate::mock::{new_tester, ChannelRegistry, Event, Origin, Outbound, System, Test};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use sp_core::H160;
+
+use snowbridge_core::{ChannelId, ChannelSource};
+
+use crate::{ChannelInfo, Channels, Error};
+
+fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
+
+#[test]
+fn register_and_deregister_channel() {
+	new_tester().execute_with(|| {
+		let priority = ChannelId(2);
+		let source = H160::repeat_byte(3);
+
+		assert_noop!(ChannelRegistry::outbound(priority), Error::<Test>::UnknownChannel);
+
+		assert_ok!(ChannelRegistry::register_channel(
+			Origin::root(),
+			priority,
+			Outbound::Incentivized,
+			source
+		));
+		assert_eq!(
+			ChannelRegistry::channel(priority),
+			Some(ChannelInfo { outbound: Outbound::Incentivized, source })
+		);
+		assert_eq!(ChannelRegistry::outbound(priority), Ok(Outbound::Incentivized));
+		assert_eq!(<ChannelRegistry as ChannelSource>::source(priority), Some(source));
+		assert_eq!(
+			Event::ChannelRegistry(crate::Event::<Test>::ChannelRegistered(
+				priority,
+				Outbound::Incentivized,
+				source
+			)),
+			last_event()
+		);
+
+		assert_ok!(ChannelRegistry::deregister_channel(Origin::root(), priority));
+		assert_eq!(ChannelRegistry::channel(priority), None);
+		assert_eq!(<ChannelRegistry as ChannelSource>::source(priority), None);
+		assert_eq!(
+			Event::ChannelRegistry(crate::Event::<Test>::ChannelDeregistered(priority)),
+			last_event()
+		);
+		assert_noop!(
+			ChannelRegistry::deregister_channel(Origin::root(), priority),
+			Error::<Test>::UnknownChannel
+		);
+	});
+}
+
+#[test]
+fn register_channel_not_authorized() {
+	new_tester().execute_with(|| {
+		assert_noop!(
+			ChannelRegistry::register_channel(
+				Origin::signed(1),
+				ChannelId(2),
+				Outbound::Basic,
+				H160::zero()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ChannelRegistry::deregister_channel(Origin::signed(1), ChannelId::BASIC),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn registers_initial_channels_on_upgrade() {
+	new_tester().execute_with(|| {
+		<Channels<Test>>::remove_all(None);
+		StorageVersion::new(0).put::<ChannelRegistry>();
+
+		ChannelRegistry::on_runtime_upgrade();
+
+		assert_eq!(ChannelRegistry::channels().len(), 2);
+		assert_eq!(ChannelRegistry::outbound(ChannelId::INCENTIVIZED), Ok(Outbound::Incentivized));
+		assert_eq!(StorageVersion::get::<ChannelRegistry>(), 1);
+	});
+}
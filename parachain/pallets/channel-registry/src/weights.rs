@@ -0,0 +1,70 @@
+//! Placeholder weights for channel_registry
+//!
+//! THESE WEIGHTS ARE ESTIMATES, NOT BENCHMARK RESULTS. They are based on the benchmarked weights
+//! of extrinsics with similar storage access in other pallets, and must be replaced with the
+//! output of the benchmark CLI before this pallet is deployed to a production chain.
+
+// Command to generate the weights:
+// target/release/snowbridge
+// benchmark
+// --chain
+// spec.json
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet
+// channel_registry
+// --extra
+// --extrinsic
+// *
+// --repeat
+// 10
+// --steps
+// 50
+// --output
+// pallets/channel-registry/src/weights.rs
+// --template
+// module-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for channel_registry.
+pub trait WeightInfo {
+	fn register_channel() -> Weight;
+	fn deregister_channel() -> Weight;
+}
+
+/// Estimated weights for channel_registry, pending benchmarks on the Snowbridge node and
+/// recommended hardware.
+pub struct SnowbridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
+	fn register_channel() -> Weight {
+		(14_229_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn deregister_channel() -> Weight {
+		(17_560_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn register_channel() -> Weight {
+		(14_229_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn deregister_channel() -> Weight {
+		(17_560_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,16 @@
+//! Runtime API for querying the pause status of bridge components.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+
+use snowbridge_core::BridgeComponent;
+
+sp_api::decl_runtime_apis! {
+	pub trait CircuitBreakerApi {
+		/// Whether `component` is paused.
+		fn is_paused(component: BridgeComponent) -> bool;
+
+		/// All bridge components which are currently paused.
+		fn paused_components() -> Vec<BridgeComponent>;
+	}
+}
@@ -0,0 +1,33 @@
+//! CircuitBreaker pallet benchmarking
+use super::*;
+
+use frame_benchmarking::benchmarks;
+use frame_support::traits::UnfilteredDispatchable;
+
+use snowbridge_core::{AppId, BridgeComponent};
+
+#[allow(unused_imports)]
+use crate::Pallet as CircuitBreaker;
+
+benchmarks! {
+	pause {
+		let origin = T::PauseOrigin::successful_origin();
+		let component = BridgeComponent::App(AppId::Eth);
+		let call = Call::<T>::pause { component };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(CircuitBreaker::<T>::is_paused(component));
+	}
+
+	resume {
+		let origin = T::ResumeOrigin::successful_origin();
+		let component = BridgeComponent::App(AppId::Eth);
+		<PausedComponents<T>>::insert(component, ());
+		let call = Call::<T>::resume { component };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!CircuitBreaker::<T>::is_paused(component));
+	}
+
+	impl_benchmark_test_suite!(CircuitBreaker, crate::mock::new_tester(), crate::mock::Test,);
+}
@@ -0,0 +1,130 @@
+//! # Circuit Breaker
+//!
+//! Emergency pause for the bridge.
+//!
+//! ## Overview
+//!
+//! Inbound and outbound submission on each channel, and each bridge application, can be paused
+//! independently. Paused components reject calls until they are resumed.
+//!
+//! Pausing is meant to be quick, so it is permitted to `PauseOrigin`, which is expected to be a
+//! smaller body than `ResumeOrigin`.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Calls
+//!
+//! - `pause`: Pause a bridge component.
+//! - `resume`: Resume a paused bridge component.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::{dispatch::DispatchResult, traits::EnsureOrigin};
+use sp_std::prelude::*;
+
+use snowbridge_core::{BridgeComponent, CircuitBreaker};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin which can pause bridge components.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin which can resume paused bridge components.
+		type ResumeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Bridge component was paused.
+		Paused(BridgeComponent),
+		/// Bridge component was resumed.
+		Resumed(BridgeComponent),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Bridge component is paused.
+		Paused,
+		/// Bridge component is already paused.
+		AlreadyPaused,
+		/// Bridge component is not paused.
+		NotPaused,
+	}
+
+	/// Bridge components which are currently paused.
+	#[pallet::storage]
+	pub type PausedComponents<T: Config> =
+		StorageMap<_, Twox64Concat, BridgeComponent, (), OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, component: BridgeComponent) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_paused(component), Error::<T>::AlreadyPaused);
+			<PausedComponents<T>>::insert(component, ());
+			Self::deposit_event(Event::Paused(component));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::resume())]
+		pub fn resume(origin: OriginFor<T>, component: BridgeComponent) -> DispatchResult {
+			T::ResumeOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_paused(component), Error::<T>::NotPaused);
+			<PausedComponents<T>>::remove(component);
+			Self::deposit_event(Event::Resumed(component));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn is_paused(component: BridgeComponent) -> bool {
+			<PausedComponents<T>>::contains_key(component)
+		}
+
+		pub fn paused_components() -> Vec<BridgeComponent> {
+			<PausedComponents<T>>::iter_keys().collect()
+		}
+	}
+
+	impl<T: Config> CircuitBreaker for Pallet<T> {
+		fn ensure_active(component: BridgeComponent) -> DispatchResult {
+			ensure!(!Self::is_paused(component), Error::<T>::Paused);
+			Ok(())
+		}
+	}
+}
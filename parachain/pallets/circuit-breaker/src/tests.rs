@@ -0,0 +1,86 @@
+use crate::mock::{new_tester, CircuitBreaker, Event, Origin, System, Test};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+
+use snowbridge_core::{AppId, BridgeComponent, ChannelId, CircuitBreaker as _};
+
+use crate::Error;
+
+const RESPONDER: u64 = 1;
+
+fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
+
+#[test]
+fn pause_and_resume() {
+	new_tester().execute_with(|| {
+		let component = BridgeComponent::InboundChannel(ChannelId::BASIC);
+
+		assert_ok!(CircuitBreaker::pause(Origin::signed(RESPONDER), component));
+		assert!(CircuitBreaker::is_paused(component));
+		assert_eq!(Event::CircuitBreaker(crate::Event::<Test>::Paused(component)), last_event());
+		assert_noop!(CircuitBreaker::ensure_active(component), Error::<Test>::Paused);
+
+		assert_ok!(CircuitBreaker::resume(Origin::root(), component));
+		assert!(!CircuitBreaker::is_paused(component));
+		assert_eq!(Event::CircuitBreaker(crate::Event::<Test>::Resumed(component)), last_event());
+		assert_ok!(CircuitBreaker::ensure_active(component));
+	});
+}
+
+#[test]
+fn components_are_paused_independently() {
+	new_tester().execute_with(|| {
+		assert_ok!(CircuitBreaker::pause(
+			Origin::signed(RESPONDER),
+			BridgeComponent::OutboundChannel(ChannelId::INCENTIVIZED)
+		));
+		assert_ok!(CircuitBreaker::pause(
+			Origin::signed(RESPONDER),
+			BridgeComponent::App(AppId::Erc20)
+		));
+
+		assert_ok!(CircuitBreaker::ensure_active(BridgeComponent::InboundChannel(
+			ChannelId::INCENTIVIZED
+		)));
+		assert_ok!(CircuitBreaker::ensure_active(BridgeComponent::OutboundChannel(
+			ChannelId::BASIC
+		)));
+		assert_ok!(CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Eth)));
+
+		let paused = CircuitBreaker::paused_components();
+		assert_eq!(paused.len(), 2);
+		assert!(paused.contains(&BridgeComponent::OutboundChannel(ChannelId::INCENTIVIZED)));
+		assert!(paused.contains(&BridgeComponent::App(AppId::Erc20)));
+	});
+}
+
+#[test]
+fn pause_and_resume_not_authorized() {
+	new_tester().execute_with(|| {
+		let component = BridgeComponent::App(AppId::Dot);
+
+		assert_noop!(CircuitBreaker::pause(Origin::signed(2), component), DispatchError::BadOrigin);
+
+		assert_ok!(CircuitBreaker::pause(Origin::signed(RESPONDER), component));
+		assert_noop!(
+			CircuitBreaker::resume(Origin::signed(RESPONDER), component),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn pause_and_resume_fail_on_unchanged_status() {
+	new_tester().execute_with(|| {
+		let component = BridgeComponent::App(AppId::Eth);
+
+		assert_noop!(CircuitBreaker::resume(Origin::root(), component), Error::<Test>::NotPaused);
+
+		assert_ok!(CircuitBreaker::pause(Origin::signed(RESPONDER), component));
+		assert_noop!(
+			CircuitBreaker::pause(Origin::signed(RESPONDER), component),
+			Error::<Test>::AlreadyPaused
+		);
+	});
+}
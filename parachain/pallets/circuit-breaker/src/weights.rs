@@ -0,0 +1,72 @@
+//! Placeholder weights for circuit_breaker
+//!
+//! THESE WEIGHTS ARE ESTIMATES, NOT BENCHMARK RESULTS. They are based on the benchmarked weights
+//! of extrinsics with similar storage access in other pallets, and must be replaced with the
+//! output of the benchmark CLI before this pallet is deployed to a production chain.
+
+// Command to generate the weights:
+// target/release/snowbridge
+// benchmark
+// --chain
+// spec.json
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet
+// circuit_breaker
+// --extra
+// --extrinsic
+// *
+// --repeat
+// 10
+// --steps
+// 50
+// --output
+// pallets/circuit-breaker/src/weights.rs
+// --template
+// module-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for circuit_breaker.
+pub trait WeightInfo {
+	fn pause() -> Weight;
+	fn resume() -> Weight;
+}
+
+/// Estimated weights for circuit_breaker, pending benchmarks on the Snowbridge node and
+/// recommended hardware.
+pub struct SnowbridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
+	fn pause() -> Weight {
+		(16_873_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn resume() -> Weight {
+		(17_302_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn pause() -> Weight {
+		(16_873_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resume() -> Weight {
+		(17_302_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
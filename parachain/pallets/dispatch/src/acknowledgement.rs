@@ -0,0 +1,73 @@
+use frame_support::{dispatch::DispatchResult, traits::Get, weights::Weight};
+use sp_core::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+use ethabi::{self, Token};
+use snowbridge_core::{ChannelId, MessageId, OutboundRouter};
+
+use crate::Acknowledge;
+
+// Acknowledgement of the result of a message to Ethereum (ABI-encoded)
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AcknowledgementPayload {
+	pub channel_id: ChannelId,
+	pub nonce: u64,
+	pub success: bool,
+}
+
+impl AcknowledgementPayload {
+	/// ABI-encode this payload
+	pub fn encode(&self) -> Vec<u8> {
+		let tokens = vec![
+			Token::Uint(self.channel_id.0.into()),
+			Token::Uint(self.nonce.into()),
+			Token::Bool(self.success),
+		];
+		ethabi::encode_function("acknowledge(uint8,uint64,bool)", tokens.as_ref())
+	}
+}
+
+/// Sends acknowledgements to the source application over the basic channel, on behalf of
+/// the account given by `Sender`.
+///
+/// The source application must implement `acknowledge(uint8,uint64,bool)`, and `Sender` must
+/// be a principal of the basic outbound channel.
+pub struct OutboundAcknowledger<T, Router, Sender>(PhantomData<(T, Router, Sender)>);
+
+impl<T, Router, Sender> Acknowledge<MessageId> for OutboundAcknowledger<T, Router, Sender>
+where
+	T: frame_system::Config,
+	Router: OutboundRouter<T::AccountId>,
+	Sender: Get<T::AccountId>,
+{
+	fn acknowledge(id: &MessageId, success: bool) -> DispatchResult {
+		let payload =
+			AcknowledgementPayload { channel_id: id.channel_id, nonce: id.nonce, success };
+		let sender = Sender::get();
+		Router::submit(ChannelId::BASIC, &sender, &sender, id.source, &payload.encode(), None)
+			.map(|_| ())
+	}
+
+	fn weight() -> Weight {
+		T::DbWeight::get().reads_writes(3, 2)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+
+	#[test]
+	fn test_acknowledgement_payload_encode() {
+		let payload =
+			AcknowledgementPayload { channel_id: ChannelId::INCENTIVIZED, nonce: 7, success: true };
+		let encoded = payload.encode();
+
+		assert_eq!(encoded.len(), 4 + 3 * 32);
+		assert_eq!(encoded[4 + 31], 1);
+		assert_eq!(encoded[4 + 63], 7);
+		assert_eq!(encoded[4 + 95], 1);
+		assert_eq!(&encoded[..4], &hex!("a7928036")[..]);
+	}
+}
@@ -1,21 +1,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-	dispatch::{DispatchResult, Dispatchable, Parameter},
+	dispatch::{DispatchErrorWithPostInfo, DispatchResult, Dispatchable, Parameter},
+	storage::with_transaction,
 	traits::{Contains, EnsureOrigin},
-	weights::GetDispatchInfo,
+	weights::{extract_actual_weight, GetDispatchInfo, Weight},
 };
 
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 
 use sp_core::H160;
+use sp_runtime::TransactionOutcome;
 use sp_std::prelude::*;
 
 use snowbridge_core::MessageDispatch;
 
 use codec::{Decode, Encode};
 
+pub mod acknowledgement;
+pub mod migration;
+
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct RawOrigin(pub H160);
 
@@ -25,6 +30,34 @@ impl From<H160> for RawOrigin {
 	}
 }
 
+/// Why a message was rejected without being dispatched.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum RejectionReason {
+	/// The call is blocked by `Config::CallFilter`.
+	Filtered,
+	/// The source is not allowed to make the call.
+	NotAllowed,
+}
+
+/// Notifies the source of a message on Ethereum of the result of the message.
+pub trait Acknowledge<MessageId> {
+	/// Acknowledge the final result of message `id`. A message which failed is only
+	/// acknowledged once it won't be retried.
+	fn acknowledge(id: &MessageId, success: bool) -> DispatchResult;
+	/// Weight of [`Acknowledge::acknowledge`].
+	fn weight() -> Weight;
+}
+
+impl<MessageId> Acknowledge<MessageId> for () {
+	fn acknowledge(_: &MessageId, _: bool) -> DispatchResult {
+		Ok(())
+	}
+
+	fn weight() -> Weight {
+		0
+	}
+}
+
 pub struct EnsureEthereumAccount;
 
 impl<OuterOrigin> EnsureOrigin<OuterOrigin> for EnsureEthereumAccount
@@ -52,8 +85,13 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -79,13 +117,146 @@ pub mod pallet {
 		/// The pallet will filter all incoming calls right before they're dispatched. If this
 		/// filter rejects the call, special event (`Event::MessageRejected`) is emitted.
 		type CallFilter: Contains<<Self as Config>::Call>;
+
+		/// Max weight of a call dispatched when its message is received. Heavier calls are
+		/// queued, to be executed later with [`Pallet::execute_queued`].
+		#[pallet::constant]
+		type MaxDispatchWeight: Get<Weight>;
+
+		/// Max number of failed or overweight messages kept for retrying.
+		#[pallet::constant]
+		type MaxQueueSize: Get<u32>;
+
+		/// The origin which may discard queued messages.
+		type DiscardOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// The origin which may change the calls allowed for each source.
+		type AllowlistOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// Acknowledges the results of messages. Use `()` to not send acknowledgements.
+		type Acknowledger: Acknowledge<Self::MessageId>;
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
+	/// Failed or overweight messages waiting to be retried, with their source.
+	#[pallet::storage]
+	pub type QueuedMessages<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MessageId, (H160, <T as Config>::Call), OptionQuery>;
+
+	/// Number of entries in [`QueuedMessages`].
+	#[pallet::storage]
+	pub type QueuedMessageCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Calls, by pallet and call index, which each source may dispatch.
+	#[pallet::storage]
+	pub type AllowedCalls<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, H160, Twox64Concat, (u8, u8), (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub allowed_calls: Vec<(H160, u8, u8)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { allowed_calls: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for (source, pallet_index, call_index) in &self.allowed_calls {
+				<AllowedCalls<T>>::insert(source, (*pallet_index, *call_index), ());
+			}
+		}
+	}
+
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Retry a queued message, using at most `weight_limit` to dispatch its call.
+		#[pallet::weight({
+			T::DbWeight::get()
+				.reads_writes(2, 2)
+				.saturating_add(*weight_limit)
+				.saturating_add(T::Acknowledger::weight())
+		})]
+		pub fn execute_queued(
+			origin: OriginFor<T>,
+			id: T::MessageId,
+			weight_limit: Weight,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let (source, call) = <QueuedMessages<T>>::get(&id).ok_or(Error::<T>::NotQueued)?;
+			ensure!(call.get_dispatch_info().weight <= weight_limit, Error::<T>::WeightLimitTooLow);
+
+			let (result, dispatch_weight) = Self::dispatch_call(source, call);
+			let actual_weight =
+				T::DbWeight::get().reads_writes(2, 2).saturating_add(dispatch_weight);
+			// The entry is kept when the call fails, as its effects are rolled back.
+			result.map_err(|error| DispatchErrorWithPostInfo {
+				post_info: Some(actual_weight).into(),
+				error,
+			})?;
+
+			Self::remove_queued(&id);
+			Self::deposit_event(Event::MessageDispatched(id.clone(), Ok(())));
+			let actual_weight = actual_weight.saturating_add(Self::acknowledge(id, true));
+
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Discard a queued message.
+		#[pallet::weight({
+			T::DbWeight::get().reads_writes(2, 2).saturating_add(T::Acknowledger::weight())
+		})]
+		pub fn discard_queued(origin: OriginFor<T>, id: T::MessageId) -> DispatchResult {
+			T::DiscardOrigin::ensure_origin(origin)?;
+			ensure!(<QueuedMessages<T>>::contains_key(&id), Error::<T>::NotQueued);
+
+			Self::remove_queued(&id);
+			Self::deposit_event(Event::QueuedMessageDiscarded(id.clone()));
+			Self::acknowledge(id, false);
+
+			Ok(())
+		}
+
+		/// Allow `source` to dispatch the call at `call_index` in the pallet at `pallet_index`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn allow_call(
+			origin: OriginFor<T>,
+			source: H160,
+			pallet_index: u8,
+			call_index: u8,
+		) -> DispatchResult {
+			T::AllowlistOrigin::ensure_origin(origin)?;
+
+			<AllowedCalls<T>>::insert(source, (pallet_index, call_index), ());
+			Self::deposit_event(Event::CallAllowed(source, pallet_index, call_index));
+
+			Ok(())
+		}
+
+		/// Stop `source` from dispatching the call at `call_index` in the pallet at
+		/// `pallet_index`.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn disallow_call(
+			origin: OriginFor<T>,
+			source: H160,
+			pallet_index: u8,
+			call_index: u8,
+		) -> DispatchResult {
+			T::AllowlistOrigin::ensure_origin(origin)?;
+
+			<AllowedCalls<T>>::remove(source, (pallet_index, call_index));
+			Self::deposit_event(Event::CallDisallowed(source, pallet_index, call_index));
+
+			Ok(())
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -93,9 +264,31 @@ pub mod pallet {
 		/// Message has been dispatched with given result.
 		MessageDispatched(T::MessageId, DispatchResult),
 		/// Message has been rejected
-		MessageRejected(T::MessageId),
+		MessageRejected(T::MessageId, RejectionReason),
 		/// We have failed to decode a Call from the message.
 		MessageDecodeFailed(T::MessageId),
+		/// Message has been queued to be retried.
+		MessageQueued(T::MessageId),
+		/// Queued message has been discarded.
+		QueuedMessageDiscarded(T::MessageId),
+		/// Message has been dropped instead of being queued, as the queue is full.
+		MessageDropped(T::MessageId),
+		/// Source has been allowed to dispatch a call. \[source, pallet_index, call_index\]
+		CallAllowed(H160, u8, u8),
+		/// Source is no longer allowed to dispatch a call. \[source, pallet_index, call_index\]
+		CallDisallowed(H160, u8, u8),
+		/// We have failed to send an acknowledgement for the message.
+		AcknowledgementFailed(T::MessageId, DispatchError),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is heavier than [`Config::MaxDispatchWeight`].
+		Overweight,
+		/// No message is queued with this id.
+		NotQueued,
+		/// The weight limit is lower than the weight of the queued call.
+		WeightLimitTooLow,
 	}
 
 	#[pallet::origin]
@@ -103,28 +296,110 @@ pub mod pallet {
 
 	pub type MessageIdOf<T> = <T as Config>::MessageId;
 
+	impl<T: Config> Pallet<T> {
+		/// Dispatch a call, rolling back its changes if it fails.
+		fn dispatch_call(source: H160, call: <T as Config>::Call) -> (DispatchResult, Weight) {
+			let info = call.get_dispatch_info();
+			let result = with_transaction(|| {
+				let result = call.dispatch(RawOrigin(source).into());
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			});
+			let weight = extract_actual_weight(&result, &info);
+			(result.map(drop).map_err(|e| e.error), weight)
+		}
+
+		/// Queue a message to be retried. The message is dropped if the queue is full.
+		fn enqueue(id: MessageIdOf<T>, source: H160, call: <T as Config>::Call) -> Weight {
+			let count = <QueuedMessageCount<T>>::get();
+			if count >= T::MaxQueueSize::get() {
+				Self::deposit_event(Event::MessageDropped(id.clone()));
+				return T::DbWeight::get().reads(1).saturating_add(Self::acknowledge(id, false))
+			}
+
+			<QueuedMessages<T>>::insert(&id, (source, call));
+			<QueuedMessageCount<T>>::put(count + 1);
+			Self::deposit_event(Event::MessageQueued(id));
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		/// Whether `source` may dispatch the call encoded in `payload`, which starts with the
+		/// pallet and call indices.
+		fn is_allowed(source: H160, payload: &[u8]) -> bool {
+			match payload {
+				[pallet_index, call_index, ..] => {
+					<AllowedCalls<T>>::contains_key(source, (*pallet_index, *call_index))
+				},
+				_ => false,
+			}
+		}
+
+		/// Acknowledge the result of a message which won't be retried.
+		fn acknowledge(id: MessageIdOf<T>, success: bool) -> Weight {
+			if let Err(err) = T::Acknowledger::acknowledge(&id, success) {
+				Self::deposit_event(Event::AcknowledgementFailed(id, err));
+			}
+			T::Acknowledger::weight()
+		}
+
+		fn remove_queued(id: &MessageIdOf<T>) {
+			<QueuedMessages<T>>::remove(id);
+			<QueuedMessageCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+		}
+	}
+
 	impl<T: Config> MessageDispatch<T, MessageIdOf<T>> for Pallet<T> {
-		fn dispatch(source: H160, id: MessageIdOf<T>, payload: &[u8]) {
+		fn dispatch(source: H160, id: MessageIdOf<T>, payload: &[u8]) -> Weight {
 			let call = match <T as Config>::Call::decode(&mut &payload[..]) {
 				Ok(call) => call,
 				Err(_) => {
-					Self::deposit_event(Event::MessageDecodeFailed(id));
-					return
+					Self::deposit_event(Event::MessageDecodeFailed(id.clone()));
+					return Self::acknowledge(id, false)
 				},
 			};
 
 			if !T::CallFilter::contains(&call) {
-				Self::deposit_event(Event::MessageRejected(id));
-				return
+				Self::deposit_event(Event::MessageRejected(id.clone(), RejectionReason::Filtered));
+				return Self::acknowledge(id, false)
 			}
 
-			let origin = RawOrigin(source).into();
-			let result = call.dispatch(origin);
+			if !Self::is_allowed(source, payload) {
+				Self::deposit_event(Event::MessageRejected(
+					id.clone(),
+					RejectionReason::NotAllowed,
+				));
+				return Self::acknowledge(id, false)
+			}
+
+			let overweight = call.get_dispatch_info().weight > T::MaxDispatchWeight::get();
+			let (result, weight) = if overweight {
+				(Err(Error::<T>::Overweight.into()), 0)
+			} else {
+				Self::dispatch_call(source, call.clone())
+			};
 
-			Self::deposit_event(Event::MessageDispatched(
-				id,
-				result.map(drop).map_err(|e| e.error),
-			));
+			Self::deposit_event(Event::MessageDispatched(id.clone(), result));
+
+			match result {
+				Ok(()) => weight.saturating_add(Self::acknowledge(id, true)),
+				Err(_) => weight.saturating_add(Self::enqueue(id, source, call)),
+			}
+		}
+
+		fn dispatch_weight(payload: &[u8]) -> Weight {
+			<T as Config>::Call::decode(&mut &payload[..])
+				.map(|call| {
+					call.get_dispatch_info()
+						.weight
+						.min(T::MaxDispatchWeight::get())
+						.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				})
+				.unwrap_or(0)
+				.saturating_add(T::Acknowledger::weight())
 		}
 
 		#[cfg(feature = "runtime-benchmarks")]
@@ -140,8 +415,13 @@ pub mod pallet {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::{dispatch::DispatchError, parameter_types, traits::Everything};
-	use frame_system::{EventRecord, Phase};
+	use frame_support::{
+		assert_err_ignore_postinfo, assert_noop, assert_ok,
+		dispatch::DispatchError,
+		parameter_types,
+		traits::{Everything, GenesisBuild, OnRuntimeUpgrade, StorageVersion},
+	};
+	use frame_system::{EnsureRoot, EventRecord, Phase};
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::Header,
@@ -150,6 +430,53 @@ mod tests {
 
 	use crate as dispatch;
 
+	#[frame_support::pallet]
+	pub mod mock_app {
+		use super::*;
+		use frame_support::pallet_prelude::*;
+		use frame_system::pallet_prelude::*;
+
+		#[pallet::pallet]
+		pub struct Pallet<T>(_);
+
+		#[pallet::config]
+		pub trait Config: frame_system::Config {
+			type CallOrigin: EnsureOrigin<Self::Origin, Success = H160>;
+		}
+
+		#[pallet::storage]
+		pub type Ready<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+		#[pallet::storage]
+		pub type Attempts<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+		#[pallet::storage]
+		pub type Acknowledged<T: Config> = StorageValue<_, Vec<(u64, bool)>, ValueQuery>;
+
+		#[pallet::error]
+		pub enum Error<T> {
+			NotReady,
+		}
+
+		#[pallet::call]
+		impl<T: Config> Pallet<T> {
+			/// Counts the attempt, then fails until `Ready` is set.
+			#[pallet::weight(1_000)]
+			pub fn execute(origin: OriginFor<T>) -> DispatchResult {
+				T::CallOrigin::ensure_origin(origin)?;
+				<Attempts<T>>::mutate(|attempts| *attempts += 1);
+				ensure!(<Ready<T>>::get(), Error::<T>::NotReady);
+				Ok(())
+			}
+
+			#[pallet::weight(1_000_000)]
+			pub fn heavy(origin: OriginFor<T>) -> DispatchResult {
+				T::CallOrigin::ensure_origin(origin)?;
+				Ok(())
+			}
+		}
+	}
+
 	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 	type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -160,7 +487,8 @@ mod tests {
 			UncheckedExtrinsic = UncheckedExtrinsic,
 		{
 			System: frame_system::{Pallet, Call, Storage, Event<T>},
-			Dispatch: dispatch::{Pallet, Storage, Origin, Event<T>},
+			Dispatch: dispatch::{Pallet, Call, Config, Storage, Origin, Event<T>},
+			MockApp: mock_app::{Pallet, Call, Storage},
 		}
 	);
 
@@ -197,11 +525,18 @@ mod tests {
 		type MaxConsumers = frame_support::traits::ConstU32<16>;
 	}
 
+	parameter_types! {
+		pub const MaxDispatchWeight: Weight = 500_000;
+		pub const MaxQueueSize: u32 = 2;
+		pub SeededCalls: Vec<(H160, u8, u8)> = vec![(H160::repeat_byte(9), 3, 0)];
+	}
+
 	pub struct CallFilter;
 	impl frame_support::traits::Contains<Call> for CallFilter {
 		fn contains(call: &Call) -> bool {
 			match call {
 				Call::System(frame_system::pallet::Call::<Test>::remark { remark: _ }) => true,
+				Call::MockApp(_) => true,
 				_ => false,
 			}
 		}
@@ -213,10 +548,49 @@ mod tests {
 		type MessageId = u64;
 		type Call = Call;
 		type CallFilter = CallFilter;
+		type MaxDispatchWeight = MaxDispatchWeight;
+		type MaxQueueSize = MaxQueueSize;
+		type DiscardOrigin = EnsureRoot<AccountId>;
+		type AllowlistOrigin = EnsureRoot<AccountId>;
+		type Acknowledger = MockAcknowledger;
+	}
+
+	pub struct MockAcknowledger;
+	impl Acknowledge<u64> for MockAcknowledger {
+		fn acknowledge(id: &u64, success: bool) -> DispatchResult {
+			mock_app::Acknowledged::<Test>::append((*id, success));
+			Ok(())
+		}
+
+		fn weight() -> Weight {
+			0
+		}
+	}
+
+	impl mock_app::Config for Test {
+		type CallOrigin = EnsureEthereumAccount;
+	}
+
+	fn call_index(call: Call) -> (u8, u8) {
+		let encoded = call.encode();
+		(encoded[0], encoded[1])
 	}
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		let allowed_calls = vec![
+			call_index(Call::System(frame_system::Call::remark { remark: vec![] })),
+			call_index(Call::System(frame_system::Call::set_code { code: vec![] })),
+			call_index(Call::MockApp(mock_app::Call::execute {})),
+			call_index(Call::MockApp(mock_app::Call::heavy {})),
+		]
+		.into_iter()
+		.map(|(pallet_index, call_index)| (H160::repeat_byte(7), pallet_index, call_index))
+		.collect();
+		let config = dispatch::GenesisConfig { allowed_calls };
+		GenesisBuild::<Test>::assimilate_storage(&config, &mut t).unwrap();
+
 		sp_io::TestExternalities::new(t)
 	}
 
@@ -233,18 +607,162 @@ mod tests {
 
 			assert_eq!(
 				System::events(),
-				vec![EventRecord {
-					phase: Phase::Initialization,
-					event: Event::Dispatch(crate::Event::<Test>::MessageDispatched(
-						id,
-						Err(DispatchError::BadOrigin)
-					)),
-					topics: vec![],
-				}],
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: Event::Dispatch(crate::Event::<Test>::MessageDispatched(
+							id,
+							Err(DispatchError::BadOrigin)
+						)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: Event::Dispatch(crate::Event::<Test>::MessageQueued(id)),
+						topics: vec![],
+					},
+				],
+			);
+		})
+	}
+
+	#[test]
+	fn test_failed_message_is_queued_and_retried() {
+		new_test_ext().execute_with(|| {
+			let id = 37;
+			let source = H160::repeat_byte(7);
+			let message = Call::MockApp(mock_app::Call::execute {}).encode();
+
+			System::set_block_number(1);
+			Dispatch::dispatch(source, id, &message);
+
+			// Changes made by the failed call are rolled back
+			assert_eq!(mock_app::Attempts::<Test>::get(), 0);
+			assert!(QueuedMessages::<Test>::contains_key(id));
+			assert_eq!(QueuedMessageCount::<Test>::get(), 1);
+
+			// Retrying with too little weight is refused
+			assert_noop!(
+				Dispatch::execute_queued(Origin::signed(1), id, 999),
+				Error::<Test>::WeightLimitTooLow
+			);
+
+			// Retrying while the call still fails keeps the entry
+			assert_err_ignore_postinfo!(
+				Dispatch::execute_queued(Origin::signed(1), id, 1_000),
+				mock_app::Error::<Test>::NotReady
+			);
+			assert!(QueuedMessages::<Test>::contains_key(id));
+
+			// Nothing is acknowledged while the message can still be retried
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![]);
+
+			mock_app::Ready::<Test>::put(true);
+			assert_ok!(Dispatch::execute_queued(Origin::signed(1), id, 1_000));
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![(id, true)]);
+
+			assert_eq!(mock_app::Attempts::<Test>::get(), 1);
+			assert!(!QueuedMessages::<Test>::contains_key(id));
+			assert_eq!(QueuedMessageCount::<Test>::get(), 0);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Dispatch(crate::Event::<Test>::MessageDispatched(id, Ok(())))
+			);
+
+			assert_noop!(
+				Dispatch::execute_queued(Origin::signed(1), id, 1_000),
+				Error::<Test>::NotQueued
 			);
 		})
 	}
 
+	#[test]
+	fn test_overweight_message_is_queued() {
+		new_test_ext().execute_with(|| {
+			let id = 37;
+			let source = H160::repeat_byte(7);
+			let message = Call::MockApp(mock_app::Call::heavy {}).encode();
+
+			assert_eq!(Dispatch::dispatch_weight(&message), MaxDispatchWeight::get());
+
+			System::set_block_number(1);
+			assert_eq!(Dispatch::dispatch(source, id, &message), 0);
+
+			assert_eq!(
+				System::events()[0].event,
+				Event::Dispatch(crate::Event::<Test>::MessageDispatched(
+					id,
+					Err(Error::<Test>::Overweight.into())
+				))
+			);
+			assert!(QueuedMessages::<Test>::contains_key(id));
+
+			assert_ok!(Dispatch::execute_queued(Origin::signed(1), id, 1_000_000));
+			assert!(!QueuedMessages::<Test>::contains_key(id));
+		})
+	}
+
+	#[test]
+	fn test_queue_is_bounded() {
+		new_test_ext().execute_with(|| {
+			let source = H160::repeat_byte(7);
+			let message = Call::MockApp(mock_app::Call::execute {}).encode();
+
+			System::set_block_number(1);
+			for id in 0..3 {
+				Dispatch::dispatch(source, id, &message);
+			}
+
+			assert_eq!(QueuedMessageCount::<Test>::get(), 2);
+			assert!(!QueuedMessages::<Test>::contains_key(2));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Dispatch(crate::Event::<Test>::MessageDropped(2))
+			);
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![(2, false)]);
+		})
+	}
+
+	#[test]
+	fn test_discard_queued() {
+		new_test_ext().execute_with(|| {
+			let id = 37;
+			let source = H160::repeat_byte(7);
+			let message = Call::MockApp(mock_app::Call::execute {}).encode();
+
+			System::set_block_number(1);
+			Dispatch::dispatch(source, id, &message);
+
+			assert_noop!(Dispatch::discard_queued(Origin::signed(1), id), DispatchError::BadOrigin);
+
+			assert_ok!(Dispatch::discard_queued(Origin::root(), id));
+			assert!(!QueuedMessages::<Test>::contains_key(id));
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![(id, false)]);
+			assert_eq!(QueuedMessageCount::<Test>::get(), 0);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Dispatch(crate::Event::<Test>::QueuedMessageDiscarded(id))
+			);
+
+			assert_noop!(Dispatch::discard_queued(Origin::root(), id), Error::<Test>::NotQueued);
+		})
+	}
+
+	#[test]
+	fn test_dispatch_weight() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let message = call.encode();
+
+			assert_eq!(Dispatch::dispatch_weight(&message), call.get_dispatch_info().weight);
+			assert_eq!(Dispatch::dispatch_weight(&[1, 2, 3]), 0);
+
+			System::set_block_number(1);
+			let rejected = Call::System(frame_system::Call::set_code { code: vec![] }).encode();
+			assert_eq!(Dispatch::dispatch(H160::repeat_byte(7), 37, &rejected), 0);
+		})
+	}
+
 	#[test]
 	fn test_message_decode_failed() {
 		new_test_ext().execute_with(|| {
@@ -282,10 +800,76 @@ mod tests {
 				System::events(),
 				vec![EventRecord {
 					phase: Phase::Initialization,
-					event: Event::Dispatch(crate::Event::<Test>::MessageRejected(id)),
+					event: Event::Dispatch(crate::Event::<Test>::MessageRejected(
+						id,
+						RejectionReason::Filtered
+					)),
 					topics: vec![],
 				}],
 			);
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![(id, false)]);
+		})
+	}
+
+	#[test]
+	fn test_message_not_allowed_for_source() {
+		new_test_ext().execute_with(|| {
+			let id = 37;
+			let source = H160::repeat_byte(8);
+			let call = Call::MockApp(mock_app::Call::execute {});
+			let (pallet_index, call_index) = call_index(call.clone());
+			let message = call.encode();
+			mock_app::Ready::<Test>::put(true);
+
+			System::set_block_number(1);
+			Dispatch::dispatch(source, id, &message);
+
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Dispatch(crate::Event::<Test>::MessageRejected(
+					id,
+					RejectionReason::NotAllowed
+				))
+			);
+			assert_eq!(mock_app::Attempts::<Test>::get(), 0);
+
+			assert_noop!(
+				Dispatch::allow_call(Origin::signed(1), source, pallet_index, call_index),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Dispatch::allow_call(Origin::root(), source, pallet_index, call_index));
+
+			Dispatch::dispatch(source, id, &message);
+			assert_eq!(mock_app::Attempts::<Test>::get(), 1);
+			assert_eq!(mock_app::Acknowledged::<Test>::get(), vec![(id, false), (id, true)]);
+
+			assert_ok!(Dispatch::disallow_call(Origin::root(), source, pallet_index, call_index));
+			assert!(!AllowedCalls::<Test>::contains_key(source, (pallet_index, call_index)));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Dispatch(crate::Event::<Test>::CallDisallowed(
+					source,
+					pallet_index,
+					call_index
+				))
+			);
+		})
+	}
+
+	#[test]
+	fn test_migration_seeds_allowed_calls() {
+		new_test_ext().execute_with(|| {
+			let source = H160::repeat_byte(9);
+			StorageVersion::new(0).put::<Dispatch>();
+
+			migration::v1::SeedAllowedCalls::<Test, SeededCalls>::on_runtime_upgrade();
+			assert!(AllowedCalls::<Test>::contains_key(source, (3, 0)));
+			assert_eq!(StorageVersion::get::<Dispatch>(), STORAGE_VERSION);
+
+			// Calls disallowed after the migration stay disallowed.
+			assert_ok!(Dispatch::disallow_call(Origin::root(), source, 3, 0));
+			migration::v1::SeedAllowedCalls::<Test, SeededCalls>::on_runtime_upgrade();
+			assert!(!AllowedCalls::<Test>::contains_key(source, (3, 0)));
 		})
 	}
 }
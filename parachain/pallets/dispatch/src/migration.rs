@@ -0,0 +1,41 @@
+//! Storage migrations for the dispatch pallet.
+
+pub mod v1 {
+	use crate::{pallet::STORAGE_VERSION, AllowedCalls, Config, Pallet};
+	use frame_support::{
+		log,
+		traits::{Get, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	use sp_core::H160;
+	use sp_std::{marker::PhantomData, prelude::*};
+
+	/// Allow the calls in `Calls`, by source, pallet index and call index, on chains which were
+	/// running before calls were restricted to an allowlist.
+	///
+	/// Without it, every message is rejected after the upgrade until the calls are allowed.
+	pub struct SeedAllowedCalls<T, Calls>(PhantomData<(T, Calls)>);
+
+	impl<T, Calls> OnRuntimeUpgrade for SeedAllowedCalls<T, Calls>
+	where
+		T: Config,
+		Calls: Get<Vec<(H160, u8, u8)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let calls = Calls::get();
+			let allowed = calls.len() as Weight;
+			for (source, pallet_index, call_index) in calls {
+				<AllowedCalls<T>>::insert(source, (pallet_index, call_index), ());
+			}
+			STORAGE_VERSION.put::<Pallet<T>>();
+
+			log::info!(target: "dispatch", "Allowed {} calls", allowed);
+
+			T::DbWeight::get().reads_writes(1, allowed.saturating_add(1))
+		}
+	}
+}
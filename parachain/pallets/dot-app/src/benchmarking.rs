@@ -10,12 +10,20 @@ use sp_core::H160;
 use sp_runtime::traits::{StaticLookup, Zero};
 use sp_std::prelude::*;
 
-use crate::{primitives::wrap, Address, Call, Config as DotAppConfig, Pallet as DotApp};
-use snowbridge_core::ChannelId;
+use crate::{
+	primitives::wrap, Address, Call, Config as DotAppConfig, Escrow, Escrows, Limit,
+	Pallet as DotApp, PendingTransfers, Unlock,
+};
+use snowbridge_core::{
+	limits::{PendingTransfer, TransferLimit},
+	ChannelId, MessageOutcome,
+};
 
 use pallet_assets::Config as AssetsConfig;
-use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principal};
-use snowbridge_incentivized_channel::outbound::{Config as IncentivizedOutboundChannelConfig, Fee};
+use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principals};
+use snowbridge_incentivized_channel::outbound::{
+	Config as IncentivizedOutboundChannelConfig, Fee, Outcomes,
+};
 
 use frame_support::traits::fungible::Mutate as FungibleMutate;
 
@@ -33,8 +41,8 @@ benchmarks! {
 		let lock_account = DotApp::<T>::account_id();
 		let recipient = H160::zero();
 
-		// set principal for basic channel
-		Principal::<T>::set(Some(caller.clone()));
+		// authorize app for basic channel
+		Principals::<T>::insert(lock_account.clone(), ());
 
 		let balance = existential_deposit * 10u32.into();
 		// The amount is chosen such that balance - amount < existential_deposit
@@ -47,7 +55,7 @@ benchmarks! {
 		// Create account to store locked DOT
 		<T as DotAppConfig>::Currency::make_free_balance_be(&lock_account, 0u32.into());
 
-	}: lock(RawOrigin::Signed(caller.clone()), ChannelId::Basic, recipient, amount)
+	}: lock(RawOrigin::Signed(caller.clone()), ChannelId::BASIC, recipient, amount, None)
 	verify {
 		assert!(!balance.is_zero() && !amount.is_zero());
 		assert_eq!(<T as DotAppConfig>::Currency::free_balance(&caller), Zero::zero());
@@ -75,7 +83,7 @@ benchmarks! {
 		// Create account to store locked DOT
 		<T as DotAppConfig>::Currency::make_free_balance_be(&lock_account, 0u32.into());
 
-	}: lock(RawOrigin::Signed(caller.clone()), ChannelId::Incentivized, recipient, amount)
+	}: lock(RawOrigin::Signed(caller.clone()), ChannelId::INCENTIVIZED, recipient, amount, None)
 	verify {
 		assert!(!balance.is_zero() && !amount.is_zero());
 		assert_eq!(<T as DotAppConfig>::Currency::free_balance(&caller), Zero::zero());
@@ -84,6 +92,7 @@ benchmarks! {
 
 	// Benchmark `unlock` extrinsic under worst case conditions:
 	// * The amount is successfully unlocked
+	// * The amount is counted against the transfer limit
 	unlock {
 		let origin = T::CallOrigin::successful_origin();
 		if let Ok(caller) = T::CallOrigin::try_origin(origin.clone()) {
@@ -108,6 +117,8 @@ benchmarks! {
 		// Create account to store locked DOT
 		<T as DotAppConfig>::Currency::make_free_balance_be(&lock_account, balance);
 
+		Limit::<T>::put(TransferLimit { period: 100u32.into(), global_cap: balance, recipient_cap: balance });
+
 		let call = Call::<T>::unlock { sender: sender, recipient: recipient_lookup, amount: amount_wrapped };
 
 	}: { call.dispatch_bypass_filter(origin)? }
@@ -117,5 +128,82 @@ benchmarks! {
 		assert_eq!(<T as DotAppConfig>::Currency::free_balance(&recipient), amount);
 	}
 
+	set_transfer_limit {
+		let origin = T::LimitOrigin::successful_origin();
+		let existential_deposit = <T as DotAppConfig>::Currency::minimum_balance();
+		let limit = TransferLimit {
+			period: 100u32.into(),
+			global_cap: existential_deposit * 1000u32.into(),
+			recipient_cap: existential_deposit * 100u32.into(),
+		};
+		let call = Call::<T>::set_transfer_limit { limit: Some(limit) };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(DotApp::<T>::transfer_limit(), Some(limit));
+	}
+
+	// Benchmark `execute_pending` extrinsic under worst case conditions:
+	// * The queued amount is successfully unlocked
+	execute_pending {
+		let caller: T::AccountId = whitelisted_caller();
+		let existential_deposit = <T as DotAppConfig>::Currency::minimum_balance();
+		let lock_account = DotApp::<T>::account_id();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+
+		let balance = existential_deposit * 10u32.into();
+		let amount = existential_deposit * 8u32.into();
+
+		<T as DotAppConfig>::Currency::make_free_balance_be(&recipient, 0u32.into());
+		<T as DotAppConfig>::Currency::make_free_balance_be(&lock_account, balance);
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Unlock { sender: H160::zero(), recipient: recipient.clone(), amount },
+			execute_after: 0u32.into(),
+		});
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(<T as DotAppConfig>::Currency::free_balance(&recipient), amount);
+	}
+
+	cancel_pending {
+		let origin = T::LimitOrigin::successful_origin();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Unlock {
+				sender: H160::zero(),
+				recipient,
+				amount: <T as DotAppConfig>::Currency::minimum_balance(),
+			},
+			execute_after: 0u32.into(),
+		});
+
+		let call = Call::<T>::cancel_pending { id: 0 };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(0));
+	}
+
+	// Benchmark `settle_escrow` extrinsic under worst case conditions:
+	// * the message was not delivered, so the escrowed amount is unlocked to the sender
+	settle_escrow {
+		let caller: T::AccountId = whitelisted_caller();
+		let existential_deposit = <T as DotAppConfig>::Currency::minimum_balance();
+		let lock_account = DotApp::<T>::account_id();
+		let sender: T::AccountId = account("sender", 0, 0);
+
+		let balance = existential_deposit * 10u32.into();
+		let amount = existential_deposit * 8u32.into();
+
+		<T as DotAppConfig>::Currency::make_free_balance_be(&sender, 0u32.into());
+		<T as DotAppConfig>::Currency::make_free_balance_be(&lock_account, balance);
+
+		Escrows::<T>::insert(ChannelId::INCENTIVIZED, 0, Escrow { sender: sender.clone(), amount });
+		Outcomes::<T>::insert(0, MessageOutcome::Undelivered);
+	}: _(RawOrigin::Signed(caller), ChannelId::INCENTIVIZED, 0)
+	verify {
+		assert_eq!(<T as DotAppConfig>::Currency::free_balance(&sender), amount);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_tester(), crate::mock::Test);
 }
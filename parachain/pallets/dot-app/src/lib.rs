@@ -13,6 +13,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	traits::{
@@ -26,10 +27,15 @@ use frame_support::{
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
 
-use snowbridge_core::{ChannelId, OutboundRouter};
-use sp_core::{H160, U256};
-use sp_runtime::traits::{AccountIdConversion, StaticLookup};
-use sp_std::prelude::*;
+use scale_info::TypeInfo;
+use snowbridge_core::{
+	limits::{PendingTransfer, PendingTransferOf, TransferLimit, TransferLimiter, TransferUsage},
+	outbound_submit_weight, AppId, BridgeComponent, ChannelId, CircuitBreaker, MessageOutcome,
+	OutboundRouter,
+};
+use sp_core::{RuntimeDebug, H160, U256};
+use sp_runtime::traits::{AccountIdConversion, Saturating, StaticLookup};
+use sp_std::{marker::PhantomData, prelude::*};
 
 use payload::OutboundPayload;
 use primitives::{unwrap, wrap};
@@ -40,6 +46,21 @@ pub use pallet::*;
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// An unlock requested from Ethereum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Unlock<AccountId, Balance> {
+	pub sender: H160,
+	pub recipient: AccountId,
+	pub amount: Balance,
+}
+
+/// DOT locked for a message with an expiry, which is unlocked if the message is not delivered.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Escrow<AccountId, Balance> {
+	pub sender: AccountId,
+	pub amount: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 
@@ -62,25 +83,100 @@ pub mod pallet {
 
 		type CallOrigin: EnsureOrigin<Self::Origin, Success = H160>;
 
+		/// Origin which can set transfer limits and approve or cancel queued transfers
+		type LimitOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of blocks a transfer over the limit is queued before anyone can execute it
+		#[pallet::constant]
+		type TransferDelay: Get<Self::BlockNumber>;
+
 		type PalletId: Get<PalletId>;
 
 		#[pallet::constant]
 		type Decimals: Get<u32>;
 
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Limiter::<T>::prune(now, remaining_weight, T::DbWeight::get())
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		Locked(T::AccountId, H160, BalanceOf<T>),
 		Unlocked(H160, T::AccountId, BalanceOf<T>),
+		TransferLimitSet(Option<TransferLimit<BalanceOf<T>, T::BlockNumber>>),
+		/// An unlock exceeded the transfer limit and was queued. \[id, sender, recipient, amount\]
+		TransferQueued(u64, H160, T::AccountId, BalanceOf<T>),
+		PendingTransferExecuted(u64),
+		PendingTransferCancelled(u64),
+		/// The escrow of a lock was settled. \[channel_id, message_id, outcome\]
+		EscrowSettled(ChannelId, u64, MessageOutcome),
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn address)]
 	pub type Address<T: Config> = StorageValue<_, H160, ValueQuery>;
 
+	/// Caps on the amount of DOT unlocked per period. Unlocks are not limited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_limit)]
+	pub type Limit<T: Config> =
+		StorageValue<_, TransferLimit<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// Amount of DOT unlocked in the rolling period.
+	#[pallet::storage]
+	pub type GlobalUsage<T: Config> =
+		StorageValue<_, TransferUsage<BalanceOf<T>, T::BlockNumber>, ValueQuery>;
+
+	/// Amount of DOT unlocked to each recipient in the rolling period.
+	#[pallet::storage]
+	pub type RecipientUsage<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		TransferUsage<BalanceOf<T>, T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub type PendingTransfers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		PendingTransfer<Unlock<T::AccountId, BalanceOf<T>>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type NextPendingId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Key in `RecipientUsage` after which usage is pruned next.
+	#[pallet::storage]
+	pub type UsagePruneCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Escrows of locks with an expiry, by channel and message ID.
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub type Escrows<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ChannelId,
+		Twox64Concat,
+		u64,
+		Escrow<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Illegal conversion between native and wrapped DOT.
@@ -89,6 +185,14 @@ pub mod pallet {
 		/// we've tested. If however the bridge or the peer Ethereum contract
 		/// is exploited, then all bets are off.
 		Overflow,
+		/// There is no queued transfer with this id.
+		UnknownPendingTransfer,
+		/// The queued transfer cannot be executed until the transfer delay has elapsed.
+		TransferDelayNotElapsed,
+		/// There is no escrow for this message.
+		UnknownEscrow,
+		/// The outcome of the message is not yet known.
+		OutcomeUnknown,
 	}
 
 	#[pallet::genesis_config]
@@ -112,20 +216,27 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight({
-			match channel_id {
-				ChannelId::Basic => T::WeightInfo::lock_basic_channel(),
-				ChannelId::Incentivized => T::WeightInfo::lock_incentivized_channel(),
-			}
-		})]
+		/// Lock DOT to mint wrapped DOT on the Ethereum side
+		///
+		/// If `expires_at` is set, the locked amount is held in escrow until the outcome of the
+		/// message is known.
+		#[pallet::weight(
+			outbound_submit_weight(
+				T::WeightInfo::lock_basic_channel(),
+				T::WeightInfo::lock_incentivized_channel(),
+			)
+			.saturating_add(T::DbWeight::get().writes(1))
+		)]
 		#[transactional]
 		pub fn lock(
 			origin: OriginFor<T>,
 			channel_id: ChannelId,
 			recipient: H160,
 			amount: BalanceOf<T>,
+			expires_at: Option<u64>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Dot))?;
 
 			T::Currency::transfer(&who, &Self::account_id(), amount, AllowDeath)?;
 
@@ -138,7 +249,21 @@ pub mod pallet {
 				amount: amount_wrapped,
 			};
 
-			T::OutboundRouter::submit(channel_id, &who, <Address<T>>::get(), &message.encode())?;
+			let message_id = T::OutboundRouter::submit(
+				channel_id,
+				&Self::account_id(),
+				&who,
+				<Address<T>>::get(),
+				&message.encode(),
+				expires_at,
+			)?;
+			if expires_at.is_some() {
+				<Escrows<T>>::insert(
+					channel_id,
+					message_id,
+					Escrow { sender: who.clone(), amount },
+				);
+			}
 			Self::deposit_event(Event::Locked(who.clone(), recipient, amount));
 			Ok(())
 		}
@@ -155,13 +280,85 @@ pub mod pallet {
 			if who != <Address<T>>::get() {
 				return Err(DispatchError::BadOrigin.into());
 			}
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Dot))?;
 
 			let amount_unwrapped =
 				unwrap::<T>(amount, T::Decimals::get()).ok_or(Error::<T>::Overflow)?;
 
 			let recipient = T::Lookup::lookup(recipient)?;
-			T::Currency::transfer(&Self::account_id(), &recipient, amount_unwrapped, KeepAlive)?;
-			Self::deposit_event(Event::Unlocked(sender, recipient, amount_unwrapped));
+			let unlock = Unlock { sender, recipient, amount: amount_unwrapped };
+			if !Self::consume_limit(&unlock.recipient, amount_unwrapped) {
+				Self::queue_transfer(unlock);
+				return Ok(())
+			}
+
+			Self::do_unlock(unlock)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_transfer_limit())]
+		pub fn set_transfer_limit(
+			origin: OriginFor<T>,
+			limit: Option<TransferLimit<BalanceOf<T>, T::BlockNumber>>,
+		) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			<Limit<T>>::set(limit);
+			Self::deposit_event(Event::TransferLimitSet(limit));
+			Ok(())
+		}
+
+		/// Execute a queued transfer once the transfer delay has elapsed
+		#[pallet::weight(T::WeightInfo::execute_pending())]
+		#[transactional]
+		pub fn execute_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+			let pending = Self::take_pending(id)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= pending.execute_after, Error::<T>::TransferDelayNotElapsed);
+			Self::execute_transfer(id, pending.transfer)
+		}
+
+		/// Execute a queued transfer without waiting for the transfer delay
+		#[pallet::weight(T::WeightInfo::execute_pending())]
+		#[transactional]
+		pub fn approve_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			let pending = Self::take_pending(id)?;
+			Self::execute_transfer(id, pending.transfer)
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_pending())]
+		pub fn cancel_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			Self::take_pending(id)?;
+			Self::deposit_event(Event::PendingTransferCancelled(id));
+			Ok(())
+		}
+
+		/// Settle the escrow of a lock once the outcome of its message is known, unlocking the
+		/// amount to the sender if the message was not delivered
+		#[pallet::weight(T::WeightInfo::settle_escrow())]
+		#[transactional]
+		pub fn settle_escrow(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+			message_id: u64,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Dot))?;
+
+			let escrow =
+				<Escrows<T>>::take(channel_id, message_id).ok_or(Error::<T>::UnknownEscrow)?;
+			let outcome = T::OutboundRouter::take_outcome(channel_id, message_id)?
+				.ok_or(Error::<T>::OutcomeUnknown)?;
+			if outcome == MessageOutcome::Undelivered {
+				T::Currency::transfer(
+					&Self::account_id(),
+					&escrow.sender,
+					escrow.amount,
+					AllowDeath,
+				)?;
+			}
+			Self::deposit_event(Event::EscrowSettled(channel_id, message_id, outcome));
 			Ok(())
 		}
 	}
@@ -170,5 +367,60 @@ pub mod pallet {
 		pub fn account_id() -> T::AccountId {
 			T::PalletId::get().into_account()
 		}
+
+		/// Count an unlock against the transfer limit.
+		///
+		/// Returns `false`, and counts nothing, if the unlock would exceed a cap.
+		fn consume_limit(recipient: &T::AccountId, amount: BalanceOf<T>) -> bool {
+			let limit = match <Limit<T>>::get() {
+				Some(limit) => limit,
+				None => return true,
+			};
+			let now = <frame_system::Pallet<T>>::block_number();
+			<GlobalUsage<T>>::mutate(|global| {
+				Limiter::<T>::consume(&limit, global, recipient, amount, now)
+			})
+		}
+
+		fn queue_transfer(unlock: Unlock<T::AccountId, BalanceOf<T>>) {
+			let execute_after =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::TransferDelay::get());
+			let (sender, recipient, amount) =
+				(unlock.sender, unlock.recipient.clone(), unlock.amount);
+			let id = Limiter::<T>::queue(unlock, execute_after);
+			Self::deposit_event(Event::TransferQueued(id, sender, recipient, amount));
+		}
+
+		fn take_pending(id: u64) -> Result<PendingTransferOf<Limiter<T>>, DispatchError> {
+			Limiter::<T>::take(id).ok_or_else(|| Error::<T>::UnknownPendingTransfer.into())
+		}
+
+		fn execute_transfer(id: u64, unlock: Unlock<T::AccountId, BalanceOf<T>>) -> DispatchResult {
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Dot))?;
+			Self::do_unlock(unlock)?;
+			Self::deposit_event(Event::PendingTransferExecuted(id));
+			Ok(())
+		}
+
+		fn do_unlock(unlock: Unlock<T::AccountId, BalanceOf<T>>) -> DispatchResult {
+			let Unlock { sender, recipient, amount } = unlock;
+			T::Currency::transfer(&Self::account_id(), &recipient, amount, KeepAlive)?;
+			Self::deposit_event(Event::Unlocked(sender, recipient, amount));
+			Ok(())
+		}
 	}
 }
+
+/// Transfer limits on DOT unlocked, by recipient.
+pub struct Limiter<T>(PhantomData<T>);
+
+impl<T: Config> TransferLimiter for Limiter<T> {
+	type Recipient = T::AccountId;
+	type Balance = BalanceOf<T>;
+	type BlockNumber = T::BlockNumber;
+	type Transfer = Unlock<T::AccountId, BalanceOf<T>>;
+	type RecipientUsage = RecipientUsage<T>;
+	type PendingTransfers = PendingTransfers<T>;
+	type NextPendingId = NextPendingId<T>;
+	type PruneCursor = UsagePruneCursor<T>;
+}
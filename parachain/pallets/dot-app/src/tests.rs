@@ -3,7 +3,8 @@ use crate::{
 	Config,
 };
 use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, traits::Currency};
-use snowbridge_core::ChannelId;
+use snowbridge_core::{limits::TransferLimit, ChannelId, CommitmentVersion, MessageOutcome};
+use snowbridge_incentivized_channel::outbound::{self as incentivized_outbound, Outcomes};
 use sp_core::H160;
 use sp_keyring::AccountKeyring as Keyring;
 
@@ -22,9 +23,10 @@ fn should_lock() {
 
 		assert_ok!(DotApp::lock(
 			Origin::signed(sender.clone()),
-			ChannelId::Incentivized,
+			ChannelId::INCENTIVIZED,
 			recipient.clone(),
-			amount
+			amount,
+			None
 		));
 
 		assert_eq!(Balances::total_balance(&DotApp::account_id()), amount);
@@ -112,20 +114,150 @@ fn should_not_lock_on_add_commitment_failure() {
 		for _ in 0..3 {
 			let _ = DotApp::lock(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				recipient.clone(),
 				1,
+				None,
 			);
 		}
 
 		assert_noop!(
 			DotApp::lock(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				recipient.clone(),
-				amount
+				amount,
+				None
 			),
 			snowbridge_incentivized_channel::outbound::Error::<Test>::QueueSizeLimitReached
 		);
 	});
 }
+
+#[test]
+fn should_queue_unlock_over_transfer_limit() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let sender = H160::repeat_byte(7);
+		let recipient: AccountId = Keyring::Bob.into();
+		let amount = 100;
+		let amount_wrapped =
+			crate::primitives::wrap::<Test>(amount, <Test as Config>::Decimals::get()).unwrap();
+
+		let _ = Balances::deposit_creating(&DotApp::account_id(), 500);
+		assert_ok!(DotApp::set_transfer_limit(
+			Origin::root(),
+			Some(TransferLimit { period: 100, global_cap: 150, recipient_cap: 150 }),
+		));
+
+		for _ in 0..2 {
+			assert_ok!(DotApp::unlock(
+				snowbridge_dispatch::RawOrigin(peer_contract).into(),
+				sender,
+				recipient.clone(),
+				amount_wrapped,
+			));
+		}
+		assert_eq!(Balances::total_balance(&recipient), amount);
+		assert_eq!(
+			Event::DotApp(crate::Event::<Test>::TransferQueued(
+				0,
+				sender,
+				recipient.clone(),
+				amount
+			)),
+			last_event()
+		);
+
+		assert_noop!(
+			DotApp::execute_pending(Origin::signed(recipient.clone()), 0),
+			crate::Error::<Test>::TransferDelayNotElapsed
+		);
+		System::set_block_number(System::block_number() + 10);
+		assert_ok!(DotApp::execute_pending(Origin::signed(recipient.clone()), 0));
+
+		assert_eq!(Balances::total_balance(&recipient), amount * 2);
+		assert_eq!(Event::DotApp(crate::Event::<Test>::PendingTransferExecuted(0)), last_event());
+	});
+}
+
+#[test]
+fn should_cancel_pending_unlock() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let recipient: AccountId = Keyring::Bob.into();
+		let amount_wrapped =
+			crate::primitives::wrap::<Test>(100, <Test as Config>::Decimals::get()).unwrap();
+
+		let _ = Balances::deposit_creating(&DotApp::account_id(), 500);
+		assert_ok!(DotApp::set_transfer_limit(
+			Origin::root(),
+			Some(TransferLimit { period: 100, global_cap: 50, recipient_cap: 50 }),
+		));
+		assert_ok!(DotApp::unlock(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			H160::repeat_byte(7),
+			recipient.clone(),
+			amount_wrapped,
+		));
+
+		assert_noop!(
+			DotApp::cancel_pending(Origin::signed(recipient.clone()), 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(DotApp::cancel_pending(Origin::root(), 0));
+		assert!(DotApp::pending_transfer(0).is_none());
+		assert_eq!(Balances::total_balance(&recipient), 0);
+	});
+}
+
+#[test]
+fn should_settle_escrow_of_undelivered_lock() {
+	new_tester().execute_with(|| {
+		let sender: AccountId = Keyring::Bob.into();
+		let amount = 100;
+
+		let _ = Balances::deposit_creating(&sender, amount * 2);
+
+		assert_ok!(incentivized_outbound::Pallet::<Test>::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V2]
+		));
+		assert_ok!(DotApp::lock(
+			Origin::signed(sender.clone()),
+			ChannelId::INCENTIVIZED,
+			H160::repeat_byte(2),
+			amount,
+			Some(1000)
+		));
+		let id = incentivized_outbound::Pallet::<Test>::queued_messages()[0].id;
+		assert_eq!(DotApp::escrow(ChannelId::INCENTIVIZED, id).unwrap().amount, amount);
+
+		assert_noop!(
+			DotApp::settle_escrow(Origin::signed(sender.clone()), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::OutcomeUnknown
+		);
+
+		Outcomes::<Test>::insert(id, MessageOutcome::Undelivered);
+		assert_ok!(DotApp::settle_escrow(
+			Origin::signed(sender.clone()),
+			ChannelId::INCENTIVIZED,
+			id
+		));
+
+		assert_eq!(Balances::total_balance(&sender), amount * 2);
+		assert_eq!(Balances::total_balance(&DotApp::account_id()), 0);
+		assert_eq!(
+			Event::DotApp(crate::Event::<Test>::EscrowSettled(
+				ChannelId::INCENTIVIZED,
+				id,
+				MessageOutcome::Undelivered
+			)),
+			last_event()
+		);
+		assert_noop!(
+			DotApp::settle_escrow(Origin::signed(sender), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::UnknownEscrow
+		);
+	});
+}
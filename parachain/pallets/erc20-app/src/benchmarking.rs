@@ -4,15 +4,23 @@ use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
 use frame_system::RawOrigin;
 use sp_core::H160;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{AccountIdConversion, StaticLookup};
 use sp_std::prelude::*;
 
-use crate::{Address, AssetId, Call, Config as Erc20AppConfig, Pallet as Erc20App};
-use snowbridge_core::ChannelId;
+use crate::{
+	Address, AssetId, Call, Config as Erc20AppConfig, Escrow, Escrows, Limit, Mint,
+	Pallet as Erc20App, PendingTransfers,
+};
+use snowbridge_core::{
+	limits::{PendingTransfer, TransferLimit},
+	ChannelId, MessageOutcome,
+};
 
 use pallet_assets::Config as AssetsConfig;
-use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principal};
-use snowbridge_incentivized_channel::outbound::{Config as IncentivizedOutboundChannelConfig, Fee};
+use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principals};
+use snowbridge_incentivized_channel::outbound::{
+	Config as IncentivizedOutboundChannelConfig, Fee, Outcomes,
+};
 
 use frame_support::traits::{
 	fungible::Mutate as FungibleMutate,
@@ -33,8 +41,8 @@ benchmarks! {
 		let recipient = H160::repeat_byte(2);
 		let amount: u128 = 500;
 
-		// set principal for basic channel
-		Principal::<T>::set(Some(caller.clone()));
+		// authorize app for basic channel
+		Principals::<T>::insert(T::PalletId::get().into_account(), ());
 
 		// create wrapped token
 		let origin = T::CallOrigin::successful_origin();
@@ -50,7 +58,7 @@ benchmarks! {
 
 		T::Assets::mint_into(asset_id, &caller, amount)?;
 
-	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::Basic, token, recipient, amount)
+	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::BASIC, token, recipient, amount, None)
 	verify {
 		assert_eq!(T::Assets::balance(asset_id, &caller), 0);
 	}
@@ -79,13 +87,21 @@ benchmarks! {
 
 		T::Assets::mint_into(asset_id, &caller, amount)?;
 
-	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::Incentivized, token, recipient, amount)
+	}: burn(
+		RawOrigin::Signed(caller.clone()),
+		ChannelId::INCENTIVIZED,
+		token,
+		recipient,
+		amount,
+		None
+	)
 	verify {
 		assert_eq!(T::Assets::balance(asset_id, &caller), 0);
 	}
 
 	// Benchmark `mint` extrinsic under worst case conditions:
 	// * `mint` successfully adds amount to recipient account
+	// * the amount is counted against the transfer limit of the token
 	mint {
 		let origin = T::CallOrigin::successful_origin();
 		if let Ok(caller) = T::CallOrigin::try_origin(origin.clone()) {
@@ -112,6 +128,8 @@ benchmarks! {
 
 		let asset_id = <AssetId<T>>::get(token).unwrap();
 
+		Limit::<T>::insert(token, TransferLimit { period: 100u32.into(), global_cap: 1000, recipient_cap: 1000 });
+
 		let call = Call::<T>::mint { token: token, sender: sender, recipient: recipient_lookup, amount : amount, destination: None };
 
 	}: { call.dispatch_bypass_filter(origin)? }
@@ -119,5 +137,102 @@ benchmarks! {
 		assert_eq!(T::Assets::balance(asset_id, &recipient), amount);
 	}
 
+	set_transfer_limit {
+		let origin = T::LimitOrigin::successful_origin();
+		let token = H160::repeat_byte(2);
+		let limit = TransferLimit { period: 100u32.into(), global_cap: 1000, recipient_cap: 100 };
+		let call = Call::<T>::set_transfer_limit { token: token, limit: Some(limit) };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Erc20App::<T>::transfer_limit(token), Some(limit));
+	}
+
+	// Benchmark `execute_pending` extrinsic under worst case conditions:
+	// * the queued transfer is minted to the recipient
+	execute_pending {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = H160::repeat_byte(2);
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let amount = 500;
+
+		// create wrapped token
+		let origin = T::CallOrigin::successful_origin();
+		if let Ok(addr) = T::CallOrigin::try_origin(origin.clone()) {
+				<Address<T>>::put(addr);
+		} else {
+				return Err("Failed to extract caller address from origin".into());
+		}
+		let call = Call::<T>::create { token: token };
+		call.dispatch_bypass_filter(origin)?;
+
+		let asset_id = <AssetId<T>>::get(token).unwrap();
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Mint {
+				token,
+				sender: H160::zero(),
+				recipient: recipient.clone(),
+				amount,
+				destination: None,
+			},
+			execute_after: 0u32.into(),
+		});
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(T::Assets::balance(asset_id, &recipient), amount);
+	}
+
+	cancel_pending {
+		let origin = T::LimitOrigin::successful_origin();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Mint {
+				token: H160::repeat_byte(2),
+				sender: H160::zero(),
+				recipient,
+				amount: 500,
+				destination: None,
+			},
+			execute_after: 0u32.into(),
+		});
+
+		let call = Call::<T>::cancel_pending { id: 0 };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(0));
+	}
+
+	// Benchmark `settle_escrow` extrinsic under worst case conditions:
+	// * the message was not delivered, so the escrowed tokens are minted back to the sender
+	settle_escrow {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = H160::repeat_byte(2);
+		let sender: T::AccountId = account("sender", 0, 0);
+		let amount = 500;
+
+		// create wrapped token
+		let origin = T::CallOrigin::successful_origin();
+		if let Ok(addr) = T::CallOrigin::try_origin(origin.clone()) {
+				<Address<T>>::put(addr);
+		} else {
+				return Err("Failed to extract caller address from origin".into());
+		}
+		let call = Call::<T>::create { token: token };
+		call.dispatch_bypass_filter(origin)?;
+
+		let asset_id = <AssetId<T>>::get(token).unwrap();
+
+		Escrows::<T>::insert(ChannelId::INCENTIVIZED, 0, Escrow {
+			token,
+			sender: sender.clone(),
+			amount,
+		});
+		Outcomes::<T>::insert(0, MessageOutcome::Undelivered);
+	}: _(RawOrigin::Signed(caller), ChannelId::INCENTIVIZED, 0)
+	verify {
+		assert_eq!(T::Assets::balance(asset_id, &sender), amount);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_tester(), crate::mock::Test,);
 }
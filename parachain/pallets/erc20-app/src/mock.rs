@@ -19,7 +19,7 @@ use sp_runtime::{
 
 use snowbridge_core::{
 	assets::{RemoteParachain, XcmReserveTransfer},
-	ChannelId,
+	ChannelId, MessageOutcome,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -129,6 +129,11 @@ impl snowbridge_dispatch::Config for Test {
 	type MessageId = u64;
 	type Call = Call;
 	type CallFilter = Everything;
+	type MaxDispatchWeight = frame_support::traits::ConstU64<1_000_000_000>;
+	type MaxQueueSize = frame_support::traits::ConstU32<16>;
+	type DiscardOrigin = frame_system::EnsureRoot<AccountId>;
+	type AllowlistOrigin = frame_system::EnsureRoot<AccountId>;
+	type Acknowledger = ();
 }
 
 pub struct OutboundRouter<T>(PhantomData<T>);
@@ -140,15 +145,35 @@ where
 {
 	fn submit(
 		channel_id: ChannelId,
-		who: &T::AccountId,
+		app: &T::AccountId,
+		sender: &T::AccountId,
 		target: H160,
 		payload: &[u8],
-	) -> DispatchResult {
+		expires_at: Option<u64>,
+	) -> Result<u64, DispatchError> {
+		match channel_id {
+			ChannelId::BASIC => snowbridge_basic_channel::outbound::Pallet::<T>::submit(
+				app, target, payload, expires_at,
+			),
+			ChannelId::INCENTIVIZED =>
+				snowbridge_incentivized_channel::outbound::Pallet::<T>::submit(
+					sender, target, payload, expires_at,
+				),
+			_ => Err(DispatchError::Other("Unknown channel")),
+		}
+	}
+
+	fn take_outcome(
+		channel_id: ChannelId,
+		message_id: u64,
+	) -> Result<Option<MessageOutcome>, DispatchError> {
 		match channel_id {
-			ChannelId::Basic =>
-				snowbridge_basic_channel::outbound::Pallet::<T>::submit(who, target, payload),
-			ChannelId::Incentivized =>
-				snowbridge_incentivized_channel::outbound::Pallet::<T>::submit(who, target, payload),
+			ChannelId::BASIC =>
+				Ok(snowbridge_basic_channel::outbound::Pallet::<T>::take_outcome(message_id)),
+			ChannelId::INCENTIVIZED => Ok(
+				snowbridge_incentivized_channel::outbound::Pallet::<T>::take_outcome(message_id),
+			),
+			_ => Err(DispatchError::Other("Unknown channel")),
 		}
 	}
 }
@@ -159,6 +184,7 @@ parameter_types! {
 	pub const Erc20AppPalletId: PalletId = PalletId(*b"erc20app");
 	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 3;
+	pub const MaxQueuedMessages: u32 = 3;
 }
 
 pub type Ether = ItemOf<Assets, EtherAssetId, AccountId>;
@@ -168,7 +194,12 @@ impl snowbridge_basic_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ();
+	type EthereumTime = ();
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
@@ -177,8 +208,14 @@ impl snowbridge_incentivized_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type FeeCurrency = Ether;
 	type SetFeeOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<AccountId>;
+	type FeeFeederOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ();
+	type EthereumTime = ();
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 pub struct XcmAssetTransfererMock<T>(PhantomData<T>);
@@ -204,6 +241,9 @@ impl crate::Config for Test {
 	type NextAssetId = AssetRegistry;
 	type OutboundRouter = OutboundRouter<Test>;
 	type CallOrigin = snowbridge_dispatch::EnsureEthereumAccount;
+	type LimitOrigin = frame_system::EnsureRoot<AccountId>;
+	type TransferDelay = frame_support::traits::ConstU64<10>;
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 	type XcmReserveTransfer = XcmAssetTransfererMock<Self>;
 }
@@ -1,6 +1,9 @@
 use crate::mock::{new_tester, AccountId, Assets, Erc20App, Event, Origin, System, Test};
 use frame_support::{assert_noop, assert_ok};
-use snowbridge_core::{assets::RemoteParachain, ChannelId};
+use snowbridge_core::{
+	assets::RemoteParachain, limits::TransferLimit, ChannelId, CommitmentVersion, MessageOutcome,
+};
+use snowbridge_incentivized_channel::outbound::{self as incentivized_outbound, Outcomes};
 use sp_core::H160;
 use sp_keyring::AccountKeyring as Keyring;
 
@@ -88,10 +91,11 @@ fn burn_should_emit_bridge_event() {
 
 		assert_ok!(Erc20App::burn(
 			Origin::signed(bob.clone()),
-			ChannelId::Incentivized,
+			ChannelId::INCENTIVIZED,
 			token_id,
 			recipient.clone(),
-			20
+			20,
+			None
 		));
 
 		assert_eq!(
@@ -120,22 +124,195 @@ fn should_not_burn_on_commitment_failure() {
 		for _ in 0..3 {
 			let _ = Erc20App::burn(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				token_id,
 				recipient.clone(),
 				20,
+				None,
 			);
 		}
 
 		assert_noop!(
 			Erc20App::burn(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				token_id,
 				recipient.clone(),
-				20
+				20,
+				None
 			),
 			snowbridge_incentivized_channel::outbound::Error::<Test>::QueueSizeLimitReached
 		);
 	});
 }
+
+#[test]
+fn limits_are_per_token() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let token = H160::repeat_byte(2);
+		let other_token = H160::repeat_byte(4);
+		let sender = H160::repeat_byte(3);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(Erc20App::create(snowbridge_dispatch::RawOrigin(peer_contract).into(), token));
+		assert_ok!(Erc20App::create(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			other_token
+		));
+		assert_ok!(Erc20App::set_transfer_limit(
+			Origin::root(),
+			token,
+			Some(TransferLimit { period: 100, global_cap: 100, recipient_cap: 100 }),
+		));
+
+		for token in [token, other_token] {
+			assert_ok!(Erc20App::mint(
+				snowbridge_dispatch::RawOrigin(peer_contract).into(),
+				token,
+				sender,
+				recipient.clone(),
+				150,
+				None
+			));
+		}
+
+		assert_eq!(Assets::balance(<AssetId<Test>>::get(token).unwrap(), &recipient), 0);
+		assert_eq!(Assets::balance(<AssetId<Test>>::get(other_token).unwrap(), &recipient), 150);
+		assert_eq!(Erc20App::pending_transfer(0).unwrap().transfer.token, token);
+	});
+}
+
+#[test]
+fn executes_pending_transfer_after_delay() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let token = H160::repeat_byte(2);
+		let sender = H160::repeat_byte(3);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(Erc20App::create(snowbridge_dispatch::RawOrigin(peer_contract).into(), token));
+		assert_ok!(Erc20App::set_transfer_limit(
+			Origin::root(),
+			token,
+			Some(TransferLimit { period: 100, global_cap: 100, recipient_cap: 100 }),
+		));
+		assert_ok!(Erc20App::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			token,
+			sender,
+			recipient.clone(),
+			150,
+			None
+		));
+		assert_eq!(
+			Event::Erc20App(crate::Event::<Test>::TransferQueued(
+				0,
+				token,
+				sender,
+				recipient.clone(),
+				150
+			)),
+			last_event()
+		);
+
+		assert_noop!(
+			Erc20App::execute_pending(Origin::signed(recipient.clone()), 0),
+			crate::Error::<Test>::TransferDelayNotElapsed
+		);
+
+		System::set_block_number(System::block_number() + 10);
+		assert_ok!(Erc20App::execute_pending(Origin::signed(recipient.clone()), 0));
+		assert_eq!(Assets::balance(<AssetId<Test>>::get(token).unwrap(), &recipient), 150);
+	});
+}
+
+#[test]
+fn cancels_pending_transfer() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let token = H160::repeat_byte(2);
+		let recipient: AccountId = Keyring::Bob.into();
+
+		assert_ok!(Erc20App::create(snowbridge_dispatch::RawOrigin(peer_contract).into(), token));
+		assert_ok!(Erc20App::set_transfer_limit(
+			Origin::root(),
+			token,
+			Some(TransferLimit { period: 100, global_cap: 100, recipient_cap: 100 }),
+		));
+		assert_ok!(Erc20App::mint(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			token,
+			H160::repeat_byte(3),
+			recipient.clone(),
+			150,
+			None
+		));
+
+		assert_ok!(Erc20App::cancel_pending(Origin::root(), 0));
+		assert_noop!(
+			Erc20App::approve_pending(Origin::root(), 0),
+			crate::Error::<Test>::UnknownPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn settles_escrow_of_undelivered_burn() {
+	new_tester().execute_with(|| {
+		let peer_contract = H160::repeat_byte(1);
+		let token_id = H160::repeat_byte(2);
+		let bob: AccountId = Keyring::Bob.into();
+
+		assert_ok!(Erc20App::create(
+			snowbridge_dispatch::RawOrigin(peer_contract).into(),
+			token_id,
+		));
+		let asset_id = <AssetId<Test>>::get(token_id).unwrap();
+		Assets::mint_into(asset_id, &bob, 500).unwrap();
+
+		assert_ok!(incentivized_outbound::Pallet::<Test>::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V2]
+		));
+		assert_ok!(Erc20App::burn(
+			Origin::signed(bob.clone()),
+			ChannelId::INCENTIVIZED,
+			token_id,
+			H160::repeat_byte(3),
+			20,
+			Some(1000)
+		));
+		assert_eq!(Assets::balance(asset_id, &bob), 480);
+
+		let id = incentivized_outbound::Pallet::<Test>::queued_messages()[0].id;
+		let escrow = Erc20App::escrow(ChannelId::INCENTIVIZED, id).unwrap();
+		assert_eq!((escrow.token, escrow.amount), (token_id, 20));
+
+		assert_noop!(
+			Erc20App::settle_escrow(Origin::signed(bob.clone()), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::OutcomeUnknown
+		);
+
+		Outcomes::<Test>::insert(id, MessageOutcome::Undelivered);
+		assert_ok!(Erc20App::settle_escrow(
+			Origin::signed(bob.clone()),
+			ChannelId::INCENTIVIZED,
+			id
+		));
+
+		assert_eq!(Assets::balance(asset_id, &bob), 500);
+		assert_eq!(
+			Event::Erc20App(crate::Event::<Test>::EscrowSettled(
+				ChannelId::INCENTIVIZED,
+				id,
+				MessageOutcome::Undelivered
+			)),
+			last_event()
+		);
+		assert_noop!(
+			Erc20App::settle_escrow(Origin::signed(bob), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::UnknownEscrow
+		);
+	});
+}
@@ -3,18 +3,26 @@ use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError
 use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
 use frame_system::RawOrigin;
 use sp_core::H160;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{AccountIdConversion, StaticLookup};
 use sp_std::prelude::*;
 
 use frame_support::traits::fungible::Mutate;
 
-use crate::{Address, Call, Config as EtherAppConfig, Pallet as EtherApp};
-use snowbridge_core::ChannelId;
+use crate::{
+	Address, Call, Config as EtherAppConfig, Escrow, Escrows, Limit, Mint, Pallet as EtherApp,
+	PendingTransfers,
+};
+use snowbridge_core::{
+	limits::{PendingTransfer, TransferLimit},
+	ChannelId, MessageOutcome,
+};
 
 use frame_support::traits::fungible::Inspect;
 use pallet_assets::Config as AssetsConfig;
-use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principal};
-use snowbridge_incentivized_channel::outbound::{Config as IncentivizedOutboundChannelConfig, Fee};
+use snowbridge_basic_channel::outbound::{Config as BasicOutboundChannelConfig, Principals};
+use snowbridge_incentivized_channel::outbound::{
+	Config as IncentivizedOutboundChannelConfig, Fee, Outcomes,
+};
 
 pub struct Pallet<T: Config>(EtherApp<T>);
 
@@ -29,11 +37,11 @@ benchmarks! {
 		let recipient = H160::repeat_byte(2);
 		let amount = 500;
 
-		// set principal for basic channel
-		Principal::<T>::set(Some(caller.clone()));
+		// authorize app for basic channel
+		Principals::<T>::insert(T::PalletId::get().into_account(), ());
 
 		T::Asset::mint_into(&caller, amount)?;
-	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::Basic, recipient, amount)
+	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::BASIC, recipient, amount, None)
 	verify {
 		assert_eq!(T::Asset::balance(&caller), 0);
 	}
@@ -50,13 +58,14 @@ benchmarks! {
 
 		T::Asset::mint_into(&caller, amount)?;
 
-	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::Incentivized, recipient, amount)
+	}: burn(RawOrigin::Signed(caller.clone()), ChannelId::INCENTIVIZED, recipient, amount, None)
 	verify {
 		assert_eq!(T::Asset::balance(&caller), 0);
 	}
 
 	// Benchmark `mint` extrinsic under worst case conditions:
 	// * `mint` successfully adds amount to recipient account
+	// * the amount is counted against the transfer limit
 	mint {
 		let origin = T::CallOrigin::successful_origin();
 		if let Ok(caller) = T::CallOrigin::try_origin(origin.clone()) {
@@ -70,11 +79,72 @@ benchmarks! {
 		let sender = H160::zero();
 		let amount = 500;
 
+		Limit::<T>::put(TransferLimit { period: 100u32.into(), global_cap: 1000, recipient_cap: 1000 });
+
 		let call = Call::<T>::mint { sender: sender, recipient: recipient_lookup, amount: amount, destination: None  };
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		assert_eq!(T::Asset::balance(&recipient), amount);
 	}
 
+	set_transfer_limit {
+		let origin = T::LimitOrigin::successful_origin();
+		let limit = TransferLimit { period: 100u32.into(), global_cap: 1000, recipient_cap: 100 };
+		let call = Call::<T>::set_transfer_limit { limit: Some(limit) };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(EtherApp::<T>::transfer_limit(), Some(limit));
+	}
+
+	// Benchmark `execute_pending` extrinsic under worst case conditions:
+	// * the queued transfer is minted to the recipient
+	execute_pending {
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let amount = 500;
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Mint {
+				sender: H160::zero(),
+				recipient: recipient.clone(),
+				amount,
+				destination: None,
+			},
+			execute_after: 0u32.into(),
+		});
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(T::Asset::balance(&recipient), amount);
+	}
+
+	cancel_pending {
+		let origin = T::LimitOrigin::successful_origin();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+
+		PendingTransfers::<T>::insert(0, PendingTransfer {
+			transfer: Mint { sender: H160::zero(), recipient, amount: 500, destination: None },
+			execute_after: 0u32.into(),
+		});
+
+		let call = Call::<T>::cancel_pending { id: 0 };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(0));
+	}
+
+	// Benchmark `settle_escrow` extrinsic under worst case conditions:
+	// * the message was not delivered, so the escrowed amount is minted back to the sender
+	settle_escrow {
+		let caller: T::AccountId = whitelisted_caller();
+		let sender: T::AccountId = account("sender", 0, 0);
+		let amount = 500;
+
+		Escrows::<T>::insert(ChannelId::INCENTIVIZED, 0, Escrow { sender: sender.clone(), amount });
+		Outcomes::<T>::insert(0, MessageOutcome::Undelivered);
+	}: _(RawOrigin::Signed(caller), ChannelId::INCENTIVIZED, 0)
+	verify {
+		assert_eq!(T::Asset::balance(&sender), amount);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_tester(), crate::mock::Test,);
 }
@@ -13,6 +13,25 @@
 //! ### Dispatchable Calls
 //!
 //! - `burn`: Burn an ETH balance.
+//! - `settle_escrow`: Settle the escrow of a burn with an expiry, once the outcome of its message
+//!   is known.
+//! - `set_transfer_limit`: Set the caps on ETH minted per period. Callable by `LimitOrigin`.
+//! - `execute_pending`: Mint a queued transfer once `TransferDelay` has elapsed.
+//! - `approve_pending`: Mint a queued transfer immediately. Callable by `LimitOrigin`.
+//! - `cancel_pending`: Drop a queued transfer. Callable by `LimitOrigin`.
+//!
+//! ## Transfer Limits
+//!
+//! Mints requested from Ethereum are counted against a global cap, and a cap per recipient, over a
+//! rolling period. A mint which would exceed either cap is queued instead of being executed, which
+//! limits the damage from a compromised light client or peer contract. Usage of recipients which
+//! has left the period is pruned in `on_idle`.
+//!
+//! ## Escrow
+//!
+//! A burn may set an Ethereum timestamp after which its message can no longer be delivered. The
+//! burned amount is then held in escrow until the outbound channel knows the outcome of the
+//! message, and minted back to the sender if the message was not delivered.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod payload;
@@ -27,6 +46,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	log,
@@ -34,19 +54,38 @@ use frame_support::{
 	transactional, PalletId,
 };
 use frame_system::ensure_signed;
-use sp_core::H160;
-use sp_runtime::traits::StaticLookup;
-use sp_std::prelude::*;
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H160};
+use sp_runtime::traits::{AccountIdConversion, Saturating, StaticLookup};
+use sp_std::{marker::PhantomData, prelude::*};
 
 use snowbridge_core::{
 	assets::{RemoteParachain, XcmReserveTransfer},
-	ChannelId, OutboundRouter,
+	limits::{PendingTransfer, PendingTransferOf, TransferLimit, TransferLimiter, TransferUsage},
+	outbound_submit_weight, AppId, BridgeComponent, ChannelId, CircuitBreaker, MessageOutcome,
+	OutboundRouter,
 };
 
 pub use pallet::*;
 use payload::OutboundPayload;
 pub use weights::WeightInfo;
 
+/// A mint requested from Ethereum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Mint<AccountId> {
+	pub sender: H160,
+	pub recipient: AccountId,
+	pub amount: u128,
+	pub destination: Option<RemoteParachain>,
+}
+
+/// ETH burned for a message with an expiry, which is minted back if the message is not delivered.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Escrow<AccountId> {
+	pub sender: AccountId,
+	pub amount: u128,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 
@@ -71,27 +110,107 @@ pub mod pallet {
 
 		type CallOrigin: EnsureOrigin<Self::Origin, Success = H160>;
 
+		/// Origin which can set transfer limits and approve or cancel queued transfers
+		type LimitOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of blocks a transfer over the limit is queued before anyone can execute it
+		#[pallet::constant]
+		type TransferDelay: Get<Self::BlockNumber>;
+
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
 		type WeightInfo: WeightInfo;
 
 		type XcmReserveTransfer: XcmReserveTransfer<Self::AccountId, Self::Origin>;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Limiter::<T>::prune(now, remaining_weight, T::DbWeight::get())
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		Burned(T::AccountId, H160, u128),
 		Minted(H160, T::AccountId, u128),
+		TransferLimitSet(Option<TransferLimit<u128, T::BlockNumber>>),
+		/// A mint exceeded the transfer limit and was queued. \[id, sender, recipient, amount\]
+		TransferQueued(u64, H160, T::AccountId, u128),
+		PendingTransferExecuted(u64),
+		PendingTransferCancelled(u64),
+		/// The escrow of a burn was settled. \[channel_id, message_id, outcome\]
+		EscrowSettled(ChannelId, u64, MessageOutcome),
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn address)]
 	pub(super) type Address<T: Config> = StorageValue<_, H160, ValueQuery>;
 
+	/// Caps on the amount of ETH minted per period. Mints are not limited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_limit)]
+	pub type Limit<T: Config> = StorageValue<_, TransferLimit<u128, T::BlockNumber>, OptionQuery>;
+
+	/// Amount of ETH minted in the rolling period.
+	#[pallet::storage]
+	pub type GlobalUsage<T: Config> =
+		StorageValue<_, TransferUsage<u128, T::BlockNumber>, ValueQuery>;
+
+	/// Amount of ETH minted to each recipient in the rolling period.
+	#[pallet::storage]
+	pub type RecipientUsage<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		TransferUsage<u128, T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub type PendingTransfers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		PendingTransfer<Mint<T::AccountId>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type NextPendingId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Key in `RecipientUsage` after which usage is pruned next.
+	#[pallet::storage]
+	pub type UsagePruneCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Escrows of burns with an expiry, by channel and message ID.
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub type Escrows<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ChannelId,
+		Twox64Concat,
+		u64,
+		Escrow<T::AccountId>,
+		OptionQuery,
+	>;
+
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// There is no queued transfer with this id.
+		UnknownPendingTransfer,
+		/// The queued transfer cannot be executed until the transfer delay has elapsed.
+		TransferDelayNotElapsed,
+		/// There is no escrow for this message.
+		UnknownEscrow,
+		/// The outcome of the message is not yet known.
+		OutcomeUnknown,
+	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
@@ -115,27 +234,47 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Users can burn their holdings to release funds on the Ethereum side
-		#[pallet::weight({
-			match channel_id {
-				ChannelId::Basic => T::WeightInfo::burn_basic_channel(),
-				ChannelId::Incentivized => T::WeightInfo::burn_incentivized_channel(),
-			}
-		})]
+		///
+		/// If `expires_at` is set, the burned amount is held in escrow until the outcome of the
+		/// message is known.
+		#[pallet::weight(
+			outbound_submit_weight(
+				T::WeightInfo::burn_basic_channel(),
+				T::WeightInfo::burn_incentivized_channel(),
+			)
+			.saturating_add(T::DbWeight::get().writes(1))
+		)]
 		#[transactional]
 		pub fn burn(
 			origin: OriginFor<T>,
 			channel_id: ChannelId,
 			recipient: H160,
 			amount: u128,
+			expires_at: Option<u64>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Eth))?;
 
 			T::Asset::burn_from(&who, amount)?;
 
 			let message =
 				OutboundPayload { sender: who.clone(), recipient: recipient.clone(), amount };
 
-			T::OutboundRouter::submit(channel_id, &who, <Address<T>>::get(), &message.encode())?;
+			let message_id = T::OutboundRouter::submit(
+				channel_id,
+				&T::PalletId::get().into_account(),
+				&who,
+				<Address<T>>::get(),
+				&message.encode(),
+				expires_at,
+			)?;
+			if expires_at.is_some() {
+				<Escrows<T>>::insert(
+					channel_id,
+					message_id,
+					Escrow { sender: who.clone(), amount },
+				);
+			}
 			Self::deposit_event(Event::Burned(who.clone(), recipient, amount));
 
 			Ok(())
@@ -154,8 +293,117 @@ pub mod pallet {
 			if who != <Address<T>>::get() {
 				return Err(DispatchError::BadOrigin.into());
 			}
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Eth))?;
 
 			let recipient = T::Lookup::lookup(recipient)?;
+			let mint = Mint { sender, recipient, amount, destination };
+			if !Self::consume_limit(&mint.recipient, amount) {
+				Self::queue_transfer(mint);
+				return Ok(())
+			}
+
+			Self::do_mint(mint)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_transfer_limit())]
+		pub fn set_transfer_limit(
+			origin: OriginFor<T>,
+			limit: Option<TransferLimit<u128, T::BlockNumber>>,
+		) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			<Limit<T>>::set(limit);
+			Self::deposit_event(Event::TransferLimitSet(limit));
+			Ok(())
+		}
+
+		/// Execute a queued transfer once the transfer delay has elapsed
+		#[pallet::weight(T::WeightInfo::execute_pending())]
+		#[transactional]
+		pub fn execute_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+			let pending = Self::take_pending(id)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= pending.execute_after, Error::<T>::TransferDelayNotElapsed);
+			Self::execute_transfer(id, pending.transfer)
+		}
+
+		/// Execute a queued transfer without waiting for the transfer delay
+		#[pallet::weight(T::WeightInfo::execute_pending())]
+		#[transactional]
+		pub fn approve_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			let pending = Self::take_pending(id)?;
+			Self::execute_transfer(id, pending.transfer)
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_pending())]
+		pub fn cancel_pending(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimitOrigin::ensure_origin(origin)?;
+			Self::take_pending(id)?;
+			Self::deposit_event(Event::PendingTransferCancelled(id));
+			Ok(())
+		}
+
+		/// Settle the escrow of a burn once the outcome of its message is known, minting the
+		/// burned amount back to the sender if the message was not delivered
+		#[pallet::weight(T::WeightInfo::settle_escrow())]
+		#[transactional]
+		pub fn settle_escrow(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+			message_id: u64,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Eth))?;
+
+			let escrow =
+				<Escrows<T>>::take(channel_id, message_id).ok_or(Error::<T>::UnknownEscrow)?;
+			let outcome = T::OutboundRouter::take_outcome(channel_id, message_id)?
+				.ok_or(Error::<T>::OutcomeUnknown)?;
+			if outcome == MessageOutcome::Undelivered {
+				T::Asset::mint_into(&escrow.sender, escrow.amount)?;
+			}
+			Self::deposit_event(Event::EscrowSettled(channel_id, message_id, outcome));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Count a mint against the transfer limit.
+		///
+		/// Returns `false`, and counts nothing, if the mint would exceed a cap.
+		fn consume_limit(recipient: &T::AccountId, amount: u128) -> bool {
+			let limit = match <Limit<T>>::get() {
+				Some(limit) => limit,
+				None => return true,
+			};
+			let now = <frame_system::Pallet<T>>::block_number();
+			<GlobalUsage<T>>::mutate(|global| {
+				Limiter::<T>::consume(&limit, global, recipient, amount, now)
+			})
+		}
+
+		fn queue_transfer(mint: Mint<T::AccountId>) {
+			let execute_after =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::TransferDelay::get());
+			let (sender, recipient, amount) = (mint.sender, mint.recipient.clone(), mint.amount);
+			let id = Limiter::<T>::queue(mint, execute_after);
+			Self::deposit_event(Event::TransferQueued(id, sender, recipient, amount));
+		}
+
+		fn take_pending(id: u64) -> Result<PendingTransferOf<Limiter<T>>, DispatchError> {
+			Limiter::<T>::take(id).ok_or_else(|| Error::<T>::UnknownPendingTransfer.into())
+		}
+
+		fn execute_transfer(id: u64, mint: Mint<T::AccountId>) -> DispatchResult {
+			T::CircuitBreaker::ensure_active(BridgeComponent::App(AppId::Eth))?;
+			Self::do_mint(mint)?;
+			Self::deposit_event(Event::PendingTransferExecuted(id));
+			Ok(())
+		}
+
+		fn do_mint(mint: Mint<T::AccountId>) -> DispatchResult {
+			let Mint { sender, recipient, amount, destination } = mint;
 			T::Asset::mint_into(&recipient, amount)?;
 			Self::deposit_event(Event::Minted(sender, recipient.clone(), amount));
 
@@ -178,3 +426,17 @@ pub mod pallet {
 		}
 	}
 }
+
+/// Transfer limits on ETH minted, by recipient.
+pub struct Limiter<T>(PhantomData<T>);
+
+impl<T: Config> TransferLimiter for Limiter<T> {
+	type Recipient = T::AccountId;
+	type Balance = u128;
+	type BlockNumber = T::BlockNumber;
+	type Transfer = Mint<T::AccountId>;
+	type RecipientUsage = RecipientUsage<T>;
+	type PendingTransfers = PendingTransfers<T>;
+	type NextPendingId = NextPendingId<T>;
+	type PruneCursor = UsagePruneCursor<T>;
+}
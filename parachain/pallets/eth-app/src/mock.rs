@@ -18,7 +18,7 @@ use sp_runtime::{
 
 use snowbridge_core::{
 	assets::{RemoteParachain, XcmReserveTransfer},
-	ChannelId,
+	ChannelId, MessageOutcome,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -123,6 +123,11 @@ impl snowbridge_dispatch::Config for Test {
 	type MessageId = u64;
 	type Call = Call;
 	type CallFilter = Everything;
+	type MaxDispatchWeight = frame_support::traits::ConstU64<1_000_000_000>;
+	type MaxQueueSize = frame_support::traits::ConstU32<16>;
+	type DiscardOrigin = frame_system::EnsureRoot<AccountId>;
+	type AllowlistOrigin = frame_system::EnsureRoot<AccountId>;
+	type Acknowledger = ();
 }
 
 pub struct OutboundRouter<T>(PhantomData<T>);
@@ -134,15 +139,35 @@ where
 {
 	fn submit(
 		channel_id: ChannelId,
-		who: &T::AccountId,
+		app: &T::AccountId,
+		sender: &T::AccountId,
 		target: H160,
 		payload: &[u8],
-	) -> DispatchResult {
+		expires_at: Option<u64>,
+	) -> Result<u64, DispatchError> {
 		match channel_id {
-			ChannelId::Basic =>
-				snowbridge_basic_channel::outbound::Pallet::<T>::submit(who, target, payload),
-			ChannelId::Incentivized =>
-				snowbridge_incentivized_channel::outbound::Pallet::<T>::submit(who, target, payload),
+			ChannelId::BASIC => snowbridge_basic_channel::outbound::Pallet::<T>::submit(
+				app, target, payload, expires_at,
+			),
+			ChannelId::INCENTIVIZED =>
+				snowbridge_incentivized_channel::outbound::Pallet::<T>::submit(
+					sender, target, payload, expires_at,
+				),
+			_ => Err(DispatchError::Other("Unknown channel")),
+		}
+	}
+
+	fn take_outcome(
+		channel_id: ChannelId,
+		message_id: u64,
+	) -> Result<Option<MessageOutcome>, DispatchError> {
+		match channel_id {
+			ChannelId::BASIC =>
+				Ok(snowbridge_basic_channel::outbound::Pallet::<T>::take_outcome(message_id)),
+			ChannelId::INCENTIVIZED => Ok(
+				snowbridge_incentivized_channel::outbound::Pallet::<T>::take_outcome(message_id),
+			),
+			_ => Err(DispatchError::Other("Unknown channel")),
 		}
 	}
 }
@@ -150,6 +175,7 @@ where
 parameter_types! {
 	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 3;
+	pub const MaxQueuedMessages: u32 = 3;
 }
 
 impl snowbridge_basic_channel::outbound::Config for Test {
@@ -157,7 +183,12 @@ impl snowbridge_basic_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type SetPrincipalOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ();
+	type EthereumTime = ();
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
@@ -166,8 +197,14 @@ impl snowbridge_incentivized_channel::outbound::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type FeeCurrency = Ether;
 	type SetFeeOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<AccountId>;
+	type FeeFeederOrigin = frame_system::EnsureRoot<AccountId>;
+	type Verifier = ();
+	type EthereumTime = ();
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
@@ -201,6 +238,9 @@ impl crate::Config for Test {
 	type PalletId = EtherAppPalletId;
 	type XcmReserveTransfer = XcmAssetTransfererMock<Self>;
 	type CallOrigin = snowbridge_dispatch::EnsureEthereumAccount;
+	type LimitOrigin = frame_system::EnsureRoot<AccountId>;
+	type TransferDelay = frame_support::traits::ConstU64<10>;
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
@@ -220,6 +260,14 @@ pub fn new_tester() -> sp_io::TestExternalities {
 	};
 	GenesisBuild::<Test>::assimilate_storage(&assets_config, &mut storage).unwrap();
 
+	let basic_channel_config: snowbridge_basic_channel::outbound::GenesisConfig<Test> =
+		snowbridge_basic_channel::outbound::GenesisConfig {
+			interval: 1,
+			principals: vec![EtherAppPalletId::get().into_account()],
+			target_channel: Default::default(),
+		};
+	GenesisBuild::<Test>::assimilate_storage(&basic_channel_config, &mut storage).unwrap();
+
 	let mut ext: sp_io::TestExternalities = storage.into();
 	ext.execute_with(|| System::set_block_number(1));
 	ext
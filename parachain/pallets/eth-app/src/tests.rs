@@ -1,12 +1,23 @@
-use crate::mock::{new_tester, AccountId, Ether, EtherApp, Event, Origin, System, Test};
+use crate::{
+	mock::{new_tester, AccountId, Ether, EtherApp, Event, Origin, System, Test},
+	RecipientUsage,
+};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::fungible::{Inspect, Mutate},
+	dispatch::DispatchError,
+	traits::{
+		fungible::{Inspect, Mutate},
+		Hooks,
+	},
+	weights::Weight,
 };
 use sp_core::H160;
 use sp_keyring::AccountKeyring as Keyring;
 
-use snowbridge_core::{assets::RemoteParachain, ChannelId};
+use snowbridge_core::{
+	assets::RemoteParachain, limits::TransferLimit, ChannelId, CommitmentVersion, MessageOutcome,
+};
+use snowbridge_incentivized_channel::outbound::{self as incentivized_outbound, Outcomes};
 
 fn last_event() -> Event {
 	System::events().pop().expect("Event expected").event
@@ -70,15 +81,58 @@ fn burn_should_emit_bridge_event() {
 
 		assert_ok!(EtherApp::burn(
 			Origin::signed(bob.clone()),
-			ChannelId::Incentivized,
+			ChannelId::INCENTIVIZED,
 			recipient.clone(),
-			20
+			20,
+			None
 		));
 
 		assert_eq!(Event::EtherApp(crate::Event::<Test>::Burned(bob, recipient, 20)), last_event());
 	});
 }
 
+#[test]
+fn burn_on_basic_channel_is_submitted_by_app() {
+	new_tester().execute_with(|| {
+		let recipient = H160::repeat_byte(2);
+		let bob: AccountId = Keyring::Bob.into();
+
+		Ether::mint_into(&bob, 500).unwrap();
+
+		assert_ok!(EtherApp::burn(
+			Origin::signed(bob.clone()),
+			ChannelId::BASIC,
+			recipient.clone(),
+			20,
+			None
+		));
+
+		assert_eq!(Ether::balance(&bob), 480);
+		assert_eq!(Event::EtherApp(crate::Event::<Test>::Burned(bob, recipient, 20)), last_event());
+	});
+}
+
+#[test]
+fn should_not_burn_on_unknown_channel() {
+	new_tester().execute_with(|| {
+		let sender: AccountId = Keyring::Bob.into();
+
+		Ether::mint_into(&sender, 500).unwrap();
+
+		assert_noop!(
+			EtherApp::burn(
+				Origin::signed(sender.clone()),
+				ChannelId(7),
+				H160::repeat_byte(9),
+				20,
+				None
+			),
+			DispatchError::Other("Unknown channel")
+		);
+		assert_eq!(Ether::balance(&sender), 500);
+	});
+}
+
 #[test]
 fn should_not_burn_on_commitment_failure() {
 	new_tester().execute_with(|| {
@@ -91,20 +145,298 @@ fn should_not_burn_on_commitment_failure() {
 		for _ in 0..3 {
 			let _ = EtherApp::burn(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				recipient.clone(),
 				20,
+				None,
 			);
 		}
 
 		assert_noop!(
 			EtherApp::burn(
 				Origin::signed(sender.clone()),
-				ChannelId::Incentivized,
+				ChannelId::INCENTIVIZED,
 				recipient.clone(),
-				20
+				20,
+				None
 			),
 			snowbridge_incentivized_channel::outbound::Error::<Test>::QueueSizeLimitReached
 		);
 	});
 }
+
+fn set_limit(period: u64, global_cap: u128, recipient_cap: u128) {
+	assert_ok!(EtherApp::set_transfer_limit(
+		Origin::root(),
+		Some(TransferLimit { period, global_cap, recipient_cap }),
+	));
+}
+
+fn mint(recipient: &AccountId, amount: u128) {
+	assert_ok!(EtherApp::mint(
+		snowbridge_dispatch::RawOrigin(H160::repeat_byte(1)).into(),
+		H160::repeat_byte(7),
+		recipient.clone(),
+		amount,
+		None,
+	));
+}
+
+#[test]
+fn set_transfer_limit_requires_limit_origin() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		assert_noop!(
+			EtherApp::set_transfer_limit(Origin::signed(bob), None),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn mints_within_transfer_limit() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+		set_limit(100, 150, 100);
+
+		mint(&bob, 100);
+		mint(&charlie, 50);
+
+		assert_eq!(Ether::balance(&bob), 100);
+		assert_eq!(Ether::balance(&charlie), 50);
+		assert!(EtherApp::pending_transfer(0).is_none());
+	});
+}
+
+#[test]
+fn queues_mint_over_recipient_cap() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		set_limit(100, 1000, 100);
+
+		mint(&bob, 60);
+		mint(&bob, 60);
+
+		assert_eq!(Ether::balance(&bob), 60);
+		assert_eq!(
+			Event::EtherApp(crate::Event::<Test>::TransferQueued(
+				0,
+				H160::repeat_byte(7),
+				bob.clone(),
+				60
+			)),
+			last_event()
+		);
+		let pending = EtherApp::pending_transfer(0).unwrap();
+		assert_eq!(pending.transfer.recipient, bob);
+		assert_eq!(pending.execute_after, 11);
+	});
+}
+
+#[test]
+fn queues_mint_over_global_cap() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+		set_limit(100, 100, 100);
+
+		mint(&bob, 60);
+		mint(&charlie, 60);
+
+		assert_eq!(Ether::balance(&charlie), 0);
+		assert!(EtherApp::pending_transfer(0).is_some());
+
+		// Queued mints are not counted against the limit
+		mint(&charlie, 40);
+		assert_eq!(Ether::balance(&charlie), 40);
+	});
+}
+
+#[test]
+fn transfer_limit_counts_mints_over_rolling_period() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		set_limit(100, 1000, 100);
+
+		mint(&bob, 60);
+		System::set_block_number(90);
+		mint(&bob, 40);
+
+		// The mint at block 1 is still in the period
+		System::set_block_number(101);
+		mint(&bob, 40);
+		assert_eq!(Ether::balance(&bob), 100);
+		assert!(EtherApp::pending_transfer(0).is_some());
+
+		System::set_block_number(117);
+		mint(&bob, 60);
+		assert_eq!(Ether::balance(&bob), 160);
+	});
+}
+
+#[test]
+fn prunes_expired_recipient_usage() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		let charlie: AccountId = Keyring::Charlie.into();
+		set_limit(100, 1000, 100);
+
+		mint(&bob, 60);
+		System::set_block_number(150);
+		mint(&charlie, 60);
+
+		EtherApp::on_idle(200, Weight::MAX);
+		assert!(!RecipientUsage::<Test>::contains_key(&bob));
+		assert!(RecipientUsage::<Test>::contains_key(&charlie));
+	});
+}
+
+#[test]
+fn executes_pending_transfer_after_delay() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		set_limit(100, 100, 100);
+		mint(&bob, 150);
+
+		assert_noop!(
+			EtherApp::execute_pending(Origin::signed(bob.clone()), 0),
+			crate::Error::<Test>::TransferDelayNotElapsed
+		);
+
+		System::set_block_number(11);
+		assert_ok!(EtherApp::execute_pending(Origin::signed(bob.clone()), 0));
+
+		assert_eq!(Ether::balance(&bob), 150);
+		assert_eq!(Event::EtherApp(crate::Event::<Test>::PendingTransferExecuted(0)), last_event());
+		assert_noop!(
+			EtherApp::execute_pending(Origin::signed(bob), 0),
+			crate::Error::<Test>::UnknownPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn approves_pending_transfer_before_delay() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		set_limit(100, 100, 100);
+		mint(&bob, 150);
+
+		assert_noop!(
+			EtherApp::approve_pending(Origin::signed(bob.clone()), 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EtherApp::approve_pending(Origin::root(), 0));
+
+		assert_eq!(Ether::balance(&bob), 150);
+		assert!(EtherApp::pending_transfer(0).is_none());
+	});
+}
+
+#[test]
+fn cancels_pending_transfer() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		set_limit(100, 100, 100);
+		mint(&bob, 150);
+
+		assert_ok!(EtherApp::cancel_pending(Origin::root(), 0));
+		assert_eq!(
+			Event::EtherApp(crate::Event::<Test>::PendingTransferCancelled(0)),
+			last_event()
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			EtherApp::execute_pending(Origin::signed(bob.clone()), 0),
+			crate::Error::<Test>::UnknownPendingTransfer
+		);
+		assert_eq!(Ether::balance(&bob), 0);
+	});
+}
+
+fn burn_expiring(sender: &AccountId, amount: u128) -> u64 {
+	assert_ok!(incentivized_outbound::Pallet::<Test>::set_commitment_versions(
+		Origin::root(),
+		vec![CommitmentVersion::V2]
+	));
+	assert_ok!(EtherApp::burn(
+		Origin::signed(sender.clone()),
+		ChannelId::INCENTIVIZED,
+		H160::repeat_byte(2),
+		amount,
+		Some(1000)
+	));
+	incentivized_outbound::Pallet::<Test>::queued_messages().last().unwrap().id
+}
+
+#[test]
+fn burn_with_expiry_is_escrowed() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&bob, 500).unwrap();
+
+		let id = burn_expiring(&bob, 20);
+
+		let escrow = EtherApp::escrow(ChannelId::INCENTIVIZED, id).unwrap();
+		assert_eq!(escrow.sender, bob);
+		assert_eq!(escrow.amount, 20);
+	});
+}
+
+#[test]
+fn settles_escrow_of_undelivered_burn() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&bob, 500).unwrap();
+		let id = burn_expiring(&bob, 20);
+		let balance = Ether::balance(&bob);
+
+		assert_noop!(
+			EtherApp::settle_escrow(Origin::signed(bob.clone()), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::OutcomeUnknown
+		);
+
+		Outcomes::<Test>::insert(id, MessageOutcome::Undelivered);
+		assert_ok!(EtherApp::settle_escrow(
+			Origin::signed(bob.clone()),
+			ChannelId::INCENTIVIZED,
+			id
+		));
+
+		assert_eq!(Ether::balance(&bob), balance + 20);
+		assert_eq!(
+			Event::EtherApp(crate::Event::<Test>::EscrowSettled(
+				ChannelId::INCENTIVIZED,
+				id,
+				MessageOutcome::Undelivered
+			)),
+			last_event()
+		);
+		assert_noop!(
+			EtherApp::settle_escrow(Origin::signed(bob), ChannelId::INCENTIVIZED, id),
+			crate::Error::<Test>::UnknownEscrow
+		);
+	});
+}
+
+#[test]
+fn settles_escrow_of_delivered_burn() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&bob, 500).unwrap();
+		let id = burn_expiring(&bob, 20);
+		let balance = Ether::balance(&bob);
+
+		Outcomes::<Test>::insert(id, MessageOutcome::Delivered);
+		assert_ok!(EtherApp::settle_escrow(
+			Origin::signed(bob.clone()),
+			ChannelId::INCENTIVIZED,
+			id
+		));
+
+		assert_eq!(Ether::balance(&bob), balance);
+		assert!(EtherApp::escrow(ChannelId::INCENTIVIZED, id).is_none());
+	});
+}
@@ -3,6 +3,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-11-25, STEPS: `50`, REPEAT: 10, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("spec.json"), DB CACHE: 128
+//!
+//! Entries marked ESTIMATE are not output of the benchmark CLI. They must be replaced by
+//! re-running the benchmarks before this pallet is deployed to a production chain.
 
 // Executed Command:
 // target/release/snowbridge
@@ -40,6 +43,10 @@ pub trait WeightInfo {
 	fn burn_basic_channel() -> Weight;
 	fn burn_incentivized_channel() -> Weight;
 	fn mint() -> Weight;
+	fn set_transfer_limit() -> Weight;
+	fn execute_pending() -> Weight;
+	fn cancel_pending() -> Weight;
+	fn settle_escrow() -> Weight;
 }
 
 /// Weights for eth_app using the Snowbridge node and recommended hardware.
@@ -55,10 +62,37 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// ESTIMATE: the benchmarked `mint`, plus reading the transfer limit and counting the
+	// amount in the usage of the recipient and of all recipients.
 	fn mint() -> Weight {
-		(29_679_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+		(36_128_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// ESTIMATE: a single write, as for other setters of a storage value.
+	fn set_transfer_limit() -> Weight {
+		(12_903_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: `mint` of the queued transfer, without the limit, plus taking it from the
+	// queue.
+	fn execute_pending() -> Weight {
+		(34_217_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// ESTIMATE: taking a transfer from the queue.
+	fn cancel_pending() -> Weight {
+		(15_882_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: taking the escrow and the outcome of the message, plus minting the amount
+	// back to the sender. Replace with the output of the `settle_escrow` benchmark.
+	fn settle_escrow() -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 }
 
@@ -74,9 +108,36 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// ESTIMATE: the benchmarked `mint`, plus reading the transfer limit and counting the
+	// amount in the usage of the recipient and of all recipients.
 	fn mint() -> Weight {
-		(29_679_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+		(36_128_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// ESTIMATE: a single write, as for other setters of a storage value.
+	fn set_transfer_limit() -> Weight {
+		(12_903_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: `mint` of the queued transfer, without the limit, plus taking it from the
+	// queue.
+	fn execute_pending() -> Weight {
+		(34_217_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// ESTIMATE: taking a transfer from the queue.
+	fn cancel_pending() -> Weight {
+		(15_882_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: taking the escrow and the outcome of the message, plus minting the amount
+	// back to the sender. Replace with the output of the `settle_escrow` benchmark.
+	fn settle_escrow() -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 }
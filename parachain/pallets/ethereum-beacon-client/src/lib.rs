@@ -10,7 +10,7 @@ mod ssz;
 mod config;
 
 use codec::{Decode, Encode};
-use frame_support::{dispatch::DispatchResult, log, transactional};
+use frame_support::{dispatch::{DispatchError, DispatchResult}, log, transactional};
 use frame_system::ensure_signed;
 use scale_info::TypeInfo;
 use sp_core::H256;
@@ -18,6 +18,8 @@ use sp_io::hashing::sha2_256;
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use snowbridge_beacon::{SyncCommittee, BeaconHeader, SyncAggregate, ForkData, Root, Domain, PublicKey, SigningData, ExecutionHeader, BeaconBlock};
+use snowbridge_core::{Message, Proof, VerificationInfo, Verifier};
+use snowbridge_ethereum::{header::check_receipt_proof, Log};
 
 const SLOTS_PER_EPOCH: u64 = 32;
 
@@ -126,6 +128,10 @@ pub mod pallet {
 		SignatureVerificationFailed,
 		NoBranchExpected,
 		HeaderNotFinalized,
+		MissingHeader,
+		InvalidProof,
+		UnsupportedProof,
+		DecodeFailed,
 	}
 
 	#[pallet::hooks]
@@ -758,4 +764,44 @@ pub mod pallet {
 			Ok(sync_committee)
 		}
 	}
+
+	impl<T: Config> Verifier for Pallet<T> {
+		/// Verify a message by verifying the existence of the corresponding
+		/// Ethereum log in an execution block. Returns the log if successful.
+		fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
+			let proof = match &message.proof {
+				Proof::Execution(proof) => proof,
+				_ => return Err(Error::<T>::UnsupportedProof.into()),
+			};
+
+			let header = <ExecutionHeaders<T>>::get(proof.block_hash)
+				.ok_or(Error::<T>::MissingHeader)?;
+
+			let receipt = match check_receipt_proof(header.receipts_root, &proof.data.1) {
+				Some(Ok(receipt)) => receipt,
+				_ => return Err(Error::<T>::InvalidProof.into()),
+			};
+
+			let log: Log = rlp::decode(&message.data).map_err(|_| Error::<T>::DecodeFailed)?;
+
+			let log_index = receipt.log_index(&log).ok_or(Error::<T>::InvalidProof)?;
+
+			log::trace!(
+				target: "ethereum-beacon-client",
+				"💫 Verified log for transaction at index {} in execution block {}.",
+				proof.tx_index,
+				proof.block_hash
+			);
+
+			Ok((
+				log,
+				VerificationInfo {
+					block_number: header.block_number,
+					block_hash: proof.block_hash,
+					timestamp: header.timestamp,
+					log_index: log_index as u32,
+				},
+			))
+		}
+	}
 }
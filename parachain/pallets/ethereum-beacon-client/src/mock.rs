@@ -8,6 +8,7 @@ use sp_runtime::{
 };
 use frame_system as system;
 use hex_literal::hex;
+use snowbridge_core::ReceiptProof;
 use snowbridge_beacon::{Attestation, AttestationData, AttesterSlashing, BeaconBlock, Body, Checkpoint, Eth1Data, ExecutionPayload, IndexedAttestation};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -5769,3 +5770,36 @@ pub fn get_current_sync_committee_for_header_update() -> SyncCommittee {
 		aggregate_pubkey: ethereum_beacon_client::PublicKey(hex!("88ecc4fb9e9d4fe6315d0229ec0cf0c5d1f6b9b54f649474617201f76ae26152726fa76496b44236912542a36308727f").into())
 	}
 }
+
+// from https://ropsten.etherscan.io/tx/0x3541903322b74942aa9dd436ac6277d36d874865c35032fe915518d2659fc64c
+pub fn get_message_with_execution_proof() -> Message {
+	Message {
+		data: hex!("f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
+		proof: Proof::Execution(ReceiptProof {
+			block_hash: hex!("a5b871f284c883a67a525e8001a106463234dd968c49eeb300d9382d64f25619").into(),
+			tx_index: 25,
+			data: (
+				vec![
+					hex!("587bceddb4e618b754faf26ab09b1b10fbf957dfc6f0f79207d73e23c4324af9").to_vec(),
+					hex!("e94a68e76d4bb10270ee9f1f50a4155a96ca51bc1f35328113d4c201a25dd8dd").to_vec(),
+					hex!("a90abaedf9feb13afac23e55b89961fc795048f32ceb6502bb94eead9f361a08").to_vec(),
+				],
+				vec![
+					hex!("f871a01392e60e279b56496b25be598f4c7206038bf800589a7c30e86d71554fa41ee9a0e94a68e76d4bb10270ee9f1f50a4155a96ca51bc1f35328113d4c201a25dd8dd808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(),
+					hex!("f90151a03313afdd3bcd74ac9ff430a1739bf4a5a32c4140ee06855ba3e98afb65290bdca06627246dc7d76a237549835b0f6adc480fc1069e9f2bde21d3fc44ab80798695a00ba55e9264f81f6a217f1af33729948632f5cdf04ff2143ef2e952a9462e7bf5a055b2d9e14ac5cbc6871328e071b405ac9a6cf8dad2fa2455ab4a8b960085e441a0d6634b5e368571ef8fc977db39865006084d57a8c27e2ec5ce43851db514ba21a07ef093602d5faf3881949f823cd8f9b65579c73f4578a8b8849316b21ad41308a0edef934e89bb383ea29b324a5744160a706cfd6de092bdb3b7c7ed376016fa89a0fc2e5f1471d608133309328cc7e3dfa6a570c7f1d734f7c7e06dee2bf53f9d47a0531dc644c4c40b4f605b4fdde692b8c180fd6c060f17499fa34a6ffc9a5de8c5a0a90abaedf9feb13afac23e55b89961fc795048f32ceb6502bb94eead9f361a0880808080808080").to_vec(),
+					hex!("f902ca20b902c602f902c201835d1c83b9010000000008000080000000000000000000000000000000400000000000000000000000000000000000000000000200000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000020000000000000000000000000000000000000000000000000000000020000000000000000000000000000000100000000004000000000000000000000000000000000000000000000800000000000000000000400000000000000000000000000000000000000000000000f901b7f8999400273e201ffb0bccce44560454fb6841429d5071e1a0caae0f5e72020d428da73a237d1f9bf162e158dda6d4908769b8b60c095b01f4b860000000000000000000000000ef42cf85be6adf3081ada73af87e27996046fe63d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d00000000000000000000000000000000000000000000000002c68af0bb140000f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
+				],
+			),
+		}),
+	}
+}
+
+pub fn get_execution_header_for_message() -> ExecutionHeader {
+	ExecutionHeader {
+		receipts_root: hex!("587bceddb4e618b754faf26ab09b1b10fbf957dfc6f0f79207d73e23c4324af9").into(),
+		block_number: 10867486,
+		timestamp: 1629371367,
+		block_hash: hex!("a5b871f284c883a67a525e8001a106463234dd968c49eeb300d9382d64f25619").into(),
+		..Default::default()
+	}
+}
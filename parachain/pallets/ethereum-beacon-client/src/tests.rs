@@ -2,6 +2,7 @@ use crate::{mock::*, SyncCommittees, Error, BeaconHeader, FinalizedBeaconHeaders
 use frame_support::{assert_ok, assert_err};
 use hex_literal::hex;
 use sp_core::H256;
+use snowbridge_core::{Proof, VerificationInfo, Verifier};
 
 #[test]
 fn it_syncs_from_an_initial_checkpoint() {
@@ -907,3 +908,45 @@ pub fn test_sync_committee_participation_is_supermajority_errors_when_not_superm
 		assert_err!(EthereumBeaconClient::sync_committee_participation_is_supermajority(sync_committee_bits), Error::<Test>::SyncCommitteeParticipantsNotSupermajority);
 	});
 }
+
+#[test]
+fn it_verifies_a_message_with_an_execution_proof() {
+	let message = get_message_with_execution_proof();
+	let header = get_execution_header_for_message();
+
+	new_tester().execute_with(|| {
+		ExecutionHeaders::<Test>::insert(header.block_hash, header.clone());
+
+		let (log, info) = EthereumBeaconClient::verify(&message).unwrap();
+
+		assert_eq!(log, rlp::decode(&message.data).unwrap());
+		assert_eq!(info, VerificationInfo {
+			block_number: header.block_number,
+			block_hash: header.block_hash,
+			timestamp: header.timestamp,
+			log_index: 1,
+		});
+	});
+}
+
+#[test]
+fn it_rejects_a_message_for_an_unknown_execution_header() {
+	let message = get_message_with_execution_proof();
+
+	new_tester().execute_with(|| {
+		assert_err!(EthereumBeaconClient::verify(&message), Error::<Test>::MissingHeader);
+	});
+}
+
+#[test]
+fn it_rejects_a_message_with_a_pow_proof() {
+	let mut message = get_message_with_execution_proof();
+	message.proof = Proof::PoW(message.proof.receipt_proof().clone());
+	let header = get_execution_header_for_message();
+
+	new_tester().execute_with(|| {
+		ExecutionHeaders::<Test>::insert(header.block_hash, header);
+
+		assert_err!(EthereumBeaconClient::verify(&message), Error::<Test>::UnsupportedProof);
+	});
+}
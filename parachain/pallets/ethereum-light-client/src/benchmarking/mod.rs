@@ -32,11 +32,34 @@ fn set_blocks_to_prune<T: Config>(oldest_unpruned: u64, oldest_to_keep: u64) {
 
 fn assert_header_pruned<T: Config>(hash: H256, number: u64) {
 	assert!(Headers::<T>::get(hash).is_none());
+	assert!(CompactHeaders::<T>::get(hash).is_none());
 
 	let hashes_at_number = <HeadersByNumber<T>>::get(number);
 	assert!(hashes_at_number.is_none() || !hashes_at_number.unwrap().contains(&hash),);
 }
 
+// Queue a stale header for pruning at each of `numbers`. The number after each holds
+// the max number of headers, none of which are stored, so that looking for children
+// of the stale header is as costly as possible.
+fn set_stale_headers<T: Config>(numbers: &[u64]) {
+	let max_headers = T::MaxHeadersForNumber::get();
+	let mut queue = <StaleHeaders<T>>::get();
+	for (i, number) in numbers.iter().enumerate() {
+		let hash = H256::repeat_byte(i as u8);
+		let hashes: Vec<H256> = vec![hash];
+		<HeadersByNumber<T>>::insert(number, hashes.try_into().expect("one header fits; qed"));
+		let next_hashes: Vec<H256> = (0..max_headers)
+			.map(|j| H256::from_low_u64_be((i as u64) << 32 | j as u64))
+			.collect();
+		<HeadersByNumber<T>>::insert(
+			number + 1,
+			next_hashes.try_into().expect("max number of headers fits; qed"),
+		);
+		queue.try_push((*number, hash)).expect("queue holds every pruning step; qed");
+	}
+	<StaleHeaders<T>>::put(queue);
+}
+
 // NOTE: These benchmarks only run successully using the `snowbridge' runtime, which is configured
 // for Ethereum mainnet.
 
@@ -51,6 +74,9 @@ benchmarks! {
 	//   number of HeaderByNumber::take calls.
 	// * The last pruned header will have siblings that we don't prune and have to
 	//   re-insert using <HeadersByNumber<T>>::insert.
+	// * Import will take STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT stale fork pruning
+	//   steps, each of which looks up MaxHeadersForNumber possible children that are
+	//   not stored.
 	import_header {
 		let caller: T::AccountId = whitelisted_caller();
 		let descendants_until_final = T::DescendantsUntilFinalized::get();
@@ -72,6 +98,11 @@ benchmarks! {
 			headers[next_finalized_idx].number,
 		);
 
+		let stale_numbers: Vec<u64> = (0..STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT)
+			.map(|i| header.number + 1 + 2 * i)
+			.collect();
+		set_stale_headers::<T>(&stale_numbers);
+
 	}: _(RawOrigin::Signed(caller.clone()), header, header_proof)
 	verify {
 		// Check that the best header has been updated
@@ -94,6 +125,12 @@ benchmarks! {
 			get_blocks_to_prune::<T>().oldest_unpruned_block,
 			last_pruned_sibling.number,
 		);
+
+		// Check that every queued stale header has been pruned
+		assert!(<StaleHeaders<T>>::get().is_empty());
+		stale_numbers
+			.iter()
+			.for_each(|number| assert!(<HeadersByNumber<T>>::get(number).is_none()));
 	}
 
 	// Benchmark `import_header` extrinsic under worst case conditions:
@@ -7,8 +7,12 @@
 //!
 //! This module relies on the relayer service which submits `import_header`
 //! extrinsics, in order, as new blocks in the Ethereum network are authored.
+//! Headers extending the best known chain can also be submitted without fees
+//! using the unsigned `import_header_unsigned` extrinsic.
 //! It stores the most recent `FINALIZED_HEADERS_TO_KEEP` + `DescendantsUntilFinalized`
-//! headers and prunes older headers. This means verification will only succeed
+//! headers and prunes older headers. Full headers are only kept for unfinalized blocks
+//! and the latest finalized block. Older finalized headers are compacted to the fields
+//! needed for message verification. This means verification will only succeed
 //! for messages from *finalized* blocks no older than `FINALIZED_HEADERS_TO_KEEP`.
 //!
 //! ## Usage
@@ -36,15 +40,24 @@ use frame_support::{
 	traits::Get,
 	transactional,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_none, ensure_signed};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::{convert::TryInto, prelude::*};
+use sp_runtime::{
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
 
-use snowbridge_core::{Message, Proof, Verifier};
+use snowbridge_core::{
+	EthereumTime, InitializeVerifier, Message, Proof, ReceiptProof, VerificationInfo, Verifier,
+};
 use snowbridge_ethereum::{
 	difficulty::calc_difficulty,
 	ethashproof::{DoubleNodeWithMerkleProof as EthashProofData, EthashProver},
+	header::check_receipt_proof,
 	HeaderId as EthereumHeaderId, Log, Receipt, H256, U256,
 };
 pub use snowbridge_ethereum::{
@@ -57,6 +70,29 @@ pub use weights::WeightInfo;
 const FINALIZED_HEADERS_TO_KEEP: u64 = 50_000;
 /// Max number of headers we're pruning in single import call.
 const HEADERS_TO_PRUNE_IN_SINGLE_IMPORT: u64 = 8;
+/// Max number of steps spent pruning stale forks in single import call. A step either
+/// scans a finalized block number for stale siblings or prunes a single stale header.
+const STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT: u64 = 8;
+/// Max number of steps spent pruning stale forks in a single block, over all imports.
+const STALE_FORK_PRUNING_STEPS_IN_SINGLE_BLOCK: u64 = 32;
+/// Number of blocks an unsigned header import remains valid in the transaction pool.
+const UNSIGNED_HEADER_LONGEVITY: u64 = 64;
+
+/// Custom `InvalidTransaction` codes for unsigned header imports.
+const UNSIGNED_MISSING_PARENT: u8 = 1;
+const UNSIGNED_NOT_EXTENDING_BEST: u8 = 2;
+const UNSIGNED_INVALID_NUMBER: u8 = 3;
+
+/// Max number of stale headers queued for pruning. This is enough to queue the children of
+/// every header pruned in a single import, when each number has `MaxHeadersForNumber` headers.
+pub struct MaxStaleHeaders<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxStaleHeaders<T> {
+	fn get() -> u32 {
+		T::MaxHeadersForNumber::get()
+			.saturating_mul(STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT as u32)
+	}
+}
 
 /// Ethereum block header as it is stored in the runtime storage.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -73,6 +109,50 @@ pub struct StoredHeader<Submitter> {
 	pub finalized: bool,
 }
 
+/// Compact record of a finalized header as it is stored in the runtime storage.
+///
+/// Once a header is finalized and a newer header has been finalized on top of it, the
+/// full header is replaced by this record. It retains only the fields needed for
+/// ancestry checks, receipt verification and the verification of child headers, which
+/// are imported on top of it after a reset to a fork.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CompactStoredHeader {
+	/// Parent block hash.
+	pub parent_hash: H256,
+	/// Block number.
+	pub number: u64,
+	/// Block receipts root.
+	pub receipts_root: H256,
+	/// Block timestamp.
+	pub timestamp: u64,
+	/// Block difficulty.
+	pub difficulty: U256,
+	/// Gas limit of the block.
+	pub gas_limit: U256,
+	/// Whether the block has ommers.
+	pub has_ommers: bool,
+	/// Total difficulty of the chain.
+	pub total_difficulty: U256,
+	/// Indicates if the header is part of the canonical chain.
+	pub finalized: bool,
+}
+
+impl<Submitter> From<&StoredHeader<Submitter>> for CompactStoredHeader {
+	fn from(stored: &StoredHeader<Submitter>) -> Self {
+		Self {
+			parent_hash: stored.header.parent_hash,
+			number: stored.header.number,
+			receipts_root: stored.header.receipts_root,
+			timestamp: stored.header.timestamp,
+			difficulty: stored.header.difficulty,
+			gas_limit: stored.header.gas_limit,
+			has_ommers: stored.header.has_ommers(),
+			total_difficulty: stored.total_difficulty,
+			finalized: stored.finalized,
+		}
+	}
+}
+
 /// Blocks range that we want to prune.
 #[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
 struct PruningRange {
@@ -86,6 +166,8 @@ struct PruningRange {
 
 pub use pallet::*;
 
+pub mod migration;
+
 #[frame_support::pallet]
 pub mod pallet {
 
@@ -94,8 +176,12 @@ pub mod pallet {
 	use frame_support::{pallet_prelude::*, BoundedVec};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -118,10 +204,31 @@ pub mod pallet {
 		/// The maximum numbers of headers to store in storage per block number.
 		#[pallet::constant]
 		type MaxHeadersForNumber: Get<u32>;
+		/// Base priority of unsigned header imports. The header number is added
+		/// to this so that newer headers are preferred.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
 	}
 
 	#[pallet::event]
-	pub enum Event<T> {}
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A header was validated and imported. The submitter is `None` for headers
+		/// imported through unsigned transactions.
+		HeaderImported { number: u64, hash: H256, submitter: Option<T::AccountId> },
+		/// A header became the latest finalized header.
+		Finalized { number: u64, hash: H256 },
+		/// The light client was reset to a fork originating at `forked_at`.
+		ForkReset {
+			forked_at: H256,
+			best_block_number: u64,
+			finalized_number: u64,
+			finalized_hash: H256,
+		},
+		/// Headers older than `oldest_unpruned_block` have been pruned. Headers up to
+		/// `oldest_block_to_keep` are still waiting to be pruned.
+		HeadersPruned { oldest_unpruned_block: u64, oldest_block_to_keep: u64 },
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -142,6 +249,8 @@ pub mod pallet {
 		InvalidHeader,
 		/// Proof could not be applied / verified.
 		InvalidProof,
+		/// Proof is for a verification scheme other than PoW.
+		UnsupportedProof,
 		/// Log could not be decoded
 		DecodeFailed,
 		// Maximum quantity of headers for number reached
@@ -151,7 +260,21 @@ pub mod pallet {
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				let weight = migration::v1::start::<T>();
+				STORAGE_VERSION.put::<Pallet<T>>();
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			migration::v1::migrate::<T>(migration::v1::MAX_HEADERS_TO_READ)
+		}
+	}
 
 	/// Best known block.
 	#[pallet::storage]
@@ -165,16 +288,41 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type FinalizedBlock<T: Config> = StorageValue<_, EthereumHeaderId, ValueQuery>;
 
-	/// Map of imported headers by hash.
+	/// Map of imported headers by hash. Only unfinalized headers and the latest
+	/// finalized header are kept here in full.
 	#[pallet::storage]
 	pub(super) type Headers<T: Config> =
 		StorageMap<_, Identity, H256, StoredHeader<T::AccountId>, OptionQuery>;
 
+	/// Map of compacted finalized headers by hash.
+	#[pallet::storage]
+	pub(super) type CompactHeaders<T: Config> =
+		StorageMap<_, Identity, H256, CompactStoredHeader, OptionQuery>;
+
 	/// Map of imported header hashes by number.
 	#[pallet::storage]
 	pub(super) type HeadersByNumber<T: Config> =
 		StorageMap<_, Twox64Concat, u64, BoundedVec<H256, T::MaxHeadersForNumber>, OptionQuery>;
 
+	/// Raw storage key of the last header read by the migration to compact headers, or
+	/// empty if no headers have been read yet. Only present while the migration runs.
+	#[pallet::storage]
+	pub(super) type HeadersMigrationCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Next finalized block number to scan for headers on stale forks.
+	#[pallet::storage]
+	pub(super) type StaleForkCursor<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Headers on stale forks, with their numbers, that are waiting to be pruned.
+	#[pallet::storage]
+	pub(super) type StaleHeaders<T: Config> =
+		StorageValue<_, BoundedVec<(u64, H256), MaxStaleHeaders<T>>, ValueQuery>;
+
+	/// Block number, and the number of stale fork pruning steps taken in that block.
+	#[pallet::storage]
+	pub(super) type StaleForkPruningSteps<T: Config> =
+		StorageValue<_, (T::BlockNumber, u64), ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub initial_header: EthereumHeader,
@@ -202,6 +350,7 @@ pub mod pallet {
 				oldest_unpruned_block: self.initial_header.number,
 				oldest_block_to_keep: self.initial_header.number,
 			});
+			<StaleForkCursor<T>>::put(self.initial_header.number);
 		}
 	}
 
@@ -219,6 +368,9 @@ pub mod pallet {
 		///   DAG node selected in the "hashimoto"-loop.
 		/// - Iterating over ancestors: min `DescendantsUntilFinalized` reads to find the newly
 		///   finalized ancestor of a header.
+		/// - Stale fork pruning: up to `STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT` steps, each
+		///   of which reads at most `MaxHeadersForNumber` headers. No more than
+		///   `STALE_FORK_PRUNING_STEPS_IN_SINGLE_BLOCK` steps are taken over all imports in a block.
 		#[pallet::weight(T::WeightInfo::import_header())]
 		#[transactional]
 		pub fn import_header(
@@ -227,44 +379,28 @@ pub mod pallet {
 			proof: Vec<EthashProofData>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
+			Self::do_import_header(Some(sender), &header, &proof)
+		}
 
-			log::trace!(
-				target: "ethereum-light-client",
-				"Received header {}. Starting validation",
-				header.number,
-			);
-
-			if let Err(err) = Self::validate_header_to_import(&header, &proof) {
-				log::trace!(
-					target: "ethereum-light-client",
-					"Validation for header {} returned error. Skipping import",
-					header.number,
-				);
-				return Err(err)
-			}
-
-			log::trace!(
-				target: "ethereum-light-client",
-				"Validation succeeded. Starting import of header {}",
-				header.number,
-			);
-
-			if let Err(err) = Self::import_validated_header(&sender, &header) {
-				log::trace!(
-					target: "ethereum-light-client",
-					"Import of header {} failed",
-					header.number,
-				);
-				return Err(err)
-			}
-
-			log::trace!(
-				target: "ethereum-light-client",
-				"Import of header {} succeeded!",
-				header.number,
-			);
-
-			Ok(())
+		/// Import a single Ethereum PoW header without paying transaction fees.
+		///
+		/// Only headers which extend the best known chain are accepted. The header is fully
+		/// validated, including its PoW, both in the transaction pool through
+		/// `ValidateUnsigned` and again in `pre_dispatch`, so that invalid headers neither
+		/// enter the pool nor make it into a block. Unsigned calls are only dispatched once
+		/// `pre_dispatch` has succeeded, so the header is not validated again here.
+		///
+		/// The weight is identical to `import_header`, as it covers the validation in
+		/// `pre_dispatch`.
+		#[pallet::weight(T::WeightInfo::import_header())]
+		#[transactional]
+		pub fn import_header_unsigned(
+			origin: OriginFor<T>,
+			header: EthereumHeader,
+			_proof: Vec<EthashProofData>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::import_validated_header(None, &header)
 		}
 
 		/// Reset's the internal state of the light client to a previously imported header.
@@ -280,14 +416,13 @@ pub mod pallet {
 		pub fn force_reset_to_fork(origin: OriginFor<T>, forked_at: H256) -> DispatchResult {
 			ensure_root(origin)?;
 
-			let stored_header = <Headers<T>>::get(forked_at).ok_or(Error::<T>::MissingHeader)?;
+			let stored_header = Self::compact_header(forked_at).ok_or(Error::<T>::MissingHeader)?;
 			if !stored_header.finalized {
 				return Err("Cannot reset to a header that is not finalized.".into())
 			}
 
 			let required_descendants = T::DescendantsUntilFinalized::get() as usize;
-			let best_block_id =
-				EthereumHeaderId { number: stored_header.header.number, hash: forked_at };
+			let best_block_id = EthereumHeaderId { number: stored_header.number, hash: forked_at };
 
 			let current_finalized = <FinalizedBlock<T>>::get();
 			if best_block_id.number >= current_finalized.number {
@@ -304,23 +439,137 @@ pub mod pallet {
 
 			<FinalizedBlock<T>>::put(finalized_block_id);
 			<BestBlock<T>>::put((best_block_id, stored_header.total_difficulty));
+			// Headers queued for pruning may belong to the fork being reset to, and headers
+			// above the new finalized block must be scanned again once they are finalized
+			<StaleHeaders<T>>::kill();
+			<StaleForkCursor<T>>::mutate(|cursor| {
+				*cursor = (*cursor).min(finalized_block_id.number + 1)
+			});
+
+			Self::deposit_event(Event::ForkReset {
+				forked_at,
+				best_block_number: best_block_id.number,
+				finalized_number: finalized_block_id.number,
+				finalized_hash: finalized_block_id.hash,
+			});
 
 			Ok(())
 		}
 	}
 
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (header, proof) = match call {
+				Call::import_header_unsigned { header, _proof: proof } => (header, proof),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let number = Self::validate_unsigned_header(header, proof)?;
+
+			ValidTransaction::with_tag_prefix("EthereumLightClient")
+				.priority(T::UnsignedPriority::get().saturating_add(number))
+				.and_provides(header.compute_hash())
+				.longevity(UNSIGNED_HEADER_LONGEVITY)
+				.propagate(true)
+				.build()
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let (header, proof) = match call {
+				Call::import_header_unsigned { header, _proof: proof } => (header, proof),
+				_ => return Err(InvalidTransaction::Call.into()),
+			};
+
+			Self::validate_unsigned_header(header, proof).map(|_| ())
+		}
+	}
+
 	impl<T: Config> Pallet<T> {
+		fn do_import_header(
+			submitter: Option<T::AccountId>,
+			header: &EthereumHeader,
+			proof: &[EthashProofData],
+		) -> DispatchResult {
+			log::trace!(
+				target: "ethereum-light-client",
+				"Received header {}. Starting validation",
+				header.number,
+			);
+
+			if let Err(err) = Self::validate_header_to_import(header, proof) {
+				log::trace!(
+					target: "ethereum-light-client",
+					"Validation for header {} returned error. Skipping import",
+					header.number,
+				);
+				return Err(err)
+			}
+
+			log::trace!(
+				target: "ethereum-light-client",
+				"Validation succeeded. Starting import of header {}",
+				header.number,
+			);
+
+			if let Err(err) = Self::import_validated_header(submitter, header) {
+				log::trace!(
+					target: "ethereum-light-client",
+					"Import of header {} failed",
+					header.number,
+				);
+				return Err(err)
+			}
+
+			log::trace!(
+				target: "ethereum-light-client",
+				"Import of header {} succeeded!",
+				header.number,
+			);
+
+			Ok(())
+		}
+
+		// Validate an unsigned header import, in the transaction pool and before dispatch.
+		// Cheap checks against storage and the parent header run first, so that most
+		// invalid headers are rejected before their PoW is verified. Returns the number of
+		// the header, which is derived from its parent rather than taken from the header.
+		fn validate_unsigned_header(
+			header: &EthereumHeader,
+			proof: &[EthashProofData],
+		) -> Result<u64, TransactionValidityError> {
+			if Self::header_exists(header.compute_hash()) {
+				return Err(InvalidTransaction::Stale.into())
+			}
+			if header.number <= <FinalizedBlock<T>>::get().number {
+				return Err(InvalidTransaction::Stale.into())
+			}
+			let parent = Self::compact_header(header.parent_hash)
+				.ok_or(InvalidTransaction::Custom(UNSIGNED_MISSING_PARENT))?;
+			let number = parent.number.saturating_add(1);
+			if header.number != number {
+				return Err(InvalidTransaction::Custom(UNSIGNED_INVALID_NUMBER).into())
+			}
+			let (best_block_id, _) = <BestBlock<T>>::get();
+			if number <= best_block_id.number {
+				return Err(InvalidTransaction::Custom(UNSIGNED_NOT_EXTENDING_BEST).into())
+			}
+
+			Self::validate_header_to_import(header, proof)
+				.map_err(|_| InvalidTransaction::BadProof)?;
+			Ok(number)
+		}
+
 		// Validate an Ethereum header for import
 		fn validate_header_to_import(
 			header: &EthereumHeader,
 			proof: &[EthashProofData],
 		) -> DispatchResult {
 			let hash = header.compute_hash();
-			ensure!(!<Headers<T>>::contains_key(hash), Error::<T>::DuplicateHeader,);
-
-			let parent = <Headers<T>>::get(header.parent_hash)
-				.ok_or(Error::<T>::MissingParentHeader)?
-				.header;
+			ensure!(!Self::header_exists(hash), Error::<T>::DuplicateHeader,);
+			ensure!(Self::header_exists(header.parent_hash), Error::<T>::MissingParentHeader,);
 
 			let finalized_header_id = <FinalizedBlock<T>>::get();
 			ensure!(header.number > finalized_header_id.number, Error::<T>::AncientHeader,);
@@ -343,6 +592,9 @@ pub mod pallet {
 				return Ok(())
 			}
 
+			let parent =
+				Self::compact_header(header.parent_hash).ok_or(Error::<T>::MissingParentHeader)?;
+
 			// See YellowPaper formula (50) in section 4.3.4
 			ensure!(
 				header.gas_used <= header.gas_limit &&
@@ -362,8 +614,15 @@ pub mod pallet {
 			);
 
 			let difficulty_config = T::DifficultyConfig::get();
-			let header_difficulty = calc_difficulty(&difficulty_config, header.timestamp, &parent)
-				.map_err(|_| Error::<T>::InvalidHeader)?;
+			let header_difficulty = calc_difficulty(
+				&difficulty_config,
+				header.timestamp,
+				parent.number,
+				parent.timestamp,
+				parent.difficulty,
+				parent.has_ommers,
+			)
+			.map_err(|_| Error::<T>::InvalidHeader)?;
 			ensure!(header.difficulty == header_difficulty, Error::<T>::InvalidHeader,);
 
 			log::trace!(
@@ -394,18 +653,18 @@ pub mod pallet {
 
 		// Import a new, validated Ethereum header
 		fn import_validated_header(
-			sender: &T::AccountId,
+			submitter: Option<T::AccountId>,
 			header: &EthereumHeader,
 		) -> DispatchResult {
 			let hash = header.compute_hash();
 			let stored_parent_header =
-				<Headers<T>>::get(header.parent_hash).ok_or(Error::<T>::MissingParentHeader)?;
+				Self::compact_header(header.parent_hash).ok_or(Error::<T>::MissingParentHeader)?;
 			let total_difficulty = stored_parent_header
 				.total_difficulty
 				.checked_add(header.difficulty)
 				.ok_or("Total difficulty overflow")?;
 			let header_to_store = StoredHeader {
-				submitter: Some(sender.clone()),
+				submitter: submitter.clone(),
 				header: header.clone(),
 				total_difficulty,
 				finalized: false,
@@ -416,6 +675,8 @@ pub mod pallet {
 
 			<Headers<T>>::insert(hash, header_to_store);
 
+			Self::deposit_event(Event::HeaderImported { number: header.number, hash, submitter });
+
 			// Maybe track new highest difficulty chain
 			let (_, highest_difficulty) = <BestBlock<T>>::get();
 			if total_difficulty > highest_difficulty ||
@@ -430,16 +691,13 @@ pub mod pallet {
 					Self::get_best_finalized_header(&best_block_id, &finalized_block_id)?;
 				if new_finalized_block_id != finalized_block_id {
 					<FinalizedBlock<T>>::put(new_finalized_block_id);
-					<Headers<T>>::mutate(
-						new_finalized_block_id.hash,
-						|option| -> DispatchResult {
-							if let Some(header) = option {
-								header.finalized = true;
-								return Ok(())
-							}
-							Err(Error::<T>::Unknown.into())
-						},
-					)?;
+					let parent_hash = Self::finalize_header(new_finalized_block_id.hash)
+						.ok_or(Error::<T>::Unknown)?;
+					Self::compact_finalized_ancestors(parent_hash, finalized_block_id.number);
+					Self::deposit_event(Event::Finalized {
+						number: new_finalized_block_id.number,
+						hash: new_finalized_block_id.hash,
+					});
 				}
 
 				// Clean up old headers
@@ -450,17 +708,75 @@ pub mod pallet {
 					new_finalized_block_id.number.saturating_sub(FINALIZED_HEADERS_TO_KEEP),
 				)?;
 				if new_pruning_range != pruning_range {
+					Self::deposit_event(Event::HeadersPruned {
+						oldest_unpruned_block: new_pruning_range.oldest_unpruned_block,
+						oldest_block_to_keep: new_pruning_range.oldest_block_to_keep,
+					});
 					<BlocksToPrune<T>>::put(new_pruning_range);
 				}
+
+				// Drop forks which can no longer become canonical, within the steps left
+				// for this block
+				let now = <frame_system::Pallet<T>>::block_number();
+				let steps_taken = match <StaleForkPruningSteps<T>>::get() {
+					(block, steps) if block == now => steps,
+					_ => 0,
+				};
+				let max_steps = STALE_FORK_PRUNING_STEPS_IN_SINGLE_IMPORT
+					.min(STALE_FORK_PRUNING_STEPS_IN_SINGLE_BLOCK.saturating_sub(steps_taken));
+				if max_steps > 0 {
+					let steps = Self::prune_stale_forks(new_finalized_block_id.number, max_steps);
+					<StaleForkPruningSteps<T>>::put((now, steps_taken + steps));
+				}
 			}
 
 			Ok(())
 		}
 
+		// Look up a header by hash, regardless of whether it has been compacted.
+		fn compact_header(hash: H256) -> Option<CompactStoredHeader> {
+			<Headers<T>>::get(hash)
+				.map(|stored| CompactStoredHeader::from(&stored))
+				.or_else(|| <CompactHeaders<T>>::get(hash))
+		}
+
+		fn header_exists(hash: H256) -> bool {
+			<Headers<T>>::contains_key(hash) || <CompactHeaders<T>>::contains_key(hash)
+		}
+
+		// Mark the header with the given hash as finalized. Returns its parent hash.
+		//
+		// Compacted headers are already finalized. They are finalized again when headers are
+		// imported on top of them after a reset to a fork.
+		fn finalize_header(hash: H256) -> Option<H256> {
+			<Headers<T>>::mutate(hash, |option| {
+				option.as_mut().map(|stored| {
+					stored.finalized = true;
+					stored.header.parent_hash
+				})
+			})
+			.or_else(|| <CompactHeaders<T>>::get(hash).map(|compact| compact.parent_hash))
+		}
+
+		// Replace full headers with compact records, starting at `hash` and walking
+		// back until the previously finalized header (inclusive) or the first header
+		// which has already been compacted.
+		fn compact_finalized_ancestors(mut hash: H256, previous_finalized_number: u64) {
+			while let Some(mut stored) = <Headers<T>>::get(hash) {
+				if stored.header.number < previous_finalized_number {
+					break
+				}
+				stored.finalized = true;
+				<Headers<T>>::remove(hash);
+				<CompactHeaders<T>>::insert(hash, CompactStoredHeader::from(&stored));
+				hash = stored.header.parent_hash;
+			}
+		}
+
 		fn find_finalized_ancestor(
 			block_hash: H256,
 			required_descendants: usize,
-		) -> Option<(H256, EthereumHeader)> {
+		) -> Option<(H256, CompactStoredHeader)> {
 			ancestry::<T>(block_hash).enumerate().find_map(|(i, pair)| {
 				if i < required_descendants {
 					None
@@ -520,6 +836,7 @@ pub mod pallet {
 					let mut remaining = hashes_at_number.len();
 					for hash in hashes_at_number.iter() {
 						<Headers<T>>::remove(hash);
+						<CompactHeaders<T>>::remove(hash);
 						blocks_pruned += 1;
 						remaining -= 1;
 						if blocks_pruned == max_headers_to_prune {
@@ -545,22 +862,95 @@ pub mod pallet {
 			Ok(new_pruning_range)
 		}
 
+		// Remove headers which are not part of the canonical chain at finalized block
+		// numbers, along with all of their descendants. Block numbers are scanned in
+		// order, starting at `StaleForkCursor`, and stale headers are queued for removal
+		// in `StaleHeaders`. At most `max_steps` scans and removals are done per call, and
+		// the number done is returned.
+		//
+		// Children which do not fit in the queue are not removed here. They are removed
+		// along with the finalized headers at their number once it is pruned.
+		pub(super) fn prune_stale_forks(finalized_number: u64, max_steps: u64) -> u64 {
+			let mut queue = <StaleHeaders<T>>::get();
+			let cursor = <StaleForkCursor<T>>::get();
+			// Everything below the oldest unpruned block is already gone
+			let mut next_number = cursor.max(<BlocksToPrune<T>>::get().oldest_unpruned_block);
+
+			let mut steps = 0;
+			while steps < max_steps {
+				steps += 1;
+
+				if let Some((number, hash)) = queue.pop() {
+					// Queue the children of the stale header before removing it
+					for child_hash in <HeadersByNumber<T>>::get(number + 1).unwrap_or_default() {
+						let is_child = Self::compact_header(child_hash)
+							.map_or(false, |child| child.parent_hash == hash);
+						if is_child && queue.try_push((number + 1, child_hash)).is_err() {
+							log::warn!(
+								target: "ethereum-light-client",
+								"Stale header queue is full, leaving {:?} to be pruned later",
+								child_hash,
+							);
+						}
+					}
+					Self::remove_header(number, hash);
+					continue
+				}
+
+				if next_number > finalized_number {
+					break
+				}
+
+				// Queue the siblings of the canonical header
+				for hash in <HeadersByNumber<T>>::get(next_number).unwrap_or_default() {
+					let is_stale =
+						Self::compact_header(hash).map_or(false, |header| !header.finalized);
+					// The queue is empty here, so there is room for every header at the number
+					if is_stale {
+						queue
+							.try_push((next_number, hash))
+							.expect("queue is empty before the scan; qed");
+					}
+				}
+				next_number += 1;
+			}
+
+			<StaleHeaders<T>>::put(queue);
+			if next_number != cursor {
+				<StaleForkCursor<T>>::put(next_number);
+			}
+			steps
+		}
+
+		fn remove_header(number: u64, hash: H256) {
+			<Headers<T>>::remove(hash);
+			<CompactHeaders<T>>::remove(hash);
+			<HeadersByNumber<T>>::mutate_exists(number, |option| {
+				if let Some(hashes) = option {
+					hashes.retain(|h| *h != hash);
+					if hashes.is_empty() {
+						*option = None;
+					}
+				}
+			});
+		}
+
 		// Verifies that the receipt encoded in proof.data is included
 		// in the block given by proof.block_hash. Inclusion is only
 		// recognized if the block has been finalized.
-		fn verify_receipt_inclusion(proof: &Proof) -> Result<Receipt, DispatchError> {
+		fn verify_receipt_inclusion(
+			proof: &ReceiptProof,
+		) -> Result<(Receipt, CompactStoredHeader), DispatchError> {
 			let stored_header =
-				<Headers<T>>::get(proof.block_hash).ok_or(Error::<T>::MissingHeader)?;
+				Self::compact_header(proof.block_hash).ok_or(Error::<T>::MissingHeader)?;
 
 			ensure!(stored_header.finalized, Error::<T>::HeaderNotFinalized);
 
-			let result = stored_header
-				.header
-				.check_receipt_proof(&proof.data.1)
+			let result = check_receipt_proof(stored_header.receipts_root, &proof.data.1)
 				.ok_or(Error::<T>::InvalidProof)?;
 
 			match result {
-				Ok(receipt) => Ok(receipt),
+				Ok(receipt) => Ok((receipt, stored_header)),
 				Err(err) => {
 					log::trace!(
 						target: "ethereum-light-client",
@@ -574,9 +964,9 @@ pub mod pallet {
 	}
 
 	/// Return iterator over header ancestors, starting at given hash
-	fn ancestry<T: Config>(mut hash: H256) -> impl Iterator<Item = (H256, EthereumHeader)> {
+	fn ancestry<T: Config>(mut hash: H256) -> impl Iterator<Item = (H256, CompactStoredHeader)> {
 		sp_std::iter::from_fn(move || {
-			let header = <Headers<T>>::get(&hash)?.header;
+			let header = Pallet::<T>::compact_header(hash)?;
 			let current_hash = hash;
 			hash = header.parent_hash;
 			Some((current_hash, header))
@@ -586,29 +976,52 @@ pub mod pallet {
 	impl<T: Config> Verifier for Pallet<T> {
 		/// Verify a message by verifying the existence of the corresponding
 		/// Ethereum log in a block. Returns the log if successful.
-		fn verify(message: &Message) -> Result<Log, DispatchError> {
-			let receipt = Self::verify_receipt_inclusion(&message.proof)?;
+		fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
+			let proof = match &message.proof {
+				Proof::PoW(proof) => proof,
+				_ => return Err(Error::<T>::UnsupportedProof.into()),
+			};
+
+			let (receipt, stored_header) = Self::verify_receipt_inclusion(proof)?;
 
 			log::trace!(
 				target: "ethereum-light-client",
 				"Verified receipt inclusion for transaction at index {} in block {}",
-				message.proof.tx_index, message.proof.block_hash,
+				proof.tx_index, proof.block_hash,
 			);
 
 			let log: Log = rlp::decode(&message.data).map_err(|_| Error::<T>::DecodeFailed)?;
 
-			if !receipt.contains_log(&log) {
-				log::trace!(
-					target: "ethereum-light-client",
-					"Event log not found in receipt for transaction at index {} in block {}",
-					message.proof.tx_index, message.proof.block_hash,
-				);
-				return Err(Error::<T>::InvalidProof.into())
-			}
+			let log_index = match receipt.log_index(&log) {
+				Some(index) => index,
+				None => {
+					log::trace!(
+						target: "ethereum-light-client",
+						"Event log not found in receipt for transaction at index {} in block {}",
+						proof.tx_index, proof.block_hash,
+					);
+					return Err(Error::<T>::InvalidProof.into())
+				},
+			};
+
+			let info = VerificationInfo {
+				block_number: stored_header.number,
+				block_hash: proof.block_hash,
+				timestamp: stored_header.timestamp,
+				log_index: log_index as u32,
+			};
 
-			Ok(log)
+			Ok((log, info))
 		}
+	}
+
+	impl<T: Config> EthereumTime for Pallet<T> {
+		fn finalized_timestamp() -> Option<u64> {
+			Self::compact_header(<FinalizedBlock<T>>::get().hash).map(|header| header.timestamp)
+		}
+	}
 
+	impl<T: Config> InitializeVerifier for Pallet<T> {
 		/// Import an ordered vec of Ethereum headers without performing
 		/// validation.
 		///
@@ -651,7 +1064,7 @@ pub mod pallet {
 
 				let total_difficulty = {
 					let parent =
-						<Headers<T>>::get(header.parent_hash).ok_or("Missing parent header")?;
+						Self::compact_header(header.parent_hash).ok_or("Missing parent header")?;
 					parent.total_difficulty + header.difficulty
 				};
 
@@ -664,25 +1077,14 @@ pub mod pallet {
 			}
 
 			<BestBlock<T>>::put((best_block_id, best_block_difficulty));
+			<StaleForkCursor<T>>::put(oldest_header.number);
 
 			if let Some((hash, header)) =
 				Self::find_finalized_ancestor(best_block_id.hash, descendants_until_final.into())
 			{
 				<FinalizedBlock<T>>::put(EthereumHeaderId { hash, number: header.number });
-				let mut next_hash = Ok(hash);
-				loop {
-					match next_hash {
-						Ok(hash) =>
-							next_hash = <Headers<T>>::mutate(hash, |option| {
-								if let Some(header) = option {
-									header.finalized = true;
-									return Ok(header.header.parent_hash)
-								}
-								Err("No header at hash")
-							}),
-						_ => break,
-					}
-				}
+				let parent_hash = Self::finalize_header(hash).ok_or("No header at hash")?;
+				Self::compact_finalized_ancestors(parent_hash, 0);
 			}
 
 			Ok(())
@@ -0,0 +1,74 @@
+//! Storage migrations for the Ethereum light client.
+
+pub mod v1 {
+	use crate::{
+		CompactHeaders, CompactStoredHeader, Config, FinalizedBlock, Headers,
+		HeadersMigrationCursor,
+	};
+	use frame_support::{log, traits::Get, weights::Weight};
+	use sp_std::prelude::*;
+
+	/// Max number of headers read by [`migrate`] in a single block, which keeps each step
+	/// of the migration within the weight of a block.
+	pub const MAX_HEADERS_TO_READ: usize = 500;
+
+	/// Begin compacting finalized headers. The headers are compacted by [`migrate`] over
+	/// as many blocks as needed.
+	pub fn start<T: Config>() -> Weight {
+		<HeadersMigrationCursor<T>>::put(Vec::<u8>::new());
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Replace finalized headers, except the latest finalized header, with compact records,
+	/// reading at most `max_headers` headers after the ones read by earlier calls. The
+	/// migration is finished once every header has been read, after which this only reads
+	/// the cursor.
+	pub fn migrate<T: Config>(max_headers: usize) -> Weight {
+		let cursor = match <HeadersMigrationCursor<T>>::get() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let finalized_hash = <FinalizedBlock<T>>::get().hash;
+		let mut reads: Weight = 2;
+
+		let mut headers =
+			if cursor.is_empty() { <Headers<T>>::iter() } else { <Headers<T>>::iter_from(cursor) };
+		let mut headers_read = 0;
+		let to_compact: Vec<_> = headers
+			.by_ref()
+			.take(max_headers)
+			.filter_map(|(hash, stored)| {
+				headers_read += 1;
+				if stored.finalized && hash != finalized_hash {
+					Some((hash, CompactStoredHeader::from(&stored)))
+				} else {
+					None
+				}
+			})
+			.collect();
+		reads += headers_read as Weight;
+
+		// Headers are removed from the map behind the iterator, so the next call continues
+		// after the last key read even if that header is gone
+		if headers_read < max_headers {
+			<HeadersMigrationCursor<T>>::kill();
+			log::info!(target: "ethereum-light-client", "Finished compacting finalized headers");
+		} else {
+			<HeadersMigrationCursor<T>>::put(headers.last_raw_key().to_vec());
+		}
+
+		let compacted = to_compact.len() as Weight;
+		for (hash, compact_header) in to_compact {
+			<Headers<T>>::remove(hash);
+			<CompactHeaders<T>>::insert(hash, compact_header);
+		}
+
+		log::info!(
+			target: "ethereum-light-client",
+			"Compacted {} finalized headers",
+			compacted,
+		);
+
+		T::DbWeight::get().reads_writes(reads, compacted.saturating_mul(2).saturating_add(1))
+	}
+}
@@ -5,12 +5,13 @@ use frame_support::{
 	traits::{Everything, GenesisBuild},
 };
 use frame_system as system;
-use snowbridge_core::{Message, Proof};
+use snowbridge_core::{Message, Proof, ReceiptProof};
 use snowbridge_testutils::BlockWithProofs;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
+	transaction_validity::TransactionPriority,
 	MultiSignature,
 };
 use std::{fs::File, path::PathBuf};
@@ -40,7 +41,7 @@ pub mod mock_verifier {
 			UncheckedExtrinsic = UncheckedExtrinsic,
 		{
 			System: frame_system::{Pallet, Call, Storage, Event<T>},
-			Verifier: verifier::{Pallet, Call, Config, Storage, Event<T>},
+			Verifier: verifier::{Pallet, Call, Config, Storage, Event<T>, ValidateUnsigned},
 		}
 	);
 
@@ -76,6 +77,7 @@ pub mod mock_verifier {
 		pub const DifficultyConfig: EthereumDifficultyConfig = EthereumDifficultyConfig::mainnet();
 		pub const VerifyPoW: bool = false;
 		pub const MaxHeadersForNumber: u32 = 10;
+		pub const UnsignedPriority: TransactionPriority = 100;
 	}
 
 	impl verifier::Config for Test {
@@ -85,6 +87,7 @@ pub mod mock_verifier {
 		type VerifyPoW = VerifyPoW;
 		type WeightInfo = ();
 		type MaxHeadersForNumber = MaxHeadersForNumber;
+		type UnsignedPriority = UnsignedPriority;
 	}
 }
 
@@ -102,7 +105,7 @@ pub mod mock_verifier_with_pow {
 			UncheckedExtrinsic = UncheckedExtrinsic,
 		{
 			System: frame_system::{Pallet, Call, Storage, Event<T>},
-			Verifier: verifier::{Pallet, Call, Config, Storage, Event<T>},
+			Verifier: verifier::{Pallet, Call, Config, Storage, Event<T>, ValidateUnsigned},
 		}
 	);
 
@@ -138,6 +141,7 @@ pub mod mock_verifier_with_pow {
 		pub const DifficultyConfig: EthereumDifficultyConfig = EthereumDifficultyConfig::mainnet();
 		pub const VerifyPoW: bool = true;
 		pub const MaxHeadersForNumber: u32 = 10;
+		pub const UnsignedPriority: TransactionPriority = 100;
 	}
 
 	impl verifier::Config for Test {
@@ -147,6 +151,7 @@ pub mod mock_verifier_with_pow {
 		type VerifyPoW = VerifyPoW;
 		type WeightInfo = ();
 		type MaxHeadersForNumber = MaxHeadersForNumber;
+		type UnsignedPriority = UnsignedPriority;
 	}
 }
 
@@ -216,14 +221,17 @@ pub fn message_with_receipt_proof(
 	block_hash: H256,
 	proof_data: (Vec<Vec<u8>>, Vec<Vec<u8>>),
 ) -> Message {
-	Message { data: payload, proof: Proof { block_hash, tx_index: 0, data: proof_data } }
+	Message {
+		data: payload,
+		proof: Proof::PoW(ReceiptProof { block_hash, tx_index: 0, data: proof_data }),
+	}
 }
 
 // from https://ropsten.etherscan.io/tx/0x3541903322b74942aa9dd436ac6277d36d874865c35032fe915518d2659fc64c
 pub fn ropsten_london_message() -> Message {
 	Message {
 		data: hex!("f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
-		proof: Proof {
+		proof: Proof::PoW(ReceiptProof {
 			block_hash: hex!("a5b871f284c883a67a525e8001a106463234dd968c49eeb300d9382d64f25619").into(),
 			tx_index: 25,
 			data: (
@@ -238,7 +246,7 @@ pub fn ropsten_london_message() -> Message {
 					hex!("f902ca20b902c602f902c201835d1c83b9010000000008000080000000000000000000000000000000400000000000000000000000000000000000000000000200000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000020000000000000000000000000000000000000000000000000000000020000000000000000000000000000000100000000004000000000000000000000000000000000000000000000800000000000000000000400000000000000000000000000000000000000000000000f901b7f8999400273e201ffb0bccce44560454fb6841429d5071e1a0caae0f5e72020d428da73a237d1f9bf162e158dda6d4908769b8b60c095b01f4b860000000000000000000000000ef42cf85be6adf3081ada73af87e27996046fe63d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d00000000000000000000000000000000000000000000000002c68af0bb140000f90119945dd2b8d6f10623426b74d7a92d322f75b74571a3e1a0779b38144a38cfc4351816442048b17fe24ba2b0e0c63446b576e8281160b15bb8e000000000000000000000000000273e201ffb0bccce44560454fb6841429d50710000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000574101ef42cf85be6adf3081ada73af87e27996046fe6300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d000014bbf08ac602000000000000000000000000000000000000000000000000000000000000000000").to_vec(),
 				],
 			),
-		},
+		}),
 	}
 }
 
@@ -1,22 +1,34 @@
 use crate::mock::{
 	child_of_genesis_ethereum_header, child_of_header, ethereum_header_from_file,
-	ethereum_header_proof_from_file, genesis_ethereum_block_hash, log_payload,
-	message_with_receipt_proof, new_tester, new_tester_with_config, receipt_root_and_proof,
-	ropsten_london_header, ropsten_london_message, AccountId,
+	ethereum_header_proof_from_file, genesis_ethereum_block_hash, genesis_ethereum_header,
+	log_payload, message_with_receipt_proof, new_tester, new_tester_with_config,
+	receipt_root_and_proof, ropsten_london_header, ropsten_london_message, AccountId,
 };
-use snowbridge_core::Verifier as VerifierConfig;
+use snowbridge_core::{EthereumTime, Proof, VerificationInfo, Verifier as VerifierConfig};
 
 use crate::mock::mock_verifier_with_pow;
 
-use crate::mock::mock_verifier::{MaxHeadersForNumber, Origin, Test, Verifier};
+use crate::mock::mock_verifier::{
+	Event as TestEvent, MaxHeadersForNumber, Origin, System, Test, UnsignedPriority, Verifier,
+};
 
 use crate::{
-	BestBlock, Error, EthereumHeader, FinalizedBlock, GenesisConfig, Headers, HeadersByNumber,
-	PruningRange,
+	migration, BestBlock, Call, CompactHeaders, CompactStoredHeader, Error, EthereumHeader,
+	EthereumHeaderId, Event, FinalizedBlock, GenesisConfig, Headers, HeadersByNumber,
+	HeadersMigrationCursor, PruningRange, StaleForkCursor, StaleForkPruningSteps, StaleHeaders,
+	StoredHeader, STALE_FORK_PRUNING_STEPS_IN_SINGLE_BLOCK, UNSIGNED_INVALID_NUMBER,
+	UNSIGNED_MISSING_PARENT, UNSIGNED_NOT_EXTENDING_BEST,
+};
+use frame_support::{
+	assert_err, assert_ok,
+	traits::{OnInitialize, OnRuntimeUpgrade, StorageVersion},
+	unsigned::ValidateUnsigned,
 };
-use frame_support::{assert_err, assert_ok};
 use sp_keyring::AccountKeyring as Keyring;
-use sp_runtime::DispatchError;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError,
+};
 
 #[test]
 fn it_tracks_highest_difficulty_ethereum_chain() {
@@ -45,6 +57,74 @@ fn it_tracks_highest_difficulty_ethereum_chain() {
 	});
 }
 
+#[test]
+fn it_emits_events_on_import_and_finalization() {
+	new_tester::<Test>().execute_with(|| {
+		System::set_block_number(1);
+
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, None);
+		let block3 = child_of_header(&block2, None);
+		let block3_hash = block3.compute_hash();
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, block2, block3].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+
+		let events: Vec<TestEvent> = System::events().into_iter().map(|r| r.event).collect();
+		assert!(events.contains(&TestEvent::Verifier(Event::HeaderImported {
+			number: 3,
+			hash: block3_hash,
+			submitter: Some(ferdie.clone()),
+		})));
+		// Relies on DescendantsUntilFinalized = 2
+		assert_eq!(
+			events.last(),
+			Some(&TestEvent::Verifier(Event::Finalized { number: 1, hash: block1_hash })),
+		);
+	});
+}
+
+#[test]
+fn it_emits_event_on_force_reset_to_fork() {
+	new_tester::<Test>().execute_with(|| {
+		System::set_block_number(1);
+
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, Some(2));
+		let block3 = child_of_header(&block2, Some(3));
+		let block3_hash = block3.compute_hash();
+		let block4 = child_of_header(&block3, Some(4));
+		let block5 = child_of_header(&block4, Some(5));
+		let block6 = child_of_header(&block5, Some(6));
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, block2, block3, block4, block5, block6].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+
+		assert_ok!(Verifier::force_reset_to_fork(Origin::root(), block3_hash));
+
+		System::assert_last_event(TestEvent::Verifier(Event::ForkReset {
+			forked_at: block3_hash,
+			best_block_number: 3,
+			finalized_number: 1,
+			finalized_hash: block1_hash,
+		}));
+	});
+}
+
 #[test]
 fn it_tracks_multiple_unfinalized_ethereum_forks() {
 	new_tester::<Test>().execute_with(|| {
@@ -84,6 +164,7 @@ fn it_tracks_only_one_finalized_ethereum_fork() {
 		let block3_hash = block3.compute_hash();
 		let mut block4 = child_of_genesis_ethereum_header();
 		block4.difficulty = 2.into();
+		let block4_hash = block4.compute_hash();
 		let mut block5 = child_of_header(&block4, None);
 		block5.difficulty = 3.into();
 		let mut block6 = child_of_genesis_ethereum_header();
@@ -111,6 +192,10 @@ fn it_tracks_only_one_finalized_ethereum_fork() {
 		assert!(<Headers<Test>>::get(block2_hash).unwrap().finalized == false);
 		assert_eq!(BestBlock::<Test>::get().0.hash, block3_hash);
 
+		// B4 is pruned once B1 is final
+		assert!(!<Headers<Test>>::contains_key(block4_hash));
+		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap(), vec![block1_hash]);
+
 		// With invalid forks (invalid since B1 is final):
 		//       B0
 		//     / | \
@@ -121,7 +206,7 @@ fn it_tracks_only_one_finalized_ethereum_fork() {
 		//       B3
 		assert_err!(
 			Verifier::import_header(Origin::signed(ferdie.clone()), block5, Default::default(),),
-			Error::<Test>::HeaderOnStaleFork,
+			Error::<Test>::MissingParentHeader,
 		);
 		assert_err!(
 			Verifier::import_header(Origin::signed(ferdie.clone()), block6, Default::default(),),
@@ -130,6 +215,156 @@ fn it_tracks_only_one_finalized_ethereum_fork() {
 	});
 }
 
+#[test]
+fn it_compacts_finalized_ethereum_headers() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, None);
+		let block2_hash = block2.compute_hash();
+		let block3 = child_of_header(&block2, None);
+		let block3_hash = block3.compute_hash();
+		let block4 = child_of_header(&block3, None);
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, block2, block3, block4].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+
+		// Relies on DescendantsUntilFinalized = 2
+		assert_eq!(<FinalizedBlock<Test>>::get().hash, block2_hash);
+
+		// Older finalized headers are compacted
+		for hash in vec![genesis_ethereum_block_hash(), block1_hash].into_iter() {
+			assert!(!<Headers<Test>>::contains_key(hash));
+			assert!(<CompactHeaders<Test>>::get(hash).unwrap().finalized);
+		}
+		assert_eq!(<CompactHeaders<Test>>::get(block1_hash).unwrap().number, 1);
+
+		// The latest finalized header and unfinalized headers are kept in full
+		assert!(<Headers<Test>>::get(block2_hash).unwrap().finalized);
+		assert!(!<CompactHeaders<Test>>::contains_key(block2_hash));
+		assert!(!<Headers<Test>>::get(block3_hash).unwrap().finalized);
+	});
+}
+
+#[test]
+fn it_reports_timestamp_of_finalized_header() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block2 = child_of_header(&block1, Some(block1.timestamp + 15));
+		let block3 = child_of_header(&block2, Some(block2.timestamp + 15));
+		let block4 = child_of_header(&block3, Some(block3.timestamp + 15));
+		let block2_timestamp = block2.timestamp;
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, block2, block3].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+		// Relies on DescendantsUntilFinalized = 2
+		assert_ne!(Verifier::finalized_timestamp(), Some(block2_timestamp));
+
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block4,
+			Default::default(),
+		));
+		assert_eq!(Verifier::finalized_timestamp(), Some(block2_timestamp));
+	});
+}
+
+#[test]
+fn it_migrates_finalized_headers_to_compact_storage() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, None);
+		let block2_hash = block2.compute_hash();
+		let block3 = child_of_header(&block2, None);
+		let block3_hash = block3.compute_hash();
+
+		// Simulate storage written before headers were compacted
+		for (header, finalized) in vec![(block1, true), (block2, true), (block3, false)] {
+			<Headers<Test>>::insert(
+				header.compute_hash(),
+				StoredHeader {
+					submitter: None,
+					header: header.clone(),
+					total_difficulty: header.number.into(),
+					finalized,
+				},
+			);
+		}
+		<FinalizedBlock<Test>>::put(EthereumHeaderId { number: 2, hash: block2_hash });
+
+		// Reading a single header compacts at most one of the genesis header and block 1
+		migration::v1::start::<Test>();
+		migration::v1::migrate::<Test>(1);
+		assert!(<CompactHeaders<Test>>::iter().count() <= 1);
+		assert!(<HeadersMigrationCursor<Test>>::exists());
+
+		migration::v1::migrate::<Test>(migration::v1::MAX_HEADERS_TO_READ);
+		assert!(!<HeadersMigrationCursor<Test>>::exists());
+
+		assert!(!<Headers<Test>>::contains_key(genesis_ethereum_block_hash()));
+		assert!(<CompactHeaders<Test>>::contains_key(genesis_ethereum_block_hash()));
+		assert!(!<Headers<Test>>::contains_key(block1_hash));
+		assert_eq!(<CompactHeaders<Test>>::get(block1_hash).unwrap().total_difficulty, 1.into());
+		assert!(<Headers<Test>>::contains_key(block2_hash));
+		assert!(<Headers<Test>>::contains_key(block3_hash));
+		assert!(!<CompactHeaders<Test>>::contains_key(block3_hash));
+	});
+}
+
+#[test]
+fn it_migrates_headers_over_multiple_blocks() {
+	new_tester::<Test>().execute_with(|| {
+		// Simulate more finalized headers than can be read in a block
+		let mut header = genesis_ethereum_header();
+		for _ in 0..migration::v1::MAX_HEADERS_TO_READ + 100 {
+			header = child_of_header(&header, None);
+			<Headers<Test>>::insert(
+				header.compute_hash(),
+				StoredHeader {
+					submitter: None,
+					header: header.clone(),
+					total_difficulty: header.number.into(),
+					finalized: true,
+				},
+			);
+		}
+		let finalized_hash = header.compute_hash();
+		<FinalizedBlock<Test>>::put(EthereumHeaderId {
+			number: header.number,
+			hash: finalized_hash,
+		});
+		StorageVersion::new(0).put::<Verifier>();
+
+		Verifier::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Verifier>(), 1);
+
+		Verifier::on_initialize(1);
+		assert!(<HeadersMigrationCursor<Test>>::exists());
+		assert!(<Headers<Test>>::iter().count() > 100);
+
+		Verifier::on_initialize(2);
+		assert!(!<HeadersMigrationCursor<Test>>::exists());
+		assert_eq!(<Headers<Test>>::iter_keys().collect::<Vec<_>>(), vec![finalized_hash]);
+		assert_eq!(
+			<CompactHeaders<Test>>::iter().count(),
+			migration::v1::MAX_HEADERS_TO_READ + 100,
+		);
+	});
+}
+
 #[test]
 fn it_prunes_ethereum_headers_correctly() {
 	new_tester::<Test>().execute_with(|| {
@@ -139,20 +374,19 @@ fn it_prunes_ethereum_headers_correctly() {
 		let block2_hash = block2.compute_hash();
 		let block3 = child_of_header(&block2, None);
 		let block3_hash = block3.compute_hash();
-		let mut block4 = child_of_genesis_ethereum_header();
-		block4.difficulty = 2.into();
+		let block4 = child_of_header(&block1, Some(1));
 		let block4_hash = block4.compute_hash();
 
 		// Initial chain:
 		//   B0
-		//   |  \
-		//   B1  B4
 		//   |
-		//   B2
+		//   B1
+		//   |  \
+		//   B2  B4
 		//   |
 		//   B3
 		let ferdie: AccountId = Keyring::Ferdie.into();
-		for header in vec![block1, block4, block2, block3].into_iter() {
+		for header in vec![block1, block2, block4, block3].into_iter() {
 			assert_ok!(Verifier::import_header(
 				Origin::signed(ferdie.clone()),
 				header,
@@ -172,6 +406,7 @@ fn it_prunes_ethereum_headers_correctly() {
 			PruningRange { oldest_unpruned_block: 1, oldest_block_to_keep: 1 },
 		);
 		assert!(!<Headers<Test>>::contains_key(genesis_ethereum_block_hash()));
+		assert!(!<CompactHeaders<Test>>::contains_key(genesis_ethereum_block_hash()));
 		assert!(!<HeadersByNumber<Test>>::contains_key(0));
 
 		// Prune next block (B1)
@@ -183,16 +418,31 @@ fn it_prunes_ethereum_headers_correctly() {
 		assert_ok!(&new_range);
 		assert_eq!(
 			new_range.unwrap(),
-			PruningRange { oldest_unpruned_block: 1, oldest_block_to_keep: 2 },
+			PruningRange { oldest_unpruned_block: 2, oldest_block_to_keep: 2 },
 		);
 		assert!(!<Headers<Test>>::contains_key(block1_hash));
+		assert!(!<CompactHeaders<Test>>::contains_key(block1_hash));
+		assert!(!<HeadersByNumber<Test>>::contains_key(1));
+
+		// Prune next block (B2)
+		let new_range = Verifier::prune_header_range(
+			&PruningRange { oldest_unpruned_block: 2, oldest_block_to_keep: 2 },
+			1,
+			3,
+		);
+		assert_ok!(&new_range);
+		assert_eq!(
+			new_range.unwrap(),
+			PruningRange { oldest_unpruned_block: 2, oldest_block_to_keep: 3 },
+		);
+		assert!(!<Headers<Test>>::contains_key(block2_hash));
 		assert!(<Headers<Test>>::contains_key(block4_hash));
-		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap(), vec![block4_hash]);
+		assert_eq!(<HeadersByNumber<Test>>::get(2).unwrap(), vec![block4_hash]);
 
-		// Prune next two blocks (B4, B2)
+		// Prune next block (B4)
 		let new_range = Verifier::prune_header_range(
-			&PruningRange { oldest_unpruned_block: 1, oldest_block_to_keep: 2 },
-			2,
+			&PruningRange { oldest_unpruned_block: 2, oldest_block_to_keep: 3 },
+			1,
 			4,
 		);
 		assert_ok!(&new_range);
@@ -201,8 +451,6 @@ fn it_prunes_ethereum_headers_correctly() {
 			PruningRange { oldest_unpruned_block: 3, oldest_block_to_keep: 4 },
 		);
 		assert!(!<Headers<Test>>::contains_key(block4_hash));
-		assert!(!<HeadersByNumber<Test>>::contains_key(1));
-		assert!(!<Headers<Test>>::contains_key(block2_hash));
 		assert!(!<HeadersByNumber<Test>>::contains_key(2));
 
 		// Finally, we're left with B3
@@ -211,6 +459,140 @@ fn it_prunes_ethereum_headers_correctly() {
 	});
 }
 
+#[test]
+fn it_prunes_stale_ethereum_forks() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, None);
+		let block2_hash = block2.compute_hash();
+		let block3 = child_of_header(&block2, None);
+		let fork_block1 = child_of_header(&genesis_ethereum_header(), Some(1));
+		let fork_block1_hash = fork_block1.compute_hash();
+		let fork_block2 = child_of_header(&fork_block1, Some(1));
+		let fork_block2_hash = fork_block2.compute_hash();
+
+		// Initial chain:
+		//   B0
+		//   |  \
+		//   B1  F1
+		//   |    \
+		//   B2    F2
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, fork_block1, block2, fork_block2].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap(), vec![block1_hash, fork_block1_hash]);
+		assert_eq!(<HeadersByNumber<Test>>::get(2).unwrap(), vec![block2_hash, fork_block2_hash]);
+
+		// Importing B3 finalizes B1, which makes F1 and its descendants stale
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block3,
+			Default::default(),
+		));
+		// Relies on DescendantsUntilFinalized = 2
+		assert_eq!(<FinalizedBlock<Test>>::get().hash, block1_hash);
+
+		for hash in vec![fork_block1_hash, fork_block2_hash].into_iter() {
+			assert!(!<Headers<Test>>::contains_key(hash));
+			assert!(!<CompactHeaders<Test>>::contains_key(hash));
+		}
+		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap(), vec![block1_hash]);
+		assert_eq!(<HeadersByNumber<Test>>::get(2).unwrap(), vec![block2_hash]);
+		assert!(<StaleHeaders<Test>>::get().is_empty());
+		assert_eq!(<StaleForkCursor<Test>>::get(), 2);
+	});
+}
+
+#[test]
+fn it_prunes_stale_ethereum_forks_in_bounded_steps() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block1_hash = block1.compute_hash();
+		let block2 = child_of_header(&block1, None);
+		let block3 = child_of_header(&block2, None);
+		let block4 = child_of_header(&block3, None);
+		let forks: Vec<EthereumHeader> = (1..9)
+			.map(|timestamp| child_of_header(&genesis_ethereum_header(), Some(timestamp)))
+			.collect();
+		let fork_hashes: Vec<_> = forks.iter().map(|header| header.compute_hash()).collect();
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		let headers = vec![block1].into_iter().chain(forks).chain(vec![block2, block3]);
+		for header in headers {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+		assert_eq!(<FinalizedBlock<Test>>::get().hash, block1_hash);
+
+		// Scanning block numbers 0 and 1 leaves room to prune 6 out of 8 forks
+		let pruned = fork_hashes.iter().filter(|hash| !<Headers<Test>>::contains_key(hash));
+		assert_eq!(pruned.count(), 6);
+		assert_eq!(<StaleHeaders<Test>>::get().len(), 2);
+		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap().len(), 3);
+
+		// The remaining forks are pruned on the next import
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block4,
+			Default::default(),
+		));
+		assert!(fork_hashes.iter().all(|hash| !<Headers<Test>>::contains_key(hash)));
+		assert!(<StaleHeaders<Test>>::get().is_empty());
+		assert_eq!(<HeadersByNumber<Test>>::get(1).unwrap(), vec![block1_hash]);
+	});
+}
+
+#[test]
+fn it_limits_stale_fork_pruning_steps_per_block() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block2 = child_of_header(&block1, None);
+		let block3 = child_of_header(&block2, None);
+		let block4 = child_of_header(&block3, None);
+		let fork_block1 = child_of_header(&genesis_ethereum_header(), Some(1));
+		let fork_block1_hash = fork_block1.compute_hash();
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		for header in vec![block1, fork_block1, block2].into_iter() {
+			assert_ok!(Verifier::import_header(
+				Origin::signed(ferdie.clone()),
+				header,
+				Default::default(),
+			));
+		}
+
+		// No steps are left in this block, so finalizing B1 leaves F1 in place
+		System::set_block_number(1);
+		<StaleForkPruningSteps<Test>>::put((1, STALE_FORK_PRUNING_STEPS_IN_SINGLE_BLOCK));
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block3,
+			Default::default(),
+		));
+		assert!(<Headers<Test>>::contains_key(fork_block1_hash));
+		assert_eq!(<StaleForkCursor<Test>>::get(), 0);
+
+		// F1 is pruned in the next block
+		System::set_block_number(2);
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block4,
+			Default::default(),
+		));
+		assert!(!<Headers<Test>>::contains_key(fork_block1_hash));
+		assert_eq!(<StaleForkPruningSteps<Test>>::get().0, 2);
+	});
+}
+
 #[test]
 fn it_imports_ethereum_header_only_once() {
 	new_tester::<Test>().execute_with(|| {
@@ -245,6 +627,99 @@ fn it_rejects_unsigned_ethereum_header() {
 	});
 }
 
+#[test]
+fn it_imports_unsigned_ethereum_header() {
+	new_tester::<Test>().execute_with(|| {
+		let child = child_of_genesis_ethereum_header();
+		let child_hash = child.compute_hash();
+
+		let call = Call::<Test>::import_header_unsigned {
+			header: child.clone(),
+			_proof: Default::default(),
+		};
+		let validity = Verifier::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_eq!(validity.priority, UnsignedPriority::get() + child.number);
+		assert_eq!(validity.provides.len(), 1);
+		assert_ok!(Verifier::pre_dispatch(&call));
+
+		assert_ok!(Verifier::import_header_unsigned(Origin::none(), child, Default::default()));
+		assert_eq!(<Headers<Test>>::get(child_hash).unwrap().submitter, None);
+		assert_eq!(BestBlock::<Test>::get().0.hash, child_hash);
+	});
+}
+
+#[test]
+fn it_rejects_invalid_unsigned_ethereum_headers_in_pool() {
+	new_tester::<Test>().execute_with(|| {
+		let block1 = child_of_genesis_ethereum_header();
+		let block2 = child_of_header(&block1, None);
+		let mut block1_alt = child_of_genesis_ethereum_header();
+		block1_alt.difficulty = 2.into();
+		let orphan = child_of_header(&block2, None);
+		let mut block2_far_ahead = block2.clone();
+		block2_far_ahead.number = u64::MAX;
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		assert_ok!(Verifier::import_header(
+			Origin::signed(ferdie.clone()),
+			block1.clone(),
+			Default::default(),
+		));
+
+		let validate = |header: EthereumHeader| {
+			Verifier::validate_unsigned(
+				TransactionSource::External,
+				&Call::<Test>::import_header_unsigned { header, _proof: Default::default() },
+			)
+		};
+
+		// Duplicate header
+		assert_eq!(validate(block1), Err(InvalidTransaction::Stale.into()));
+		// Parent has not been imported
+		assert_eq!(
+			validate(orphan),
+			Err(InvalidTransaction::Custom(UNSIGNED_MISSING_PARENT).into())
+		);
+		// Sibling of the best block does not extend the best chain
+		assert_eq!(
+			validate(block1_alt),
+			Err(InvalidTransaction::Custom(UNSIGNED_NOT_EXTENDING_BEST).into())
+		);
+		// Number does not follow the parent's
+		assert_eq!(
+			validate(block2_far_ahead),
+			Err(InvalidTransaction::Custom(UNSIGNED_INVALID_NUMBER).into())
+		);
+		// Child of the best block is accepted
+		assert_ok!(validate(block2));
+	});
+}
+
+#[test]
+fn it_rejects_unsigned_ethereum_header_with_invalid_pow_in_pool() {
+	new_tester_with_config::<mock_verifier_with_pow::Test>(GenesisConfig {
+		initial_header: ethereum_header_from_file(11090291, ""),
+		initial_difficulty: 0.into(),
+	})
+	.execute_with(|| {
+		let header = ethereum_header_from_file(11090292, "_low_difficulty");
+		let header_proof = ethereum_header_proof_from_file(11090292, "_low_difficulty");
+		let call = Call::<mock_verifier_with_pow::Test>::import_header_unsigned {
+			header,
+			_proof: header_proof,
+		};
+
+		assert_eq!(
+			mock_verifier_with_pow::Verifier::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+		assert_eq!(
+			mock_verifier_with_pow::Verifier::pre_dispatch(&call),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+	});
+}
+
 #[test]
 fn it_rejects_ethereum_header_before_parent() {
 	new_tester::<Test>().execute_with(|| {
@@ -333,12 +808,39 @@ fn it_confirms_receipt_inclusion_in_finalized_header() {
 fn it_confirms_receipt_inclusion_in_ropsten_london_header() {
 	let finalized_header: EthereumHeader = ropsten_london_header();
 
+	new_tester_with_config::<Test>(GenesisConfig {
+		initial_header: finalized_header.clone(),
+		initial_difficulty: 0.into(),
+	})
+	.execute_with(|| {
+		let message = ropsten_london_message();
+		let (log, info) = Verifier::verify(&message).unwrap();
+
+		assert_eq!(log, rlp::decode(&message.data).unwrap());
+		assert_eq!(
+			info,
+			VerificationInfo {
+				block_number: finalized_header.number,
+				block_hash: finalized_header.compute_hash(),
+				timestamp: finalized_header.timestamp,
+				log_index: 1,
+			}
+		);
+	});
+}
+
+#[test]
+fn it_denies_receipt_inclusion_for_execution_proof() {
+	let finalized_header: EthereumHeader = ropsten_london_header();
+
 	new_tester_with_config::<Test>(GenesisConfig {
 		initial_header: finalized_header,
 		initial_difficulty: 0.into(),
 	})
 	.execute_with(|| {
-		assert_ok!(Verifier::verify(&ropsten_london_message()));
+		let mut message = ropsten_london_message();
+		message.proof = Proof::Execution(message.proof.receipt_proof().clone());
+		assert_err!(Verifier::verify(&message), Error::<Test>::UnsupportedProof);
 	});
 }
 
@@ -645,6 +1147,9 @@ fn it_should_be_able_to_force_reset_to_fork() {
 		);
 
 		assert_ok!(Verifier::force_reset_to_fork(Origin::root(), block3_hash));
+		// Blocks after the new finalized block B1 are scanned for stale forks again
+		assert!(<StaleHeaders<Test>>::get().is_empty());
+		assert_eq!(<StaleForkCursor<Test>>::get(), 2);
 
 		// Once best block is set the relayer will import the fork
 		for header in vec![fork_block4, fork_block5, fork_block6, fork_block7].into_iter() {
@@ -825,3 +1330,68 @@ fn it_should_fail_reset_for_blocks_that_are_not_finalized() {
 		assert_eq!(BestBlock::<Test>::get().0.hash, block4_hash);
 	});
 }
+
+#[test]
+fn it_validates_proof_of_work_after_force_reset_to_compacted_header() {
+	new_tester_with_config::<mock_verifier_with_pow::Test>(GenesisConfig {
+		initial_header: ethereum_header_from_file(11090290, ""),
+		initial_difficulty: 0.into(),
+	})
+	.execute_with(|| {
+		let header0 = ethereum_header_from_file(11090290, "");
+		let header1 = ethereum_header_from_file(11090291, "");
+		let header1_hash = header1.compute_hash();
+		let header1_proof = ethereum_header_proof_from_file(11090291, "");
+		let header2 = ethereum_header_from_file(11090292, "");
+		let header2_hash = header2.compute_hash();
+		let header2_proof = ethereum_header_proof_from_file(11090292, "");
+
+		let ferdie: AccountId = Keyring::Ferdie.into();
+		assert_ok!(mock_verifier_with_pow::Verifier::import_header(
+			mock_verifier_with_pow::Origin::signed(ferdie.clone()),
+			header1.clone(),
+			header1_proof,
+		));
+
+		// Simulate headers being finalized beyond header1, which compacts header1 and its
+		// parent. A compacted grandparent is needed to reset with DescendantsUntilFinalized = 2.
+		for hash in vec![header0.compute_hash(), header1_hash] {
+			let stored = <Headers<mock_verifier_with_pow::Test>>::take(hash).unwrap();
+			let compact =
+				CompactStoredHeader { finalized: true, ..CompactStoredHeader::from(&stored) };
+			<CompactHeaders<mock_verifier_with_pow::Test>>::insert(hash, compact);
+		}
+		<CompactHeaders<mock_verifier_with_pow::Test>>::insert(
+			header0.parent_hash,
+			CompactStoredHeader {
+				parent_hash: Default::default(),
+				number: header0.number - 1,
+				receipts_root: Default::default(),
+				timestamp: header0.timestamp - 1,
+				difficulty: header0.difficulty,
+				gas_limit: header0.gas_limit,
+				has_ommers: false,
+				total_difficulty: 0.into(),
+				finalized: true,
+			},
+		);
+		<FinalizedBlock<mock_verifier_with_pow::Test>>::put(EthereumHeaderId {
+			number: header2.number + 10,
+			hash: Default::default(),
+		});
+
+		assert_ok!(mock_verifier_with_pow::Verifier::force_reset_to_fork(
+			mock_verifier_with_pow::Origin::root(),
+			header1_hash,
+		));
+
+		// The parent of header2 is only stored as a compact record
+		assert_ok!(mock_verifier_with_pow::Verifier::import_header(
+			mock_verifier_with_pow::Origin::signed(ferdie),
+			header2,
+			header2_proof,
+		));
+		assert_eq!(<BestBlock<mock_verifier_with_pow::Test>>::get().0.hash, header2_hash);
+		assert_eq!(<FinalizedBlock<mock_verifier_with_pow::Test>>::get().number, header0.number);
+	});
+}
@@ -0,0 +1,48 @@
+//! ExchangeRate pallet benchmarking
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
+use frame_system::RawOrigin;
+
+#[allow(unused_imports)]
+use crate::Pallet as ExchangeRate;
+
+benchmarks! {
+	// Benchmark `set_rate` under worst case conditions:
+	// * The origin is a feeder, which requires a lookup
+	set_rate {
+		let caller: T::AccountId = whitelisted_caller();
+		<Feeders<T>>::insert(&caller, ());
+
+		let rate = FixedU128::saturating_from_rational(3, 2);
+
+	}: _(RawOrigin::Signed(caller), rate)
+	verify {
+		assert_eq!(ExchangeRate::<T>::fresh_rate(), Some(rate));
+	}
+
+	add_feeder {
+		let origin = T::UpdateOrigin::successful_origin();
+		let feeder: T::AccountId = account("feeder", 0, 0);
+		let feeder_lookup = T::Lookup::unlookup(feeder.clone());
+		let call = Call::<T>::add_feeder { feeder: feeder_lookup };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(ExchangeRate::<T>::is_feeder(&feeder));
+	}
+
+	remove_feeder {
+		let origin = T::UpdateOrigin::successful_origin();
+		let feeder: T::AccountId = account("feeder", 0, 0);
+		<Feeders<T>>::insert(&feeder, ());
+
+		let feeder_lookup = T::Lookup::unlookup(feeder.clone());
+		let call = Call::<T>::remove_feeder { feeder: feeder_lookup };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!ExchangeRate::<T>::is_feeder(&feeder));
+	}
+
+	impl_benchmark_test_suite!(ExchangeRate, crate::mock::new_tester(), crate::mock::Test,);
+}
@@ -0,0 +1,222 @@
+//! # Exchange Rate
+//!
+//! An oracle for the price of the fee asset on Ethereum in terms of the local reward currency.
+//!
+//! ## Overview
+//!
+//! Relayers are paid for delivering messages on the incentivized channel with fees collected on
+//! Ethereum. This pallet converts those fees into the currency used for rewards on the parachain,
+//! by implementing `Convert<U256, Option<Balance>>`.
+//!
+//! The rate is set by governance or by one of a set of trusted feeders. It is stamped with the
+//! block at which it was set, and is no longer used for conversion once it is older than
+//! `MaxRateAge`. Without a fresh rate, fees are converted by `FallbackConverter` instead.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Calls
+//!
+//! - `set_rate`: Set the exchange rate. Callable by `UpdateOrigin` or a feeder.
+//! - `add_feeder`: Authorize an account to set the exchange rate.
+//! - `remove_feeder`: Revoke the authorization of a feeder.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::EnsureOrigin;
+use frame_system::ensure_signed;
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, U256};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Convert, Saturating, StaticLookup, Zero},
+	FixedPointNumber, FixedU128,
+};
+use sp_std::{convert::TryFrom, prelude::*};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// An exchange rate, together with the block at which it was set.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Rate<BlockNumber> {
+	/// Amount of reward currency per smallest unit of the fee asset.
+	pub value: FixedU128,
+	/// Block at which the rate was set.
+	pub updated_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The reward currency balance type.
+		type Balance: AtLeast32BitUnsigned;
+
+		/// Number of blocks after which a rate is considered stale and no longer used.
+		#[pallet::constant]
+		type MaxRateAge: Get<Self::BlockNumber>;
+
+		/// Converts fees while there is no fresh rate, for example before feeders first set one.
+		type FallbackConverter: Convert<U256, Option<Self::Balance>>;
+
+		/// Origin which can set the rate and manage feeders.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The exchange rate was updated.
+		RateUpdated(FixedU128),
+		/// Account is now authorized to set the exchange rate.
+		FeederAdded(T::AccountId),
+		/// Account is no longer authorized to set the exchange rate.
+		FeederRemoved(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Not authorized to set the exchange rate.
+		NotFeeder,
+		/// Account is not a feeder.
+		UnknownFeeder,
+		/// The exchange rate must be non-zero.
+		ZeroRate,
+	}
+
+	/// The current exchange rate.
+	#[pallet::storage]
+	#[pallet::getter(fn rate)]
+	pub type CurrentRate<T: Config> = StorageValue<_, Rate<T::BlockNumber>, OptionQuery>;
+
+	/// Accounts which are authorized to set the exchange rate.
+	#[pallet::storage]
+	pub type Feeders<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub rate: Option<FixedU128>,
+		pub feeders: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { rate: None, feeders: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			if let Some(value) = self.rate {
+				<CurrentRate<T>>::put(Rate { value, updated_at: Zero::zero() });
+			}
+			for feeder in self.feeders.iter() {
+				<Feeders<T>>::insert(feeder, ());
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(T::WeightInfo::set_rate())]
+		pub fn set_rate(origin: OriginFor<T>, rate: FixedU128) -> DispatchResult {
+			if let Err(origin) = T::UpdateOrigin::try_origin(origin) {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::is_feeder(&who), Error::<T>::NotFeeder);
+			}
+			ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+
+			let updated_at = <frame_system::Pallet<T>>::block_number();
+			<CurrentRate<T>>::put(Rate { value: rate, updated_at });
+			Self::deposit_event(Event::RateUpdated(rate));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_feeder())]
+		pub fn add_feeder(
+			origin: OriginFor<T>,
+			feeder: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let feeder = T::Lookup::lookup(feeder)?;
+			<Feeders<T>>::insert(&feeder, ());
+			Self::deposit_event(Event::FeederAdded(feeder));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_feeder())]
+		pub fn remove_feeder(
+			origin: OriginFor<T>,
+			feeder: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let feeder = T::Lookup::lookup(feeder)?;
+			ensure!(Self::is_feeder(&feeder), Error::<T>::UnknownFeeder);
+			<Feeders<T>>::remove(&feeder);
+			Self::deposit_event(Event::FeederRemoved(feeder));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn is_feeder(account: &T::AccountId) -> bool {
+			<Feeders<T>>::contains_key(account)
+		}
+
+		/// The current exchange rate, if one is set and it is not stale.
+		pub fn fresh_rate() -> Option<FixedU128> {
+			let rate = <CurrentRate<T>>::get()?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			if now.saturating_sub(rate.updated_at) > T::MaxRateAge::get() {
+				return None
+			}
+			Some(rate.value)
+		}
+	}
+
+	/// Converts an amount of the fee asset on Ethereum into the reward currency.
+	///
+	/// Falls back to [`Config::FallbackConverter`] when there is no fresh rate. Yields `None` when
+	/// the converted amount does not fit.
+	impl<T: Config> Convert<U256, Option<T::Balance>> for Pallet<T> {
+		fn convert(amount: U256) -> Option<T::Balance> {
+			let rate = match Self::fresh_rate() {
+				Some(rate) => rate,
+				None => return T::FallbackConverter::convert(amount),
+			};
+			let amount = u128::try_from(amount).ok()?;
+			let converted = rate.checked_mul_int(amount)?;
+			T::Balance::try_from(converted).ok()
+		}
+	}
+}
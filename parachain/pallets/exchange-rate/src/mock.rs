@@ -0,0 +1,98 @@
+// Mock runtime
+use frame_support::{
+	parameter_types,
+	traits::{ConstU64, Everything, GenesisBuild},
+};
+use frame_system::EnsureRoot;
+use sp_core::{H256, U256};
+use sp_keyring::AccountKeyring as Keyring;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentifyAccount, IdentityLookup, Verify},
+	FixedPointNumber, FixedU128, MultiSignature,
+};
+
+use crate as exchange_rate;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Event<T>},
+		ExchangeRate: exchange_rate::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+pub type Signature = MultiSignature;
+
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+parameter_types! {
+	pub static FallbackAmount: Option<u128> = None;
+}
+
+pub struct FallbackConverter;
+impl Convert<U256, Option<u128>> for FallbackConverter {
+	fn convert(_: U256) -> Option<u128> {
+		FallbackAmount::get()
+	}
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl exchange_rate::Config for Test {
+	type Event = Event;
+	type Balance = u128;
+	type MaxRateAge = ConstU64<10>;
+	type FallbackConverter = FallbackConverter;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+pub fn new_tester() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	let config: exchange_rate::GenesisConfig<Test> = exchange_rate::GenesisConfig {
+		rate: Some(FixedU128::saturating_from_rational(1, 100_000_000)),
+		feeders: vec![Keyring::Alice.into()],
+	};
+	GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
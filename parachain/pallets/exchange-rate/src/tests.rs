@@ -0,0 +1,170 @@
+use crate::mock::{
+	new_tester, AccountId, Event, ExchangeRate, FallbackAmount, Origin, System, Test,
+};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use sp_core::U256;
+use sp_keyring::AccountKeyring as Keyring;
+use sp_runtime::{traits::Convert, FixedPointNumber, FixedU128};
+
+use crate::{Error, Rate};
+
+fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
+
+// 1 wDOT (18 decimals) = 1 DOT (10 decimals)
+fn genesis_rate() -> FixedU128 {
+	FixedU128::saturating_from_rational(1, 100_000_000)
+}
+
+#[test]
+fn converts_with_genesis_rate() {
+	new_tester().execute_with(|| {
+		assert_eq!(ExchangeRate::rate(), Some(Rate { value: genesis_rate(), updated_at: 0 }));
+		assert_eq!(
+			<ExchangeRate as Convert<U256, Option<u128>>>::convert(U256::from(10u128.pow(18))),
+			Some(10u128.pow(10))
+		);
+	});
+}
+
+#[test]
+fn feeder_can_set_rate() {
+	new_tester().execute_with(|| {
+		let feeder: AccountId = Keyring::Alice.into();
+		let rate = FixedU128::saturating_from_rational(3, 2);
+
+		assert_ok!(ExchangeRate::set_rate(Origin::signed(feeder), rate));
+		assert_eq!(ExchangeRate::rate(), Some(Rate { value: rate, updated_at: 1 }));
+		assert_eq!(Event::ExchangeRate(crate::Event::<Test>::RateUpdated(rate)), last_event());
+
+		assert_eq!(
+			<ExchangeRate as Convert<U256, Option<u128>>>::convert(U256::from(1000)),
+			Some(1500)
+		);
+	});
+}
+
+#[test]
+fn root_can_set_rate() {
+	new_tester().execute_with(|| {
+		assert_ok!(ExchangeRate::set_rate(
+			Origin::root(),
+			FixedU128::saturating_from_integer(2u128)
+		));
+		assert_eq!(ExchangeRate::fresh_rate(), Some(FixedU128::saturating_from_integer(2u128)));
+	});
+}
+
+#[test]
+fn set_rate_not_authorized() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+
+		assert_noop!(
+			ExchangeRate::set_rate(Origin::signed(bob), FixedU128::saturating_from_integer(2u128)),
+			Error::<Test>::NotFeeder
+		);
+		assert_noop!(
+			ExchangeRate::set_rate(Origin::none(), FixedU128::saturating_from_integer(2u128)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_rate_rejects_zero() {
+	new_tester().execute_with(|| {
+		assert_noop!(
+			ExchangeRate::set_rate(Origin::root(), FixedU128::saturating_from_integer(0u128)),
+			Error::<Test>::ZeroRate
+		);
+	});
+}
+
+#[test]
+fn stale_rate_is_not_used() {
+	new_tester().execute_with(|| {
+		let amount = U256::from(10u128.pow(18));
+
+		// MaxRateAge is 10 blocks
+		System::set_block_number(10);
+		assert!(<ExchangeRate as Convert<U256, Option<u128>>>::convert(amount).is_some());
+
+		System::set_block_number(11);
+		assert_eq!(ExchangeRate::fresh_rate(), None);
+		assert_eq!(<ExchangeRate as Convert<U256, Option<u128>>>::convert(amount), None);
+
+		// A new rate makes conversion possible again
+		assert_ok!(ExchangeRate::set_rate(Origin::root(), genesis_rate()));
+		assert_eq!(
+			<ExchangeRate as Convert<U256, Option<u128>>>::convert(amount),
+			Some(10u128.pow(10))
+		);
+	});
+}
+
+#[test]
+fn falls_back_without_fresh_rate() {
+	new_tester().execute_with(|| {
+		let amount = U256::from(10u128.pow(18));
+		FallbackAmount::set(Some(7));
+		assert_eq!(
+			<ExchangeRate as Convert<U256, Option<u128>>>::convert(amount),
+			Some(10u128.pow(10))
+		);
+
+		System::set_block_number(11);
+		assert_eq!(<ExchangeRate as Convert<U256, Option<u128>>>::convert(amount), Some(7));
+	});
+}
+
+#[test]
+fn convert_fails_on_overflow() {
+	new_tester().execute_with(|| {
+		assert_ok!(ExchangeRate::set_rate(
+			Origin::root(),
+			FixedU128::saturating_from_integer(2u128)
+		));
+
+		assert_eq!(
+			<ExchangeRate as Convert<U256, Option<u128>>>::convert(U256::from(u128::MAX)),
+			None
+		);
+		assert_eq!(<ExchangeRate as Convert<U256, Option<u128>>>::convert(U256::MAX), None);
+	});
+}
+
+#[test]
+fn add_and_remove_feeders() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+
+		assert_ok!(ExchangeRate::add_feeder(Origin::root(), bob.clone()));
+		assert!(ExchangeRate::is_feeder(&bob));
+		assert_eq!(
+			Event::ExchangeRate(crate::Event::<Test>::FeederAdded(bob.clone())),
+			last_event()
+		);
+		assert_ok!(ExchangeRate::set_rate(
+			Origin::signed(bob.clone()),
+			FixedU128::saturating_from_integer(2u128)
+		));
+
+		assert_ok!(ExchangeRate::remove_feeder(Origin::root(), bob.clone()));
+		assert!(!ExchangeRate::is_feeder(&bob));
+		assert_eq!(
+			Event::ExchangeRate(crate::Event::<Test>::FeederRemoved(bob.clone())),
+			last_event()
+		);
+
+		assert_noop!(
+			ExchangeRate::remove_feeder(Origin::root(), bob.clone()),
+			Error::<Test>::UnknownFeeder
+		);
+		assert_noop!(
+			ExchangeRate::add_feeder(Origin::signed(bob.clone()), bob),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,81 @@
+//! Placeholder weights for exchange_rate
+//!
+//! THESE WEIGHTS ARE ESTIMATES, NOT BENCHMARK RESULTS. They are based on the benchmarked weights
+//! of extrinsics with similar storage access in other pallets, and must be replaced with the
+//! output of the benchmark CLI before this pallet is deployed to a production chain.
+
+// Command to generate the weights:
+// target/release/snowbridge
+// benchmark
+// --chain
+// spec.json
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet
+// exchange_rate
+// --extra
+// --extrinsic
+// *
+// --repeat
+// 10
+// --steps
+// 50
+// --output
+// pallets/exchange-rate/src/weights.rs
+// --template
+// module-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for exchange_rate.
+pub trait WeightInfo {
+	fn set_rate() -> Weight;
+	fn add_feeder() -> Weight;
+	fn remove_feeder() -> Weight;
+}
+
+/// Estimated weights for exchange_rate, pending benchmarks on the Snowbridge node and
+/// recommended hardware.
+pub struct SnowbridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
+	fn set_rate() -> Weight {
+		(18_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_feeder() -> Weight {
+		(14_006_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_feeder() -> Weight {
+		(17_214_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_rate() -> Weight {
+		(18_512_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_feeder() -> Weight {
+		(14_006_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_feeder() -> Weight {
+		(17_214_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
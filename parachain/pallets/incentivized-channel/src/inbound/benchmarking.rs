@@ -2,7 +2,8 @@
 
 use super::*;
 
-use frame_benchmarking::{benchmarks, BenchmarkError};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_system::RawOrigin;
 
 #[allow(unused_imports)]
 use crate::inbound::Pallet as IncentivizedInboundChannel;
@@ -32,6 +33,22 @@ benchmarks! {
 		assert_eq!(<RewardFraction<T>>::get(), fraction);
 	}
 
+	// Benchmark `claim_rewards` under worst case conditions:
+	// * The claimed account does not exist yet and is created by the payout
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		let relayer: T::AccountId = account("relayer", 0, 0);
+		let amount = T::Currency::minimum_balance() * 10u32.into();
+
+		T::Currency::make_free_balance_be(&T::SourceAccount::get(), amount * 2u32.into());
+		<PendingRewards<T>>::insert(&relayer, amount);
+
+	}: _(RawOrigin::Signed(caller), relayer.clone())
+	verify {
+		assert_eq!(<PendingRewards<T>>::get(&relayer), Zero::zero());
+		assert_eq!(T::Currency::free_balance(&relayer), amount);
+	}
+
 	impl_benchmark_test_suite!(
 		IncentivizedInboundChannel,
 		crate::inbound::test::new_tester(Default::default()),
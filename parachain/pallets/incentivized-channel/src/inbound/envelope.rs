@@ -28,7 +28,7 @@ where
 	pub channel: H160,
 	/// The application on Ethereum where the message originated from.
 	pub source: H160,
-	/// A nonce for enforcing replay protection and ordering.
+	/// A nonce for enforcing replay protection and ordering of messages from `source`.
 	pub nonce: u64,
 	/// Fee paid by user for relaying the message
 	pub fee: BalanceOf<T>,
@@ -8,21 +8,26 @@ pub mod weights;
 mod test;
 
 use frame_support::{
-	log,
-	traits::{
-		Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get, Imbalance, WithdrawReasons,
-	},
+	traits::{Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get},
+	weights::Weight,
 };
 use frame_system::ensure_signed;
-use snowbridge_core::{ChannelId, Message, MessageDispatch, MessageId, Verifier};
-use sp_core::{H160, U256};
+use snowbridge_core::{
+	BridgeComponent, ChannelId, ChannelSource, CircuitBreaker, EthereumTime, Message,
+	MessageDispatch, MessageId, Verifier,
+};
+use snowbridge_ethereum::Log;
+use sp_core::{H160, H256, U256};
 use sp_std::convert::TryFrom;
 
 use envelope::Envelope;
 pub use weights::WeightInfo;
 
+/// Weight of verifying and accepting a message, excluding its dispatch.
+const SUBMIT_WEIGHT: Weight = 100_000_000;
+
 use sp_runtime::{
-	traits::{Convert, Zero},
+	traits::{Convert, Saturating, Zero},
 	Perbill,
 };
 
@@ -33,11 +38,18 @@ pub mod pallet {
 
 	use super::*;
 
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{unhashed, StoragePrefixedMap},
+	};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -50,9 +62,20 @@ pub mod pallet {
 		/// Verifier module for message verification.
 		type MessageDispatch: MessageDispatch<Self, MessageId>;
 
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
+		/// Outbound channel contract on Ethereum which messages on the incentivized channel are
+		/// sent from.
+		type ChannelSource: ChannelSource;
+
+		/// Time on Ethereum, used to reject messages which may have been accepted before nonces
+		/// were tracked per source application.
+		type EthereumTime: EthereumTime;
+
 		type Currency: Currency<Self::AccountId>;
 
-		/// Source of funds to pay relayers
+		/// Source of funds to pay out claimed rewards
 		#[pallet::constant]
 		type SourceAccount: Get<Self::AccountId>;
 
@@ -60,6 +83,8 @@ pub mod pallet {
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
+		/// Converts fees paid on Ethereum into the reward currency. Fees which cannot be
+		/// converted are treated as zero.
 		type FeeConverter: Convert<U256, Option<BalanceOf<Self>>>;
 
 		/// The origin which may update reward related params
@@ -70,10 +95,45 @@ pub mod pallet {
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				// Nonces used to be a single value, stored where the map prefix now is.
+				// Sources start from zero on a freshly deployed outbound channel contract.
+				unhashed::kill(&<Nonce<T>>::final_prefix());
+				// Messages accepted with the single nonce are all in finalized blocks. They are
+				// rejected from now on, so that they cannot be replayed with per-source nonces.
+				<LegacyMessagesUntil<T>>::set(T::EthereumTime::finalized_timestamp());
+				STORAGE_VERSION.put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(2, 3)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
 
 	#[pallet::event]
-	pub enum Event<T> {}
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Message has been verified and accepted for dispatch.
+		MessageReceived {
+			channel: ChannelId,
+			/// The application on Ethereum which sent the message.
+			source: H160,
+			nonce: u64,
+			relayer: T::AccountId,
+			/// Hash of the Ethereum block containing the message.
+			block_hash: H256,
+			/// Index of the transaction within that block.
+			tx_index: u32,
+		},
+		/// Relayer has been credited with their share of a message fee.
+		RelayerRewarded { relayer: T::AccountId, amount: BalanceOf<T> },
+		/// Treasury has been credited with the remainder of a message fee.
+		TreasuryCredited { amount: BalanceOf<T> },
+		/// Pending rewards have been paid out to an account.
+		RewardsClaimed { account: T::AccountId, amount: BalanceOf<T> },
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -83,62 +143,84 @@ pub mod pallet {
 		InvalidEnvelope,
 		/// Message has an unexpected nonce.
 		InvalidNonce,
+		/// Message is in an Ethereum block from before nonces were tracked per source application.
+		LegacyMessage,
+		/// The account has no pending rewards.
+		NoRewards,
 	}
 
-	/// Source channel on the ethereum side
+	/// Source channel on the ethereum side, from before channels were registered in the channel
+	/// registry. Only read when the registry is seeded.
 	#[pallet::storage]
-	#[pallet::getter(fn source_channel)]
 	pub type SourceChannel<T: Config> = StorageValue<_, H160, ValueQuery>;
 
+	/// Nonce of the last message received from each source application on Ethereum.
+	#[pallet::storage]
+	pub type Nonce<T: Config> = StorageMap<_, Blake2_128Concat, H160, u64, ValueQuery>;
+
+	/// Timestamp of the latest finalized Ethereum block when nonces started to be tracked per
+	/// source application. Messages in blocks up to it are rejected.
 	#[pallet::storage]
-	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+	pub type LegacyMessagesUntil<T: Config> = StorageValue<_, u64, OptionQuery>;
 
 	/// Fraction of reward going to relayer
 	#[pallet::storage]
 	#[pallet::getter(fn reward_fraction)]
 	pub type RewardFraction<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// Rewards credited to each account and not yet claimed
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rewards)]
+	pub type PendingRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
-		pub source_channel: H160,
 		pub reward_fraction: Perbill,
 	}
 
 	#[cfg(feature = "std")]
 	impl Default for GenesisConfig {
 		fn default() -> Self {
-			Self { source_channel: Default::default(), reward_fraction: Perbill::one() }
+			Self { reward_fraction: Perbill::one() }
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
-			<SourceChannel<T>>::put(self.source_channel);
 			<RewardFraction<T>>::put(self.reward_fraction);
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(100_000_000)]
-		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResult {
+		#[pallet::weight(SUBMIT_WEIGHT.saturating_add(Pallet::<T>::dispatch_weight(message)))]
+		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResultWithPostInfo {
 			let relayer = ensure_signed(origin)?;
+			T::CircuitBreaker::ensure_active(BridgeComponent::InboundChannel(
+				ChannelId::INCENTIVIZED,
+			))?;
+
 			// submit message to verifier for verification
-			let log = T::Verifier::verify(&message)?;
+			let (log, info) = T::Verifier::verify(&message)?;
 
 			// Decode log into an Envelope
 			let envelope: Envelope<T> =
 				Envelope::try_from(log).map_err(|_| Error::<T>::InvalidEnvelope)?;
 
-			// Verify that the message was submitted to us from a known
-			// outbound channel on the ethereum side
-			if envelope.channel != <SourceChannel<T>>::get() {
+			// Verify that the message was submitted to us from the outbound channel
+			// registered for the incentivized channel on the ethereum side
+			if T::ChannelSource::source(ChannelId::INCENTIVIZED) != Some(envelope.channel) {
 				return Err(Error::<T>::InvalidSourceChannel.into())
 			}
 
+			if let Some(until) = <LegacyMessagesUntil<T>>::get() {
+				ensure!(info.timestamp > until, Error::<T>::LegacyMessage);
+			}
+
 			// Verify message nonce
-			<Nonce<T>>::try_mutate(|nonce| -> DispatchResult {
+			<Nonce<T>>::try_mutate(envelope.source, |nonce| -> DispatchResult {
 				if envelope.nonce != *nonce + 1 {
 					Err(Error::<T>::InvalidNonce.into())
 				} else {
@@ -147,12 +229,23 @@ pub mod pallet {
 				}
 			})?;
 
+			Self::deposit_event(Event::MessageReceived {
+				channel: ChannelId::INCENTIVIZED,
+				source: envelope.source,
+				nonce: envelope.nonce,
+				relayer: relayer.clone(),
+				block_hash: info.block_hash,
+				tx_index: message.proof.receipt_proof().tx_index,
+			});
+
 			Self::handle_fee(envelope.fee, &relayer);
 
-			let message_id = MessageId::new(ChannelId::Incentivized, envelope.nonce);
-			T::MessageDispatch::dispatch(envelope.source, message_id, &envelope.payload);
+			let message_id =
+				MessageId::new(ChannelId::INCENTIVIZED, envelope.source, envelope.nonce);
+			let dispatch_weight =
+				T::MessageDispatch::dispatch(envelope.source, message_id, &envelope.payload);
 
-			Ok(())
+			Ok(Some(SUBMIT_WEIGHT.saturating_add(dispatch_weight)).into())
 		}
 
 		#[pallet::weight(T::WeightInfo::set_reward_fraction())]
@@ -161,59 +254,75 @@ pub mod pallet {
 			<RewardFraction<T>>::set(fraction);
 			Ok(())
 		}
+
+		/// Pay out the pending rewards of `account` from the source account.
+		///
+		/// Anyone may call this, so that accounts which cannot sign, such as the treasury,
+		/// can be paid too.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let amount = <PendingRewards<T>>::get(&account);
+			ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+
+			T::Currency::transfer(&T::SourceAccount::get(), &account, amount, KeepAlive)?;
+			<PendingRewards<T>>::remove(&account);
+			Self::deposit_event(Event::RewardsClaimed { account, amount });
+
+			Ok(())
+		}
 	}
 
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
-		<T as frame_system::Config>::AccountId,
-	>>::PositiveImbalance;
 
 	impl<T: Config> Pallet<T> {
+		/// Weight declared by the call carried in `message`.
+		///
+		/// Messages which cannot be decoded get no extra weight, as they will fail verification.
+		fn dispatch_weight(message: &Message) -> Weight {
+			rlp::decode::<Log>(&message.data)
+				.ok()
+				.and_then(|log| Envelope::<T>::try_from(log).ok())
+				.map(|envelope| T::MessageDispatch::dispatch_weight(&envelope.payload))
+				.unwrap_or(0)
+		}
+
 		/*
-		 * Pay the message submission fee into the relayer and treasury account.
+		 * Credit the message submission fee to the relayer and treasury account.
 		 *
 		 * - If the fee is zero, do nothing
-		 * - Otherwise, withdraw the fee amount from the DotApp module account, returning a
-		 *   negative imbalance
 		 * - Figure out the fraction of the fee amount that should be paid to the relayer
-		 * - Pay the relayer if their account exists, returning a positive imbalance.
-		 * - Adjust the negative imbalance by offsetting the amount paid to the relayer
-		 * - Resolve the negative imbalance by depositing it into the treasury account
+		 * - Credit the relayer with that amount in the reward ledger
+		 * - Credit the treasury account with the remainder
+		 *
+		 * Credited amounts are paid out of the source account by `claim_rewards`.
 		 */
 		pub(super) fn handle_fee(amount: BalanceOf<T>, relayer: &T::AccountId) {
 			if amount.is_zero() {
 				return
 			}
 
-			let imbalance = match T::Currency::withdraw(
-				&T::SourceAccount::get(),
-				amount,
-				WithdrawReasons::TRANSFER,
-				KeepAlive,
-			) {
-				Ok(imbalance) => imbalance,
-				Err(err) => {
-					log::error!("Unable to withdraw from source account: {:?}", err);
-					return
-				},
-			};
-
 			let reward_fraction: Perbill = <RewardFraction<T>>::get();
 			let reward_amount = reward_fraction.mul_ceil(amount);
+			let treasury_amount = amount.saturating_sub(reward_amount);
+
+			if !reward_amount.is_zero() {
+				<PendingRewards<T>>::mutate(relayer, |pending| {
+					*pending = pending.saturating_add(reward_amount)
+				});
+				Self::deposit_event(Event::RelayerRewarded {
+					relayer: relayer.clone(),
+					amount: reward_amount,
+				});
+			}
 
-			let rewarded = T::Currency::deposit_into_existing(relayer, reward_amount)
-				.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
-
-			let adjusted_imbalance = match imbalance.offset(rewarded).same() {
-				Ok(imbalance) => imbalance,
-				Err(_) => {
-					log::error!("Unable to offset imbalance");
-					return
-				},
-			};
-
-			T::Currency::resolve_creating(&T::TreasuryAccount::get(), adjusted_imbalance);
+			if !treasury_amount.is_zero() {
+				<PendingRewards<T>>::mutate(T::TreasuryAccount::get(), |pending| {
+					*pending = pending.saturating_add(treasury_amount)
+				});
+				Self::deposit_event(Event::TreasuryCredited { amount: treasury_amount });
+			}
 		}
 	}
 }
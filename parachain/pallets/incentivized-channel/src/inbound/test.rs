@@ -4,7 +4,8 @@ use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::DispatchError,
 	parameter_types,
-	traits::{Currency, Everything, GenesisBuild},
+	traits::{Currency, Everything, GenesisBuild, OnRuntimeUpgrade, StorageVersion},
+	weights::GetDispatchInfo,
 };
 use sp_core::{H160, H256};
 use sp_keyring::AccountKeyring as Keyring;
@@ -15,8 +16,8 @@ use sp_runtime::{
 };
 use sp_std::{convert::From, marker::PhantomData};
 
-use snowbridge_core::{Message, MessageDispatch, Proof};
-use snowbridge_ethereum::{Header as EthereumHeader, Log, U256};
+use snowbridge_core::{Message, MessageDispatch, Proof, ReceiptProof, VerificationInfo};
+use snowbridge_ethereum::{Log, U256};
 
 use hex_literal::hex;
 
@@ -94,25 +95,49 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
-// Mock verifier
+parameter_types! {
+	pub static MessageTimestamp: u64 = 0;
+	pub static EthereumTimestamp: Option<u64> = None;
+}
+
+// Mock verifier, which places every message in a block with timestamp `MessageTimestamp`
 pub struct MockVerifier;
 
 impl Verifier for MockVerifier {
-	fn verify(message: &Message) -> Result<Log, DispatchError> {
+	fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
 		let log: Log = rlp::decode(&message.data).unwrap();
-		Ok(log)
+		let info = VerificationInfo {
+			block_number: 0,
+			block_hash: message.proof.receipt_proof().block_hash,
+			timestamp: MessageTimestamp::get(),
+			log_index: 0,
+		};
+		Ok((log, info))
 	}
+}
 
-	fn initialize_storage(_: Vec<EthereumHeader>, _: U256, _: u8) -> Result<(), &'static str> {
-		Ok(())
+pub struct MockEthereumTime;
+
+impl EthereumTime for MockEthereumTime {
+	fn finalized_timestamp() -> Option<u64> {
+		EthereumTimestamp::get()
 	}
 }
 
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
+const DECLARED_DISPATCH_WEIGHT: Weight = 1_000_000;
+const ACTUAL_DISPATCH_WEIGHT: Weight = 400_000;
+
 impl MessageDispatch<Test, MessageId> for MockMessageDispatch {
-	fn dispatch(_: H160, _: MessageId, _: &[u8]) {}
+	fn dispatch(_: H160, _: MessageId, _: &[u8]) -> Weight {
+		ACTUAL_DISPATCH_WEIGHT
+	}
+
+	fn dispatch_weight(_: &[u8]) -> Weight {
+		DECLARED_DISPATCH_WEIGHT
+	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn successful_dispatch_event(_: MessageId) -> Option<<Test as frame_system::Config>::Event> {
@@ -133,6 +158,23 @@ impl<T: Config> Convert<U256, Option<BalanceOf<T>>> for FeeConverter<T> {
 	}
 }
 
+parameter_types! {
+	pub static RegisteredSource: Option<H160> = None;
+}
+
+// Registers `RegisteredSource` for the incentivized channel only
+pub struct MockChannelSource;
+
+impl ChannelSource for MockChannelSource {
+	fn source(channel_id: ChannelId) -> Option<H160> {
+		if channel_id == ChannelId::INCENTIVIZED {
+			RegisteredSource::get()
+		} else {
+			None
+		}
+	}
+}
+
 impl incentivized_inbound_channel::Config for Test {
 	type Event = Event;
 	type Verifier = MockVerifier;
@@ -142,12 +184,15 @@ impl incentivized_inbound_channel::Config for Test {
 	type TreasuryAccount = TreasuryAccount;
 	type FeeConverter = FeeConverter<Self>;
 	type UpdateOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CircuitBreaker = ();
+	type ChannelSource = MockChannelSource;
+	type EthereumTime = MockEthereumTime;
 	type WeightInfo = ();
 }
 
 pub fn new_tester(source_channel: H160) -> sp_io::TestExternalities {
+	RegisteredSource::set(Some(source_channel));
 	new_tester_with_config(incentivized_inbound_channel::GenesisConfig {
-		source_channel,
 		reward_fraction: Perbill::from_percent(80),
 	})
 }
@@ -164,6 +209,12 @@ pub fn new_tester_with_config(
 	ext
 }
 
+// The source application address for MESSAGE_DATA_0 and MESSAGE_DATA_1
+const SOURCE_APP_ADDR: [u8; 20] = hex!["c2c5d46481c291be111d5e3a0b52114bdf212a01"];
+
+// The source application address for MESSAGE_DATA_2
+const OTHER_SOURCE_APP_ADDR: [u8; 20] = hex!["89b4ab1ef20763630df9743acf155865600daff2"];
+
 // The originating channel address for the messages below
 const SOURCE_CHANNEL_ADDR: [u8; 20] = hex!["4130819912a398f4eb84e7f16ed443232ba638b5"];
 
@@ -199,6 +250,22 @@ const MESSAGE_DATA_1: [u8; 317] = hex!(
 "
 );
 
+// Message with nonce = 1 from another source application
+const MESSAGE_DATA_2: [u8; 317] = hex!(
+	"
+	f9013a944130819912a398f4eb84e7f16ed443232ba638b5e1a05e9ae1d7c484
+	f74d554a503aa825e823725531d97e784dd9b1aacdb58d1f7076b90100000000
+	00000000000000000089b4ab1ef20763630df9743acf155865600daff2000000
+	0000000000000000000000000000000000000000000000000000000001000000
+	0000000000000000000000000000000000000000000de0b6b3a7640000000000
+	0000000000000000000000000000000000000000000000000000000080000000
+	00000000000000000000000000000000000000000000000000000000570c0182
+	13dae5f9c236beab905c8305cb159c5fa1aae500d43593c715fdd31c61141abd
+	04a99fd6822c8558854ccde39a5684e7a56da27d0000d9e9ac2d780300000000
+	0000000000000000000000000000000000000000000000000000000000
+"
+);
+
 #[test]
 fn test_submit_with_invalid_source_channel() {
 	new_tester(H160::zero()).execute_with(|| {
@@ -208,11 +275,11 @@ fn test_submit_with_invalid_source_channel() {
 		// Submit message
 		let message = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_noop!(
 			IncentivizedInboundChannel::submit(origin.clone(), message.clone()),
@@ -230,31 +297,61 @@ fn test_submit() {
 		// Submit message 1
 		let message_1 = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(IncentivizedInboundChannel::submit(origin.clone(), message_1));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 1);
 
 		// Submit message 2
 		let message_2 = Message {
 			data: MESSAGE_DATA_1.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(IncentivizedInboundChannel::submit(origin.clone(), message_2));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 2);
 	});
 }
 
+#[test]
+fn test_submit_emits_message_received() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer.clone());
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: H256::repeat_byte(1),
+				tx_index: 3,
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(IncentivizedInboundChannel::submit(origin, message));
+
+		let expected = Event::IncentivizedInboundChannel(
+			incentivized_inbound_channel::Event::MessageReceived {
+				channel: ChannelId::INCENTIVIZED,
+				source: SOURCE_APP_ADDR.into(),
+				nonce: 1,
+				relayer: relayer.clone(),
+				block_hash: H256::repeat_byte(1),
+				tx_index: 3,
+			},
+		);
+		assert!(System::events().iter().any(|record| record.event == expected));
+	});
+}
+
 #[test]
 fn test_submit_with_invalid_nonce() {
 	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
@@ -264,14 +361,14 @@ fn test_submit_with_invalid_nonce() {
 		// Submit message
 		let message = Message {
 			data: MESSAGE_DATA_0.into(),
-			proof: Proof {
+			proof: Proof::PoW(ReceiptProof {
 				block_hash: Default::default(),
 				tx_index: Default::default(),
 				data: Default::default(),
-			},
+			}),
 		};
 		assert_ok!(IncentivizedInboundChannel::submit(origin.clone(), message.clone()));
-		let nonce: u64 = <Nonce<Test>>::get();
+		let nonce: u64 = <Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR));
 		assert_eq!(nonce, 1);
 
 		// Submit the same again
@@ -294,8 +391,94 @@ fn test_handle_fee() {
 		let fee = 10000000000; // 1 DOT
 
 		IncentivizedInboundChannel::handle_fee(fee, &relayer);
-		assert_eq!(Balances::free_balance(&TreasuryAccount::get()), 2000000001);
-		assert_eq!(Balances::free_balance(&relayer), 8000000001);
+		assert_eq!(Balances::free_balance(&TreasuryAccount::get()), 1);
+		assert_eq!(Balances::free_balance(&relayer), 1);
+		assert_eq!(
+			IncentivizedInboundChannel::pending_rewards(&TreasuryAccount::get()),
+			2000000000
+		);
+		assert_eq!(IncentivizedInboundChannel::pending_rewards(&relayer), 8000000000);
+
+		let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+		assert!(events.contains(&Event::IncentivizedInboundChannel(
+			incentivized_inbound_channel::Event::RelayerRewarded {
+				relayer: relayer.clone(),
+				amount: 8000000000,
+			}
+		)));
+		assert!(events.contains(&Event::IncentivizedInboundChannel(
+			incentivized_inbound_channel::Event::TreasuryCredited { amount: 2000000000 }
+		)));
+	});
+}
+
+#[test]
+fn test_claim_rewards() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let treasury = TreasuryAccount::get();
+
+		let _ = Balances::deposit_creating(&SourceAccount::get(), 100000000000); // 10 DOT
+
+		IncentivizedInboundChannel::handle_fee(10000000000, &relayer);
+
+		assert_ok!(IncentivizedInboundChannel::claim_rewards(
+			Origin::signed(relayer.clone()),
+			relayer.clone()
+		));
+		assert_eq!(Balances::free_balance(&relayer), 8000000000);
+		assert_eq!(IncentivizedInboundChannel::pending_rewards(&relayer), 0);
+
+		// Anyone can pay out the treasury
+		assert_ok!(IncentivizedInboundChannel::claim_rewards(
+			Origin::signed(relayer.clone()),
+			treasury.clone()
+		));
+		assert_eq!(Balances::free_balance(&treasury), 2000000000);
+		assert_eq!(IncentivizedInboundChannel::pending_rewards(&treasury), 0);
+		assert_eq!(Balances::free_balance(&SourceAccount::get()), 90000000000);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::IncentivizedInboundChannel(
+				incentivized_inbound_channel::Event::RewardsClaimed {
+					account: treasury,
+					amount: 2000000000,
+				}
+			)
+		);
+	});
+}
+
+#[test]
+fn test_claim_rewards_fails_without_rewards() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+
+		assert_noop!(
+			IncentivizedInboundChannel::claim_rewards(Origin::signed(relayer.clone()), relayer),
+			Error::<Test>::NoRewards
+		);
+	});
+}
+
+#[test]
+fn test_claim_rewards_keeps_ledger_on_failed_transfer() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+
+		// The source account holds less than the credited fee
+		let _ = Balances::deposit_creating(&SourceAccount::get(), 1000);
+		IncentivizedInboundChannel::handle_fee(10000000000, &relayer);
+
+		assert_noop!(
+			IncentivizedInboundChannel::claim_rewards(
+				Origin::signed(relayer.clone()),
+				relayer.clone()
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(IncentivizedInboundChannel::pending_rewards(&relayer), 8000000000);
 	});
 }
 
@@ -312,3 +495,97 @@ fn test_set_reward_fraction_not_authorized() {
 		);
 	});
 }
+
+#[test]
+fn test_submit_with_independent_source_nonces() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message_1 = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(IncentivizedInboundChannel::submit(origin.clone(), message_1));
+
+		// Nonce 1 is accepted again, since it comes from a different source
+		let message_2 = Message {
+			data: MESSAGE_DATA_2.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+		assert_ok!(IncentivizedInboundChannel::submit(origin.clone(), message_2.clone()));
+
+		assert_eq!(<Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR)), 1);
+		assert_eq!(<Nonce<Test>>::get(H160::from(OTHER_SOURCE_APP_ADDR)), 1);
+
+		assert_noop!(
+			IncentivizedInboundChannel::submit(origin.clone(), message_2),
+			Error::<Test>::InvalidNonce
+		);
+	});
+}
+
+#[test]
+fn test_submit_charges_dispatch_weight() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+
+		let call = crate::inbound::Call::<Test>::submit { message: message.clone() };
+		assert_eq!(call.get_dispatch_info().weight, SUBMIT_WEIGHT + DECLARED_DISPATCH_WEIGHT);
+
+		let post_info = IncentivizedInboundChannel::submit(origin, message).unwrap();
+		assert_eq!(post_info.actual_weight, Some(SUBMIT_WEIGHT + ACTUAL_DISPATCH_WEIGHT));
+	});
+}
+
+#[test]
+fn test_submit_rejects_messages_from_before_migration() {
+	new_tester(SOURCE_CHANNEL_ADDR.into()).execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = Origin::signed(relayer);
+
+		// Storage from before nonces were tracked per source application
+		StorageVersion::new(0).put::<IncentivizedInboundChannel>();
+		EthereumTimestamp::set(Some(100));
+		IncentivizedInboundChannel::on_runtime_upgrade();
+		assert_eq!(<LegacyMessagesUntil<Test>>::get(), Some(100));
+
+		let message = Message {
+			data: MESSAGE_DATA_0.into(),
+			proof: Proof::PoW(ReceiptProof {
+				block_hash: Default::default(),
+				tx_index: Default::default(),
+				data: Default::default(),
+			}),
+		};
+
+		// The message may have been accepted with the single nonce used before
+		MessageTimestamp::set(100);
+		assert_noop!(
+			IncentivizedInboundChannel::submit(origin.clone(), message.clone()),
+			Error::<Test>::LegacyMessage
+		);
+
+		MessageTimestamp::set(101);
+		assert_ok!(IncentivizedInboundChannel::submit(origin, message));
+		assert_eq!(<Nonce<Test>>::get(H160::from(SOURCE_APP_ADDR)), 1);
+	});
+}
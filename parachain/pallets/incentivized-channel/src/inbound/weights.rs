@@ -3,6 +3,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-11-29, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("spec.json"), DB CACHE: 128
+//!
+//! Entries marked ESTIMATE are not output of the benchmark CLI. They must be replaced by
+//! re-running the benchmarks before this pallet is deployed to a production chain.
 
 // Executed Command:
 // target/release/snowbridge
@@ -38,6 +41,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for incentivized_channel::inbound.
 pub trait WeightInfo {
 	fn set_reward_fraction() -> Weight;
+	fn claim_rewards() -> Weight;
 }
 
 /// Weights for incentivized_channel::inbound using the Snowbridge node and recommended hardware.
@@ -47,6 +51,13 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 		(2_321_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: taking the pending reward and transferring it to an account which is
+	// created by the payout. Replace with the output of the `claim_rewards` benchmark.
+	fn claim_rewards() -> Weight {
+		(38_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -55,4 +66,11 @@ impl WeightInfo for () {
 		(2_321_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: taking the pending reward and transferring it to an account which is
+	// created by the payout. Replace with the output of the `claim_rewards` benchmark.
+	fn claim_rewards() -> Weight {
+		(38_402_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
 }
@@ -19,6 +19,8 @@ benchmarks! {
 			<MessageQueue<T>>::try_append(Message {
 				id: 0u64,
 				target: H160::zero(),
+				fee: 0,
+				expires_at: None,
 				payload: payload.try_into().unwrap(),
 			}).unwrap();
 		}
@@ -36,6 +38,8 @@ benchmarks! {
 		<MessageQueue<T>>::try_append(Message {
 			id: 0u64,
 			target: H160::zero(),
+			fee: 0,
+			expires_at: None,
 			payload: vec![1u8; T::MaxMessagePayloadSize::get() as usize].try_into().unwrap(),
 		}).unwrap();
 
@@ -72,6 +76,50 @@ benchmarks! {
 		assert_eq!(<Fee<T>>::get(), new_fee);
 	}
 
+	// Benchmark `set_gas_config` under worst case conditions:
+	// * The origin is authorized, i.e. equals SetFeeOrigin
+	set_gas_config {
+		let authorized_origin = match T::SetFeeOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+
+		let config = GasConfig { base_gas: 100000, gas_per_byte: 16 };
+
+	}: _(authorized_origin, config)
+	verify {
+		assert_eq!(<DeliveryGas<T>>::get(), config);
+	}
+
+	// Benchmark `set_fee_inputs` under worst case conditions:
+	// * The origin is authorized, i.e. equals FeeFeederOrigin
+	set_fee_inputs {
+		let authorized_origin = match T::FeeFeederOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+
+		let inputs = FeeInputs { gas_price: 30000000000, exchange_rate: FixedU128::one() };
+
+	}: _(authorized_origin, inputs)
+	verify {
+		assert_eq!(<CurrentFeeInputs<T>>::get(), inputs);
+	}
+
+	set_commitment_versions {
+		let authorized_origin = match T::SetCommitmentVersionsOrigin::successful_origin().into() {
+			Ok(raw) => raw,
+			Err(_) => return Err(BenchmarkError::Stop("Failed to get raw origin from origin")),
+		};
+		let versions = vec![CommitmentVersion::V2, CommitmentVersion::V0];
+	}: _(authorized_origin, versions)
+	verify {
+		assert_eq!(
+			<CommitmentVersions<T>>::get().into_inner(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		);
+	}
+
 	impl_benchmark_test_suite!(
 		IncentivizedOutboundChannel,
 		crate::outbound::test::new_tester(),
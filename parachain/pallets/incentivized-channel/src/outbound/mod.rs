@@ -10,21 +10,35 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use ethabi::{self, Token};
 use frame_support::{
 	dispatch::DispatchResult,
-	ensure,
+	ensure, log,
 	traits::{fungible::Mutate, EnsureOrigin, Get},
-	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	weights::Weight,
+	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebug, RuntimeDebugNoBound,
 };
 
 use scale_info::TypeInfo;
-use sp_core::{H160, H256};
-use sp_runtime::traits::{Hash, Zero};
+use sp_core::{ConstU32, H160, H256};
+use sp_io::offchain_index;
+use sp_runtime::{
+	traits::{Hash, One, Saturating, Zero},
+	FixedPointNumber, FixedU128,
+};
 
-use sp_std::prelude::*;
+use sp_std::{convert::TryFrom, prelude::*};
 
-use snowbridge_core::{types::AuxiliaryDigestItem, ChannelId};
+use snowbridge_core::{
+	dispatched::MessageDispatched,
+	merkle::{merkle_proof, merkle_root, MerkleProof},
+	types::AuxiliaryDigestItem,
+	BridgeComponent, ChannelId, CircuitBreaker, CommitmentVersion, EthereumTime, MessageOutcome,
+	OutboundChannelState, QueuedMessage, Verifier,
+};
 
 pub use weights::WeightInfo;
 
+/// Weight of verifying and recording a report of the dispatch of a message.
+const REPORT_DISPATCH_WEIGHT: Weight = 100_000_000;
+
 /// Wire-format for committed messages
 #[derive(
 	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
@@ -32,13 +46,15 @@ pub use weights::WeightInfo;
 #[scale_info(skip_type_params(M, N))]
 #[codec(mel_bound())]
 pub struct MessageBundle<M: Get<u32>, N: Get<u32>> {
-	source_channel_id: u8,
+	/// Version of the wire format in which the messages are committed
+	pub version: CommitmentVersion,
+	pub source_channel_id: u8,
 	/// Unique nonce for to prevent replaying bundles
 	#[codec(compact)]
-	nonce: u64,
+	pub nonce: u64,
 	#[codec(compact)]
-	fee: u128,
-	messages: BoundedVec<Message<M>, N>,
+	pub fee: u128,
+	pub messages: BoundedVec<Message<M>, N>,
 }
 
 #[derive(
@@ -49,16 +65,187 @@ pub struct MessageBundle<M: Get<u32>, N: Get<u32>> {
 pub struct Message<M: Get<u32>> {
 	/// Unique message ID
 	#[codec(compact)]
-	id: u64,
+	pub id: u64,
 	/// Target application on the Ethereum side.
-	target: H160,
+	pub target: H160,
+	/// Fee paid for the message.
+	#[codec(compact)]
+	pub fee: u128,
+	/// Ethereum timestamp after which the message can no longer be delivered, if any.
+	pub expires_at: Option<u64>,
 	/// Payload for target application.
+	pub payload: BoundedVec<u8, M>,
+}
+
+/// Wire-format for messages committed in [`CommitmentVersion::V0`].
+///
+/// The layout predates commitment versions, so that relayers which do not know about versions
+/// can still decode V0 commitments.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(M, N))]
+#[codec(mel_bound())]
+pub struct LegacyMessageBundle<M: Get<u32>, N: Get<u32>> {
+	pub source_channel_id: u8,
+	/// Unique nonce for to prevent replaying bundles
+	#[codec(compact)]
+	pub nonce: u64,
+	#[codec(compact)]
+	pub fee: u128,
+	pub messages: BoundedVec<LegacyMessage<M>, N>,
+}
+
+impl<M: Get<u32>, N: Get<u32>> From<LegacyMessageBundle<M, N>> for MessageBundle<M, N> {
+	/// Fees of individual messages are not part of the legacy layout, so they are zero.
+	fn from(bundle: LegacyMessageBundle<M, N>) -> Self {
+		let messages: Vec<_> = bundle
+			.messages
+			.into_iter()
+			.map(|message| Message {
+				id: message.id,
+				target: message.target,
+				fee: 0,
+				expires_at: None,
+				payload: message.payload,
+			})
+			.collect();
+		MessageBundle {
+			version: CommitmentVersion::V0,
+			source_channel_id: bundle.source_channel_id,
+			nonce: bundle.nonce,
+			fee: bundle.fee,
+			messages: messages.try_into().expect("the number of messages is unchanged; qed"),
+		}
+	}
+}
+
+impl<M: Get<u32>, N: Get<u32>> MessageBundle<M, N> {
+	/// The bundle in the wire-format of [`CommitmentVersion::V0`], which does not include the
+	/// version, or the fee and expiry of individual messages.
+	pub fn to_legacy(&self) -> LegacyMessageBundle<M, N> {
+		let messages: Vec<_> = self
+			.messages
+			.iter()
+			.map(|message| LegacyMessage {
+				id: message.id,
+				target: message.target,
+				payload: message.payload.clone(),
+			})
+			.collect();
+		LegacyMessageBundle {
+			source_channel_id: self.source_channel_id,
+			nonce: self.nonce,
+			fee: self.fee,
+			messages: messages.try_into().expect("the number of messages is unchanged; qed"),
+		}
+	}
+
+	/// ABI-encoded messages, which form the leaves of the Merkle tree in the commitment.
+	pub fn leaves(&self) -> Vec<Vec<u8>> {
+		self.messages
+			.iter()
+			.map(|message| {
+				let mut fields = match self.version {
+					CommitmentVersion::V0 => vec![],
+					version => vec![Token::Uint((version as u8).into())],
+				};
+				fields.extend([Token::Uint(message.id.into()), Token::Address(message.target)]);
+				if self.version.encodes_expiry() {
+					fields.push(Token::Uint(message.expires_at.unwrap_or(0).into()));
+				}
+				fields.push(Token::Bytes(message.payload.to_vec()));
+				ethabi::encode(&[Token::Tuple(fields)])
+			})
+			.collect()
+	}
+
+	/// Hash of the ABI-encoded source channel, nonce and fee of the bundle, followed by the root of
+	/// the Merkle tree over its messages.
+	pub fn commitment_hash<H: Hash<Output = H256>>(&self) -> H256 {
+		let root = merkle_root::<H, _>(self.leaves());
+		H::hash(&ethabi::encode(&[
+			Token::Uint(self.source_channel_id.into()),
+			Token::Uint(self.nonce.into()),
+			Token::Uint(self.fee.into()),
+			Token::FixedBytes(root.as_bytes().to_vec()),
+		]))
+	}
+}
+
+/// Message format before commitments were versioned, which is also how messages were queued
+/// before fees were recorded per message.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(M))]
+#[codec(mel_bound())]
+pub struct LegacyMessage<M: Get<u32>> {
+	/// Unique message ID
+	#[codec(compact)]
+	pub id: u64,
+	/// Target application on the Ethereum side.
+	pub target: H160,
+	/// Payload for target application.
+	pub payload: BoundedVec<u8, M>,
+}
+
+type LegacyMessageOf<T> = LegacyMessage<<T as Config>::MaxMessagePayloadSize>;
+
+/// Queued message format before messages could expire.
+#[derive(Encode, Decode)]
+struct MessageWithoutExpiry<M: Get<u32>> {
+	#[codec(compact)]
+	id: u64,
+	target: H160,
+	#[codec(compact)]
+	fee: u128,
 	payload: BoundedVec<u8, M>,
 }
 
+type MessageWithoutExpiryOf<T> = MessageWithoutExpiry<<T as Config>::MaxMessagePayloadSize>;
+
+/// Ethereum gas needed to deliver a message.
+#[derive(
+	Encode, Decode, Copy, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct GasConfig {
+	/// Gas used by every message, regardless of its size.
+	pub base_gas: u64,
+	/// Gas used per byte of message payload.
+	pub gas_per_byte: u64,
+}
+
+/// Market prices used to convert delivery gas into fees.
+#[derive(
+	Encode, Decode, Copy, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct FeeInputs {
+	/// Ethereum gas price in wei.
+	pub gas_price: u128,
+	/// Amount of fee currency per wei.
+	pub exchange_rate: FixedU128,
+}
+
+/// Prefix for the offchain indexing keys of committed bundles.
+///
+/// Bundles are indexed in the layout of [`MessageBundle`] whatever their version, so that they
+/// can be decoded without knowing it.
+pub const INDEXING_PREFIX: &[u8] = b"incentivized_outbound_channel::commitment";
+
+/// Offchain indexing key under which the bundle for `commitment_hash` is stored.
+pub fn offchain_key(commitment_hash: H256) -> Vec<u8> {
+	(INDEXING_PREFIX, commitment_hash).encode()
+}
+
+/// Max number of versions in which a bundle is committed at once
+pub type MaxCommitmentVersions = ConstU32<2>;
+
 pub type MessageBundleOf<T> =
 	MessageBundle<<T as Config>::MaxMessagePayloadSize, <T as Config>::MaxMessagesPerCommit>;
 pub type MessageOf<T> = Message<<T as Config>::MaxMessagePayloadSize>;
+pub type LegacyMessageBundleOf<T> =
+	LegacyMessageBundle<<T as Config>::MaxMessagePayloadSize, <T as Config>::MaxMessagesPerCommit>;
 
 pub use pallet::*;
 
@@ -70,8 +257,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -88,11 +278,31 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxMessagesPerCommit: Get<u32>;
 
+		/// Max number of messages waiting to be committed, including those carried over from
+		/// previous intervals
+		#[pallet::constant]
+		type MaxQueuedMessages: Get<u32>;
+
 		type FeeCurrency: Mutate<<Self as frame_system::Config>::AccountId, Balance = u128>;
 
 		/// The origin which may update reward related params
 		type SetFeeOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which may update gas prices and exchange rates
+		type FeeFeederOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may change the versions in which commitments are emitted
+		type SetCommitmentVersionsOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Verifier module for reports of the dispatch of messages on Ethereum.
+		type Verifier: Verifier;
+
+		/// Time on Ethereum, against which the expiry of messages is checked
+		type EthereumTime: EthereumTime;
+
+		/// Emergency pause for the bridge
+		type CircuitBreaker: CircuitBreaker;
+
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -101,19 +311,45 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		MessageAccepted(u64),
-		Committed { hash: H256, data: MessageBundleOf<T> },
+		/// Messages were committed in [`CommitmentVersion::V0`].
+		Committed {
+			hash: H256,
+			data: LegacyMessageBundleOf<T>,
+		},
+		/// Commitments are now emitted in these versions.
+		CommitmentVersionsSet(Vec<CommitmentVersion>),
+		/// The outcome of a message with an expiry is known, either because it expired before
+		/// it was committed, or from a report of its dispatch on Ethereum.
+		OutcomeRecorded(u64, MessageOutcome),
+		/// Messages were committed in a version after [`CommitmentVersion::V0`].
+		VersionedCommitted {
+			hash: H256,
+			data: MessageBundleOf<T>,
+		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The message payload exceeds byte limit.
 		PayloadTooLarge,
-		/// No more messages can be queued for the channel during this commit cycle.
+		/// No more messages can be queued for the channel until the backlog is committed.
 		QueueSizeLimitReached,
 		/// Cannot pay the fee to submit a message.
 		NoFunds,
 		/// Cannot increment nonce
 		Overflow,
+		/// At least one, and at most [`MaxCommitmentVersions`], versions must be given.
+		InvalidCommitmentVersions,
+		/// Messages with an expiry require every commitment version to encode it.
+		ExpiryNotSupported,
+		/// The message has already expired.
+		MessageExpired,
+		/// Report came from an invalid inbound channel on the Ethereum side.
+		InvalidTargetChannel,
+		/// Report is not a `MessageDispatched` log.
+		InvalidDispatchLog,
+		/// No outcome is awaited for the message.
+		UnknownMessage,
 	}
 
 	/// Interval between commitments
@@ -124,29 +360,86 @@ pub mod pallet {
 	/// Messages waiting to be committed.
 	#[pallet::storage]
 	pub(super) type MessageQueue<T: Config> =
-		StorageValue<_, BoundedVec<MessageOf<T>, T::MaxMessagesPerCommit>, ValueQuery>;
+		StorageValue<_, BoundedVec<MessageOf<T>, T::MaxQueuedMessages>, ValueQuery>;
 
-	/// Fee for accepting a message
+	/// Base fee for accepting a message
 	#[pallet::storage]
 	#[pallet::getter(fn fee)]
 	pub type Fee<T: Config> = StorageValue<_, u128, ValueQuery>;
 
+	/// Gas needed to deliver a message on Ethereum
+	#[pallet::storage]
+	#[pallet::getter(fn gas_config)]
+	pub type DeliveryGas<T: Config> = StorageValue<_, GasConfig, ValueQuery>;
+
+	/// Latest gas price and exchange rate from the fee feeder
+	#[pallet::storage]
+	#[pallet::getter(fn fee_inputs)]
+	pub type CurrentFeeInputs<T: Config> = StorageValue<_, FeeInputs, ValueQuery>;
+
 	#[pallet::storage]
 	pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
 
 	#[pallet::storage]
 	pub type NextId<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	#[pallet::type_value]
+	pub(super) fn DefaultCommitmentVersions<T: Config>(
+	) -> BoundedVec<CommitmentVersion, MaxCommitmentVersions> {
+		vec![CommitmentVersion::V0]
+			.try_into()
+			.expect("a single version is within bounds; qed")
+	}
+
+	/// Versions in which each bundle is committed, in ascending order.
+	///
+	/// More than one version is only set while the peer contracts on Ethereum are upgraded from
+	/// the oldest to the newest.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment_versions)]
+	pub type CommitmentVersions<T: Config> = StorageValue<
+		_,
+		BoundedVec<CommitmentVersion, MaxCommitmentVersions>,
+		ValueQuery,
+		DefaultCommitmentVersions<T>,
+	>;
+
+	/// Hash of the most recent commitment in each version it was committed in
+	#[pallet::storage]
+	pub type LatestCommitmentHashes<T: Config> =
+		StorageValue<_, BoundedVec<(CommitmentVersion, H256), MaxCommitmentVersions>, ValueQuery>;
+
+	/// Inbound channel on the ethereum side, which reports the dispatch of messages
+	#[pallet::storage]
+	#[pallet::getter(fn target_channel)]
+	pub type TargetChannel<T: Config> = StorageValue<_, H160, ValueQuery>;
+
+	/// Expiry of committed messages whose outcome is not yet known, by message ID
+	#[pallet::storage]
+	pub type PendingOutcomes<T: Config> = StorageMap<_, Twox64Concat, u64, u64, OptionQuery>;
+
+	/// Known outcomes of messages with an expiry, until they are taken by the sending app
+	#[pallet::storage]
+	#[pallet::getter(fn outcome)]
+	pub type Outcomes<T: Config> = StorageMap<_, Twox64Concat, u64, MessageOutcome, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub interval: T::BlockNumber,
 		pub fee: u128,
+		pub gas_config: GasConfig,
+		pub target_channel: H160,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { interval: Default::default(), fee: Default::default() }
+			Self {
+				interval: Default::default(),
+				fee: Default::default(),
+				gas_config: Default::default(),
+				target_channel: Default::default(),
+			}
 		}
 	}
 
@@ -155,6 +448,8 @@ pub mod pallet {
 		fn build(&self) {
 			<Interval<T>>::put(self.interval);
 			<Fee<T>>::put(self.fee);
+			<DeliveryGas<T>>::put(self.gas_config);
+			<TargetChannel<T>>::put(self.target_channel);
 		}
 	}
 
@@ -162,8 +457,10 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		// Generate a message commitment every [`Interval`] blocks.
 		//
-		// The commitment hash is included in an [`AuxiliaryDigestItem`] in the block header,
-		// with the corresponding commitment is persisted offchain.
+		// The commitment hash binds the source channel, nonce and fee of the bundle to the root of
+		// a binary Merkle tree over the ABI-encoded messages.
+		// It is included in an [`AuxiliaryDigestItem`] in the block header, and the encoded
+		// bundle is written to offchain indexing storage under [`offchain_key`].
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			if (now % Self::interval()).is_zero() {
 				Self::commit()
@@ -171,6 +468,36 @@ pub mod pallet {
 				T::WeightInfo::on_initialize_non_interval()
 			}
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let version = StorageVersion::get::<Pallet<T>>();
+			if version == 0 {
+				// Messages queued before the upgrade were charged the flat fee, and do not expire.
+				let fee = Self::fee();
+				Self::migrate_queue(|message: LegacyMessageOf<T>| Message {
+					id: message.id,
+					target: message.target,
+					fee,
+					expires_at: None,
+					payload: message.payload,
+				});
+				STORAGE_VERSION.put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(3, 2)
+			} else if version == 1 {
+				// Messages queued before the upgrade do not expire.
+				Self::migrate_queue(|message: MessageWithoutExpiryOf<T>| Message {
+					id: message.id,
+					target: message.target,
+					fee: message.fee,
+					expires_at: None,
+					payload: message.payload,
+				});
+				STORAGE_VERSION.put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(2, 2)
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
 	}
 
 	#[pallet::call]
@@ -181,20 +508,142 @@ pub mod pallet {
 			<Fee<T>>::put(amount);
 			Ok(())
 		}
+
+		#[pallet::weight(T::WeightInfo::set_gas_config())]
+		pub fn set_gas_config(origin: OriginFor<T>, config: GasConfig) -> DispatchResult {
+			T::SetFeeOrigin::ensure_origin(origin)?;
+			<DeliveryGas<T>>::put(config);
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_fee_inputs())]
+		pub fn set_fee_inputs(origin: OriginFor<T>, inputs: FeeInputs) -> DispatchResult {
+			T::FeeFeederOrigin::ensure_origin(origin)?;
+			<CurrentFeeInputs<T>>::put(inputs);
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_commitment_versions())]
+		pub fn set_commitment_versions(
+			origin: OriginFor<T>,
+			mut versions: Vec<CommitmentVersion>,
+		) -> DispatchResult {
+			T::SetCommitmentVersionsOrigin::ensure_origin(origin)?;
+			versions.sort();
+			versions.dedup();
+			let bounded = BoundedVec::<_, MaxCommitmentVersions>::try_from(versions.clone())
+				.map_err(|_| Error::<T>::InvalidCommitmentVersions)?;
+			ensure!(!bounded.is_empty(), Error::<T>::InvalidCommitmentVersions);
+			// Queued messages must not be committed without their expiry.
+			ensure!(
+				bounded.iter().all(|version| version.encodes_expiry()) ||
+					<MessageQueue<T>>::get().iter().all(|message| message.expires_at.is_none()),
+				Error::<T>::ExpiryNotSupported,
+			);
+			<CommitmentVersions<T>>::put(bounded);
+			Self::deposit_event(Event::CommitmentVersionsSet(versions));
+			Ok(())
+		}
+
+		/// Record the outcome of a message with an expiry, from a `MessageDispatched` log of the
+		/// inbound channel on Ethereum.
+		#[pallet::weight(REPORT_DISPATCH_WEIGHT)]
+		pub fn report_dispatch(
+			origin: OriginFor<T>,
+			message: snowbridge_core::Message,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let (log, _) = T::Verifier::verify(&message)?;
+			let dispatched =
+				MessageDispatched::try_from(log).map_err(|_| Error::<T>::InvalidDispatchLog)?;
+			ensure!(
+				dispatched.channel == <TargetChannel<T>>::get(),
+				Error::<T>::InvalidTargetChannel,
+			);
+			<PendingOutcomes<T>>::take(dispatched.id).ok_or(Error::<T>::UnknownMessage)?;
+
+			let outcome = if dispatched.result {
+				MessageOutcome::Delivered
+			} else {
+				MessageOutcome::Undelivered
+			};
+			Self::record_outcome(dispatched.id, outcome);
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
-		/// Submit message on the outbound channel
-		pub fn submit(who: &T::AccountId, target: H160, payload: &[u8]) -> DispatchResult {
+		/// Convert each message in the queue from an older layout with `f`.
+		///
+		/// Messages over the bound of the queue are dropped, as is the whole queue if it cannot
+		/// be decoded. Dropped messages are logged, as their fees are not refunded.
+		fn migrate_queue<O: Decode>(f: impl Fn(O) -> MessageOf<T>) {
+			let mut dropped = Vec::new();
+			let translated = <MessageQueue<T>>::translate::<Vec<O>, _>(|queue| {
+				let mut messages: Vec<_> = queue?.into_iter().map(&f).collect();
+				let bound = T::MaxQueuedMessages::get() as usize;
+				dropped = messages.split_off(bound.min(messages.len()));
+				Some(
+					BoundedVec::try_from(messages)
+						.expect("messages are truncated to the bound of the queue; qed"),
+				)
+			});
+
+			match translated {
+				Ok(queue) => log::info!(
+					target: "incentivized-outbound-channel",
+					"Migrated {} queued messages",
+					queue.map_or(0, |queue| queue.len())
+				),
+				Err(_) => {
+					<MessageQueue<T>>::kill();
+					log::error!(
+						target: "incentivized-outbound-channel",
+						"Dropped the message queue, which could not be decoded"
+					);
+				},
+			}
+			if !dropped.is_empty() {
+				log::error!(
+					target: "incentivized-outbound-channel",
+					"Dropped queued messages over the bound of the queue: {:?}",
+					dropped.iter().map(|message| message.id).collect::<Vec<_>>()
+				);
+			}
+		}
+
+		/// Submit message on the outbound channel, returning its ID.
+		///
+		/// A message with an `expires_at` timestamp is dropped if it expires before it is
+		/// committed, and is not dispatched on Ethereum after that time.
+		pub fn submit(
+			who: &T::AccountId,
+			target: H160,
+			payload: &[u8],
+			expires_at: Option<u64>,
+		) -> Result<u64, DispatchError> {
+			T::CircuitBreaker::ensure_active(BridgeComponent::OutboundChannel(
+				ChannelId::INCENTIVIZED,
+			))?;
 			ensure!(
-				<MessageQueue<T>>::decode_len().unwrap_or(0)
-					< T::MaxMessagesPerCommit::get() as usize,
+				Self::backlog_depth() < T::MaxQueuedMessages::get(),
 				Error::<T>::QueueSizeLimitReached,
 			);
 			ensure!(
 				payload.len() <= T::MaxMessagePayloadSize::get() as usize,
 				Error::<T>::PayloadTooLarge,
 			);
+			if expires_at.is_some() {
+				ensure!(
+					Self::commitment_versions().iter().all(|version| version.encodes_expiry()),
+					Error::<T>::ExpiryNotSupported,
+				);
+				ensure!(
+					!Self::is_expired(expires_at, T::EthereumTime::finalized_timestamp()),
+					Error::<T>::MessageExpired,
+				);
+			}
 
 			let next_id = <NextId<T>>::get();
 			if next_id.checked_add(1).is_none() {
@@ -202,11 +651,14 @@ pub mod pallet {
 			}
 
 			// Attempt to charge a fee for message submission
-			T::FeeCurrency::burn_from(who, Self::fee()).map_err(|_| Error::<T>::NoFunds)?;
+			let fee = Self::message_fee(payload.len());
+			T::FeeCurrency::burn_from(who, fee).map_err(|_| Error::<T>::NoFunds)?;
 
 			<MessageQueue<T>>::try_append(Message {
 				id: next_id,
 				target,
+				fee,
+				expires_at,
 				payload: payload.to_vec().try_into().map_err(|_| Error::<T>::PayloadTooLarge)?,
 			})
 			.map_err(|_| Error::<T>::QueueSizeLimitReached)?;
@@ -215,58 +667,187 @@ pub mod pallet {
 
 			Self::deposit_event(Event::MessageAccepted(next_id));
 
-			Ok(())
+			Ok(next_id)
+		}
+
+		/// Take the outcome of a message with an expiry, once it is known.
+		///
+		/// A committed message is only known to be undelivered from a report of the inbound
+		/// channel on Ethereum, which does not dispatch expired messages. Until then it may have
+		/// been delivered, however long ago it expired.
+		pub fn take_outcome(id: u64) -> Option<MessageOutcome> {
+			<Outcomes<T>>::take(id)
+		}
+
+		/// Number of messages waiting to be committed.
+		pub fn backlog_depth() -> u32 {
+			<MessageQueue<T>>::decode_len().unwrap_or(0) as u32
+		}
+
+		/// Fee for submitting a message with a payload of `payload_size` bytes.
+		///
+		/// The base fee plus the cost of delivery gas at current prices, scaled by
+		/// [`Self::congestion_multiplier`].
+		pub fn message_fee(payload_size: usize) -> u128 {
+			let gas = Self::gas_config();
+			let inputs = Self::fee_inputs();
+			let gas_used = u128::from(gas.base_gas)
+				.saturating_add(u128::from(gas.gas_per_byte).saturating_mul(payload_size as u128));
+			let delivery_cost = inputs
+				.exchange_rate
+				.saturating_mul_int(gas_used.saturating_mul(inputs.gas_price));
+			Self::congestion_multiplier()
+				.saturating_mul_int(Self::fee().saturating_add(delivery_cost))
+		}
+
+		/// Multiplier which grows from 1 for an empty backlog to 2 for a full one.
+		pub fn congestion_multiplier() -> FixedU128 {
+			let max_queued = T::MaxQueuedMessages::get();
+			FixedU128::saturating_from_rational(
+				max_queued.saturating_add(Self::backlog_depth()),
+				max_queued,
+			)
 		}
 
 		fn commit() -> Weight {
-			let messages = <MessageQueue<T>>::take();
-			if messages.is_empty() {
+			let mut queue = <MessageQueue<T>>::take().into_inner();
+			if queue.is_empty() {
 				return T::WeightInfo::on_initialize_no_messages();
 			}
 
+			// Messages which expired while queued are dropped, as they cannot be delivered.
+			let now = T::EthereumTime::finalized_timestamp();
+			let queued = queue.len();
+			queue.retain(|message| {
+				if Self::is_expired(message.expires_at, now) {
+					Self::record_outcome(message.id, MessageOutcome::Undelivered);
+					false
+				} else {
+					true
+				}
+			});
+			let expired_weight = T::DbWeight::get().writes((queued - queue.len()) as Weight);
+			if queue.is_empty() {
+				return T::WeightInfo::on_initialize_no_messages().saturating_add(expired_weight);
+			}
+
+			// Messages which do not fit in this bundle are carried over to the next interval.
+			let backlog = queue.split_off(queue.len().min(T::MaxMessagesPerCommit::get() as usize));
+			if !backlog.is_empty() {
+				<MessageQueue<T>>::put(
+					BoundedVec::<_, T::MaxQueuedMessages>::try_from(backlog)
+						.expect("backlog is a subset of the bounded queue; qed"),
+				);
+			}
+			let messages = BoundedVec::<_, T::MaxMessagesPerCommit>::try_from(queue)
+				.expect("bundle is truncated to MaxMessagesPerCommit; qed");
+
+			// The outcome of committed messages with an expiry is awaited from Ethereum.
+			let mut expiring = 0;
+			for message in messages.iter() {
+				if let Some(expires_at) = message.expires_at {
+					<PendingOutcomes<T>>::insert(message.id, expires_at);
+					expiring += 1;
+				}
+			}
+
 			let nonce = <Nonce<T>>::get();
 			let next_nonce = nonce.saturating_add(1);
 			<Nonce<T>>::put(next_nonce);
 
-			let bundle: MessageBundleOf<T> = MessageBundle {
-				source_channel_id: ChannelId::Incentivized as u8,
-				nonce: next_nonce,
-				fee: (messages.len() as u128).saturating_mul(Self::fee()),
-				messages: messages.clone(),
-			};
-
-			let commitment_hash = Self::make_commitment_hash(&bundle);
-			let digest_item =
-				AuxiliaryDigestItem::Commitment(ChannelId::Incentivized, commitment_hash.clone())
-					.into();
-			<frame_system::Pallet<T>>::deposit_log(digest_item);
-			Self::deposit_event(Event::Committed { hash: commitment_hash, data: bundle });
+			let fee = messages.iter().fold(0u128, |acc, message| acc.saturating_add(message.fee));
+
+			// The same messages are committed once in each version, so that peer contracts on
+			// either side of an upgrade can verify them.
+			let versions = <CommitmentVersions<T>>::get();
+			let mut hashes = BoundedVec::<_, MaxCommitmentVersions>::default();
+			for version in versions.iter().copied() {
+				let bundle: MessageBundleOf<T> = MessageBundle {
+					version,
+					source_channel_id: ChannelId::INCENTIVIZED.0,
+					nonce: next_nonce,
+					fee,
+					messages: messages.clone(),
+				};
+
+				let commitment_hash = Self::make_commitment_hash(&bundle);
+				let digest_item = AuxiliaryDigestItem::commitment(
+					ChannelId::INCENTIVIZED,
+					version,
+					commitment_hash,
+				)
+				.into();
+				<frame_system::Pallet<T>>::deposit_log(digest_item);
+				hashes
+					.try_push((version, commitment_hash))
+					.expect("one hash per commitment version is within bounds; qed");
+				offchain_index::set(&offchain_key(commitment_hash), &bundle.encode());
+				let event = match version {
+					CommitmentVersion::V0 =>
+						Event::Committed { hash: commitment_hash, data: bundle.to_legacy() },
+					_ => Event::VersionedCommitted { hash: commitment_hash, data: bundle },
+				};
+				Self::deposit_event(event);
+			}
+			<LatestCommitmentHashes<T>>::put(hashes);
 
 			T::WeightInfo::on_initialize(
 				messages.len() as u32,
 				Self::average_payload_size(&messages),
 			)
+			.saturating_mul(versions.len() as Weight)
+			.saturating_add(expired_weight)
+			.saturating_add(T::DbWeight::get().writes(expiring))
 		}
 
-		fn make_commitment_hash(bundle: &MessageBundleOf<T>) -> H256 {
-			let messages: Vec<Token> = bundle
-				.messages
-				.iter()
-				.map(|message| {
-					Token::Tuple(vec![
-						Token::Uint(message.id.into()),
-						Token::Address(message.target),
-						Token::Bytes(message.payload.to_vec()),
-					])
+		/// Whether a message expiring at `expires_at` can no longer be delivered on Ethereum,
+		/// where `now` is the timestamp of the latest finalized Ethereum block.
+		fn is_expired(expires_at: Option<u64>, now: Option<u64>) -> bool {
+			match (expires_at, now) {
+				(Some(expires_at), Some(now)) => now > expires_at,
+				_ => false,
+			}
+		}
+
+		fn record_outcome(id: u64, outcome: MessageOutcome) {
+			<Outcomes<T>>::insert(id, outcome);
+			Self::deposit_event(Event::OutcomeRecorded(id, outcome));
+		}
+
+		/// Messages waiting to be committed, in the order they will be committed.
+		pub fn queued_messages() -> Vec<QueuedMessage> {
+			<MessageQueue<T>>::get()
+				.into_iter()
+				.map(|message| QueuedMessage {
+					id: message.id,
+					target: message.target,
+					fee: message.fee,
+					expires_at: message.expires_at,
+					payload: message.payload.into_inner(),
 				})
-				.collect();
-			let commitment = ethabi::encode(&vec![Token::Tuple(vec![
-				Token::Uint(bundle.source_channel_id.into()),
-				Token::Uint(bundle.nonce.into()),
-				Token::Uint(bundle.fee.into()),
-				Token::Array(messages),
-			])]);
-			<T as Config>::Hashing::hash(&commitment)
+				.collect()
+		}
+
+		/// Commitment state of the channel as of the current block.
+		pub fn channel_state() -> OutboundChannelState<T::BlockNumber> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let interval = Self::interval().max(One::one());
+			OutboundChannelState {
+				next_commitment_block: now.saturating_sub(now % interval).saturating_add(interval),
+				nonce: <Nonce<T>>::get(),
+				latest_commitment_hashes: <LatestCommitmentHashes<T>>::get().into_inner(),
+				next_message_id: <NextId<T>>::get(),
+			}
+		}
+
+		fn make_commitment_hash(bundle: &MessageBundleOf<T>) -> H256 {
+			bundle.commitment_hash::<<T as Config>::Hashing>()
+		}
+
+		/// Construct a proof that the message at `index` is part of the Merkle tree committed to
+		/// for `bundle`.
+		pub fn make_message_proof(bundle: &MessageBundleOf<T>, index: u64) -> Option<MerkleProof> {
+			merkle_proof::<<T as Config>::Hashing, _>(bundle.leaves(), index)
 		}
 
 		fn average_payload_size(messages: &[MessageOf<T>]) -> u32 {
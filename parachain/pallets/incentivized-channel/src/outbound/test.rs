@@ -1,15 +1,20 @@
 use super::*;
 
+use codec::{Compact, Decode, Encode};
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::DispatchError,
 	parameter_types,
+	storage::unhashed,
 	traits::{
 		tokens::fungible::{Inspect, ItemOf, Mutate},
-		Everything, GenesisBuild, OnInitialize
+		Everything, GenesisBuild, OnInitialize, OnRuntimeUpgrade, StorageVersion,
 	},
 	PalletId,
 };
+use hex_literal::hex;
+use snowbridge_core::{Proof, ReceiptProof, VerificationInfo};
+use snowbridge_ethereum::Log;
 use sp_core::{H160, H256};
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::{
@@ -17,7 +22,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdConversion, BlakeTwo256, IdentifyAccount, IdentityLookup, Keccak256, Verify,
 	},
-	MultiSignature,
+	DigestItem, MultiSignature,
 };
 use sp_std::convert::From;
 
@@ -118,6 +123,34 @@ impl pallet_assets::Config for Test {
 parameter_types! {
 	pub const MaxMessagePayloadSize: u32 = 128;
 	pub const MaxMessagesPerCommit: u32 = 5;
+	pub const MaxQueuedMessages: u32 = 10;
+	pub static EthereumTimestamp: Option<u64> = None;
+}
+
+// Mock verifier, which accepts any SCALE-encoded log
+pub struct MockVerifier;
+
+impl Verifier for MockVerifier {
+	fn verify(
+		message: &snowbridge_core::Message,
+	) -> Result<(Log, VerificationInfo), DispatchError> {
+		let log = Log::decode(&mut message.data.as_slice()).unwrap();
+		let info = VerificationInfo {
+			block_number: 0,
+			block_hash: message.proof.receipt_proof().block_hash,
+			timestamp: 0,
+			log_index: 0,
+		};
+		Ok((log, info))
+	}
+}
+
+pub struct MockEthereumTime;
+
+impl EthereumTime for MockEthereumTime {
+	fn finalized_timestamp() -> Option<u64> {
+		EthereumTimestamp::get()
+	}
 }
 
 parameter_types! {
@@ -132,16 +165,29 @@ impl incentivized_outbound_channel::Config for Test {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type FeeCurrency = Ether;
 	type SetFeeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type SetCommitmentVersionsOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type FeeFeederOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type Verifier = MockVerifier;
+	type EthereumTime = MockEthereumTime;
+	type CircuitBreaker = ();
 	type WeightInfo = ();
 }
 
+const TARGET_CHANNEL: [u8; 20] = [9; 20];
+
 pub fn new_tester() -> sp_io::TestExternalities {
 	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	let config: incentivized_outbound_channel::GenesisConfig<Test> =
-		incentivized_outbound_channel::GenesisConfig { interval: 1u64, fee: 100 };
+		incentivized_outbound_channel::GenesisConfig {
+			interval: 1u64,
+			fee: 100,
+			gas_config: GasConfig { base_gas: 1000, gas_per_byte: 10 },
+			target_channel: TARGET_CHANNEL.into(),
+		};
 	config.assimilate_storage(&mut storage).unwrap();
 
 	let assets_config: pallet_assets::GenesisConfig<Test> = pallet_assets::GenesisConfig {
@@ -173,7 +219,7 @@ fn test_submit() {
 		// Deposit enough money to cover fees
 		Ether::mint_into(&who, 300).unwrap();
 
-		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
 		assert_eq!(<NextId<Test>>::get(), 1);
 		assert_eq!(<Nonce<Test>>::get(), 0);
 
@@ -191,7 +237,7 @@ fn test_submit_fees_burned() {
 		// Deposit enough money to cover fees
 		Ether::mint_into(&who, 300).unwrap();
 
-		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2]));
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
 
 		assert_eq!(Ether::balance(&who), 200);
 	})
@@ -206,7 +252,7 @@ fn test_submit_not_enough_funds() {
 		Ether::mint_into(&who, 50).unwrap();
 
 		assert_noop!(
-			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2]),
+			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None),
 			Error::<Test>::NoFunds
 		);
 	})
@@ -219,20 +265,45 @@ fn test_submit_exceeds_queue_limit() {
 		let who: AccountId = Keyring::Bob.into();
 
 		// Deposit enough money to cover fees
-		Ether::mint_into(&who, 1000).unwrap();
+		Ether::mint_into(&who, 2000).unwrap();
 
-		let max_messages = MaxMessagesPerCommit::get();
+		let max_messages = MaxQueuedMessages::get();
 		(0..max_messages).for_each(|_| {
-			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2]).unwrap()
+			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None).unwrap();
 		});
 
 		assert_noop!(
-			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2]),
+			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None),
 			Error::<Test>::QueueSizeLimitReached,
 		);
 	})
 }
 
+#[test]
+fn test_commit_carries_over_backlog() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		// Deposit enough money to cover fees
+		Ether::mint_into(&who, 2000).unwrap();
+
+		let max_messages = MaxMessagesPerCommit::get();
+		(0..max_messages + 2).for_each(|_| {
+			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None).unwrap();
+		});
+		assert_eq!(IncentivizedOutboundChannel::backlog_depth(), max_messages + 2);
+
+		run_to_block(2);
+		assert_eq!(<Nonce<Test>>::get(), 1);
+		assert_eq!(IncentivizedOutboundChannel::backlog_depth(), 2);
+
+		run_to_block(3);
+		assert_eq!(<Nonce<Test>>::get(), 2);
+		assert_eq!(IncentivizedOutboundChannel::backlog_depth(), 0);
+	})
+}
+
 #[test]
 fn test_set_fee_not_authorized() {
 	new_tester().execute_with(|| {
@@ -244,6 +315,65 @@ fn test_set_fee_not_authorized() {
 	});
 }
 
+#[test]
+fn test_set_fee_inputs_not_authorized() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		assert_noop!(
+			IncentivizedOutboundChannel::set_fee_inputs(
+				Origin::signed(bob),
+				FeeInputs { gas_price: 1, exchange_rate: FixedU128::one() }
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn test_submit_fee_includes_delivery_gas() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		Ether::mint_into(&who, 10000).unwrap();
+
+		assert_ok!(IncentivizedOutboundChannel::set_fee_inputs(
+			Origin::root(),
+			FeeInputs { gas_price: 2, exchange_rate: FixedU128::saturating_from_rational(1, 2) }
+		));
+
+		// base fee + (1000 base gas + 10 gas/byte * 3 bytes) * 2 wei/gas * 0.5
+		assert_eq!(IncentivizedOutboundChannel::message_fee(3), 1130);
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
+		assert_eq!(Ether::balance(&who), 10000 - 1130);
+	})
+}
+
+#[test]
+fn test_submit_fee_grows_with_backlog() {
+	new_tester().execute_with(|| {
+		let target = H160::zero();
+		let who: AccountId = Keyring::Bob.into();
+
+		Ether::mint_into(&who, 1000).unwrap();
+
+		assert_eq!(IncentivizedOutboundChannel::congestion_multiplier(), FixedU128::one());
+		(0..5).for_each(|_| {
+			IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None).unwrap();
+		});
+
+		assert_eq!(
+			IncentivizedOutboundChannel::congestion_multiplier(),
+			FixedU128::saturating_from_rational(3, 2)
+		);
+		assert_eq!(IncentivizedOutboundChannel::message_fee(3), 150);
+
+		// Relayers are paid the fees charged for the committed messages
+		run_to_block(2);
+		assert_eq!(committed_bundle().fee, 100 + 110 + 120 + 130 + 140);
+	})
+}
+
 #[test]
 fn test_submit_exceeds_payload_limit() {
 	new_tester().execute_with(|| {
@@ -254,8 +384,479 @@ fn test_submit_exceeds_payload_limit() {
 		let payload: Vec<u8> = (0..).take(max_payload_bytes as usize + 1).collect();
 
 		assert_noop!(
-			IncentivizedOutboundChannel::submit(&who, target, payload.as_slice()),
+			IncentivizedOutboundChannel::submit(&who, target, payload.as_slice(), None),
 			Error::<Test>::PayloadTooLarge,
 		);
 	})
 }
+
+#[test]
+fn test_commitment_hash_matches_inbound_channel() {
+	let bundle = MessageBundleOf::<Test> {
+		version: CommitmentVersion::V0,
+		source_channel_id: 1,
+		nonce: 3,
+		fee: 20,
+		messages: vec![Message {
+			id: 2,
+			target: H160::repeat_byte(2),
+			fee: 20,
+			expires_at: None,
+			payload: vec![4, 5].try_into().unwrap(),
+		}]
+		.try_into()
+		.unwrap(),
+	};
+
+	// As computed by `IncentivizedInboundChannel.submit` on Ethereum.
+	assert_eq!(
+		bundle.commitment_hash::<Keccak256>(),
+		H256(hex!("8e8db1a4456aa81b6b56126f28986624023f3df6ba9bc28bbcbd2a4f77624a5e")),
+	);
+}
+
+#[test]
+fn test_legacy_bundle_layout() {
+	let bundle = MessageBundleOf::<Test> {
+		version: CommitmentVersion::V0,
+		source_channel_id: 1,
+		nonce: 1,
+		fee: 20,
+		messages: vec![Message {
+			id: 7,
+			target: H160::repeat_byte(1),
+			fee: 0,
+			expires_at: None,
+			payload: vec![1, 2, 3].try_into().unwrap(),
+		}]
+		.try_into()
+		.unwrap(),
+	};
+
+	// V0 bundles keep the layout from before commitments were versioned.
+	let expected = (
+		1u8,
+		Compact(1u64),
+		Compact(20u128),
+		vec![(Compact(7u64), H160::repeat_byte(1), vec![1u8, 2, 3])],
+	)
+		.encode();
+	assert_eq!(bundle.to_legacy().encode(), expected);
+	assert_eq!(MessageBundleOf::<Test>::from(bundle.to_legacy()), bundle);
+}
+
+#[test]
+fn test_commitment_binds_merkle_root_of_messages() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		// Deposit enough money to cover fees
+		Ether::mint_into(&who, 300).unwrap();
+
+		for i in 0..3u8 {
+			assert_ok!(IncentivizedOutboundChannel::submit(
+				&who,
+				H160::repeat_byte(i),
+				&vec![i; 3],
+				None
+			));
+		}
+		run_to_block(2);
+
+		let (hash, bundle) = commitments().into_iter().next().expect("commitment event");
+
+		let root = merkle_root::<Keccak256, _>(bundle.leaves());
+		assert_eq!(hash, bundle.commitment_hash::<Keccak256>());
+		assert_ne!(hash, root);
+
+		// The commitment differs for the same messages in a bundle with another nonce.
+		let mut replayed = bundle.clone();
+		replayed.nonce += 1;
+		assert_ne!(replayed.commitment_hash::<Keccak256>(), hash);
+
+		for index in 0..3 {
+			let proof = IncentivizedOutboundChannel::make_message_proof(&bundle, index).unwrap();
+			assert_eq!(proof.root, root);
+			assert!(snowbridge_core::merkle::verify_proof::<Keccak256>(
+				root,
+				&proof.proof,
+				proof.number_of_leaves,
+				proof.leaf_index,
+				Keccak256::hash(&proof.leaf),
+			));
+		}
+		assert!(IncentivizedOutboundChannel::make_message_proof(&bundle, 3).is_none());
+	});
+}
+
+#[test]
+fn test_channel_state() {
+	new_tester().execute_with(|| {
+		let target = H160::repeat_byte(1);
+		let who: AccountId = Keyring::Bob.into();
+		<Interval<Test>>::put(4);
+
+		// Deposit enough money to cover fees
+		Ether::mint_into(&who, 300).unwrap();
+
+		let fee = IncentivizedOutboundChannel::message_fee(3);
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, target, &vec![0, 1, 2], None));
+
+		assert_eq!(
+			IncentivizedOutboundChannel::queued_messages(),
+			vec![QueuedMessage { id: 0, target, fee, expires_at: None, payload: vec![0, 1, 2] }]
+		);
+		assert_eq!(
+			IncentivizedOutboundChannel::channel_state(),
+			OutboundChannelState {
+				next_commitment_block: 4,
+				nonce: 0,
+				latest_commitment_hashes: vec![],
+				next_message_id: 1,
+			}
+		);
+
+		run_to_block(4);
+		assert!(IncentivizedOutboundChannel::queued_messages().is_empty());
+
+		let state = IncentivizedOutboundChannel::channel_state();
+		assert_eq!(state.next_commitment_block, 8);
+		assert_eq!(state.nonce, 1);
+		assert_eq!(
+			state.latest_commitment_hashes,
+			vec![(CommitmentVersion::V0, commitments()[0].0)]
+		);
+	});
+}
+
+#[test]
+fn test_commit_indexes_bundle_offchain() {
+	let mut ext = new_tester();
+	let (hash, bundle) = ext.execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&who, 100).unwrap();
+
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], None));
+		run_to_block(2);
+
+		commitments().pop().expect("commitment event")
+	});
+	ext.persist_offchain_overlay();
+
+	// Fees of individual messages are only in the indexed bundle
+	let stored = ext.offchain_db().get(&offchain_key(hash)).expect("indexed bundle");
+	let stored = MessageBundleOf::<Test>::decode(&mut stored.as_slice()).unwrap();
+	assert_eq!(stored.to_legacy(), bundle.to_legacy());
+	assert_eq!(stored.messages[0].fee, 100);
+}
+
+#[test]
+fn test_set_commitment_versions() {
+	new_tester().execute_with(|| {
+		let bob: AccountId = Keyring::Bob.into();
+		assert_eq!(
+			IncentivizedOutboundChannel::commitment_versions().into_inner(),
+			vec![CommitmentVersion::V0]
+		);
+
+		assert_noop!(
+			IncentivizedOutboundChannel::set_commitment_versions(
+				Origin::signed(bob),
+				vec![CommitmentVersion::V2]
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			IncentivizedOutboundChannel::set_commitment_versions(Origin::root(), vec![]),
+			Error::<Test>::InvalidCommitmentVersions
+		);
+		// Relayers cannot submit commitments with a version field but no expiry, so there is
+		// no such version to set
+		let call = incentivized_outbound_channel::Call::<Test>::set_commitment_versions {
+			versions: vec![CommitmentVersion::V0, CommitmentVersion::V2],
+		};
+		let mut encoded = call.encode();
+		*encoded.last_mut().unwrap() = 1;
+		assert!(incentivized_outbound_channel::Call::<Test>::decode(&mut &encoded[..]).is_err());
+
+		assert_ok!(IncentivizedOutboundChannel::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V2, CommitmentVersion::V0]
+		));
+		assert_eq!(
+			IncentivizedOutboundChannel::commitment_versions().into_inner(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		);
+	});
+}
+
+#[test]
+fn test_commit_in_each_version() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+
+		// Deposit enough money to cover fees
+		Ether::mint_into(&who, 300).unwrap();
+
+		assert_ok!(IncentivizedOutboundChannel::set_commitment_versions(
+			Origin::root(),
+			vec![CommitmentVersion::V0, CommitmentVersion::V2]
+		));
+		assert_ok!(IncentivizedOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], None));
+		run_to_block(2);
+
+		let commitments = commitments();
+		assert_eq!(commitments.len(), 2);
+
+		let (legacy_hash, legacy) = &commitments[0];
+		let (hash, bundle) = &commitments[1];
+		assert_eq!(legacy.version, CommitmentVersion::V0);
+		assert_eq!(bundle.version, CommitmentVersion::V2);
+		// Fees of individual messages are not part of the legacy layout
+		assert_eq!(legacy.to_legacy().messages, bundle.to_legacy().messages);
+		assert_eq!(legacy.fee, bundle.fee);
+		assert_ne!(legacy_hash, hash);
+
+		let logs = System::digest().logs;
+		let expected: Vec<DigestItem> = vec![
+			AuxiliaryDigestItem::LegacyCommitment(ChannelId::INCENTIVIZED, *legacy_hash).into(),
+			AuxiliaryDigestItem::Commitment(ChannelId::INCENTIVIZED, CommitmentVersion::V2, *hash)
+				.into(),
+		];
+		assert_eq!(logs, expected);
+		assert_eq!(
+			<LatestCommitmentHashes<Test>>::get().into_inner(),
+			vec![(CommitmentVersion::V0, *legacy_hash), (CommitmentVersion::V2, *hash)]
+		);
+	});
+}
+
+fn report(channel: H160, id: u64, result: bool) -> snowbridge_core::Message {
+	let log = Log {
+		address: channel,
+		topics: vec![H256(sp_io::hashing::keccak_256(b"MessageDispatched(uint64,bool)"))],
+		data: ethabi::encode(&[Token::Uint(id.into()), Token::Bool(result)]),
+	};
+	snowbridge_core::Message {
+		data: log.encode(),
+		proof: Proof::PoW(ReceiptProof {
+			block_hash: Default::default(),
+			tx_index: Default::default(),
+			data: Default::default(),
+		}),
+	}
+}
+
+fn commitments() -> Vec<(H256, MessageBundleOf<Test>)> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::IncentivizedOutboundChannel(crate::outbound::Event::Committed {
+				hash,
+				data,
+			}) => Some((hash, data.into())),
+			Event::IncentivizedOutboundChannel(crate::outbound::Event::VersionedCommitted {
+				hash,
+				data,
+			}) => Some((hash, data)),
+			_ => None,
+		})
+		.collect()
+}
+
+fn committed_bundle() -> MessageBundleOf<Test> {
+	let (_, bundle) = commitments().into_iter().next().expect("commitment event");
+	bundle
+}
+
+fn submit_expiring(expires_at: u64) -> u64 {
+	let who: AccountId = Keyring::Bob.into();
+	Ether::mint_into(&who, 100).unwrap();
+	IncentivizedOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(expires_at))
+		.unwrap()
+}
+
+fn set_expiring_versions() {
+	assert_ok!(IncentivizedOutboundChannel::set_commitment_versions(
+		Origin::root(),
+		vec![CommitmentVersion::V2]
+	));
+}
+
+#[test]
+fn test_submit_with_expiry_requires_version_encoding_it() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&who, 100).unwrap();
+
+		assert_noop!(
+			IncentivizedOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(100)),
+			Error::<Test>::ExpiryNotSupported,
+		);
+
+		set_expiring_versions();
+		assert_eq!(submit_expiring(100), 0);
+
+		// Expiring messages in the queue must be committed with their expiry
+		assert_noop!(
+			IncentivizedOutboundChannel::set_commitment_versions(
+				Origin::root(),
+				vec![CommitmentVersion::V0, CommitmentVersion::V2]
+			),
+			Error::<Test>::ExpiryNotSupported,
+		);
+	});
+}
+
+#[test]
+fn test_submit_expired_message() {
+	new_tester().execute_with(|| {
+		let who: AccountId = Keyring::Bob.into();
+		Ether::mint_into(&who, 100).unwrap();
+		set_expiring_versions();
+		EthereumTimestamp::set(Some(100));
+
+		assert_noop!(
+			IncentivizedOutboundChannel::submit(&who, H160::zero(), &vec![0, 1, 2], Some(99)),
+			Error::<Test>::MessageExpired,
+		);
+	});
+}
+
+#[test]
+fn test_commit_drops_expired_messages() {
+	new_tester().execute_with(|| {
+		set_expiring_versions();
+		EthereumTimestamp::set(Some(100));
+		let expired = submit_expiring(150);
+		let pending = submit_expiring(250);
+
+		EthereumTimestamp::set(Some(200));
+		run_to_block(2);
+
+		assert_eq!(
+			IncentivizedOutboundChannel::outcome(expired),
+			Some(MessageOutcome::Undelivered)
+		);
+		assert_eq!(<PendingOutcomes<Test>>::get(pending), Some(250));
+
+		let bundle = committed_bundle();
+		assert_eq!(bundle.messages.len(), 1);
+		assert_eq!(bundle.messages[0].id, pending);
+		assert_eq!(bundle.messages[0].expires_at, Some(250));
+	});
+}
+
+#[test]
+fn test_report_dispatch() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Ferdie.into();
+		set_expiring_versions();
+		let id = submit_expiring(250);
+		run_to_block(2);
+
+		assert_noop!(
+			IncentivizedOutboundChannel::report_dispatch(
+				Origin::signed(relayer.clone()),
+				report(H160::repeat_byte(1), id, false)
+			),
+			Error::<Test>::InvalidTargetChannel,
+		);
+		assert_ok!(IncentivizedOutboundChannel::report_dispatch(
+			Origin::signed(relayer.clone()),
+			report(TARGET_CHANNEL.into(), id, false)
+		));
+		assert_noop!(
+			IncentivizedOutboundChannel::report_dispatch(
+				Origin::signed(relayer),
+				report(TARGET_CHANNEL.into(), id, false)
+			),
+			Error::<Test>::UnknownMessage,
+		);
+
+		assert_eq!(
+			IncentivizedOutboundChannel::take_outcome(id),
+			Some(MessageOutcome::Undelivered)
+		);
+		assert_eq!(IncentivizedOutboundChannel::take_outcome(id), None);
+	});
+}
+
+#[test]
+fn test_no_outcome_without_report() {
+	new_tester().execute_with(|| {
+		set_expiring_versions();
+		let id = submit_expiring(250);
+		run_to_block(2);
+
+		// The message may have been delivered however long ago it expired
+		EthereumTimestamp::set(Some(u64::MAX));
+		assert_eq!(IncentivizedOutboundChannel::take_outcome(id), None);
+		assert_eq!(<PendingOutcomes<Test>>::get(id), Some(250));
+	});
+}
+
+#[test]
+fn test_migrate_queue_without_expiry() {
+	new_tester().execute_with(|| {
+		StorageVersion::new(1).put::<IncentivizedOutboundChannel>();
+		let queue: Vec<MessageWithoutExpiryOf<Test>> = vec![MessageWithoutExpiry {
+			id: 3,
+			target: H160::repeat_byte(1),
+			fee: 250,
+			payload: vec![0, 1, 2].try_into().unwrap(),
+		}];
+		unhashed::put(&<MessageQueue<Test>>::hashed_key(), &queue);
+
+		IncentivizedOutboundChannel::on_runtime_upgrade();
+
+		assert_eq!(
+			IncentivizedOutboundChannel::queued_messages(),
+			vec![QueuedMessage {
+				id: 3,
+				target: H160::repeat_byte(1),
+				fee: 250,
+				expires_at: None,
+				payload: vec![0, 1, 2],
+			}]
+		);
+		assert_eq!(StorageVersion::get::<IncentivizedOutboundChannel>(), 2);
+	});
+}
+
+#[test]
+fn test_migrate_legacy_queue_over_bound() {
+	new_tester().execute_with(|| {
+		StorageVersion::new(0).put::<IncentivizedOutboundChannel>();
+		let max_messages = MaxQueuedMessages::get() as u64;
+		let queue: Vec<LegacyMessageOf<Test>> = (0..max_messages + 2)
+			.map(|id| LegacyMessage {
+				id,
+				target: H160::repeat_byte(1),
+				payload: vec![0, 1, 2].try_into().unwrap(),
+			})
+			.collect();
+		unhashed::put(&<MessageQueue<Test>>::hashed_key(), &queue);
+
+		IncentivizedOutboundChannel::on_runtime_upgrade();
+
+		// The oldest messages are kept
+		let queued = IncentivizedOutboundChannel::queued_messages();
+		assert_eq!(queued.len() as u64, max_messages);
+		assert_eq!(queued.last().unwrap().id, max_messages - 1);
+		assert_eq!(StorageVersion::get::<IncentivizedOutboundChannel>(), 2);
+	});
+}
+
+#[test]
+fn test_migrate_undecodable_queue() {
+	new_tester().execute_with(|| {
+		StorageVersion::new(1).put::<IncentivizedOutboundChannel>();
+		unhashed::put_raw(&<MessageQueue<Test>>::hashed_key(), &[0xff]);
+
+		IncentivizedOutboundChannel::on_runtime_upgrade();
+
+		assert_eq!(unhashed::get_raw(&<MessageQueue<Test>>::hashed_key()), None);
+		assert_eq!(IncentivizedOutboundChannel::queued_messages(), vec![]);
+	});
+}
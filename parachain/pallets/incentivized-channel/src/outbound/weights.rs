@@ -3,6 +3,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-11-25, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("spec.json"), DB CACHE: 128
+//!
+//! Entries marked ESTIMATE are not output of the benchmark CLI. They must be replaced by
+//! re-running the benchmarks before this pallet is deployed to a production chain.
 
 // Executed Command:
 // target/release/snowbridge
@@ -41,11 +44,16 @@ pub trait WeightInfo {
 	fn on_initialize_non_interval() -> Weight;
 	fn on_initialize_no_messages() -> Weight;
 	fn set_fee() -> Weight;
+	fn set_gas_config() -> Weight;
+	fn set_fee_inputs() -> Weight;
+	fn set_commitment_versions() -> Weight;
 }
 
 /// Weights for incentivized_channel::outbound using the Snowbridge node and recommended hardware.
 pub struct SnowbridgeWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
+	// ESTIMATE: the benchmarked weight, plus a write of the latest commitment hashes.
+	// Replace with the output of the `on_initialize` benchmark.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 32_000
@@ -53,7 +61,7 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 			// Standard Error: 1_000
 			.saturating_add((393_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn on_initialize_non_interval() -> Weight {
 		(3_469_000 as Weight)
@@ -67,10 +75,33 @@ impl<T: frame_system::Config> WeightInfo for SnowbridgeWeight<T> {
 		(2_311_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: the benchmarked `set_fee`, with a larger argument to decode. Replace with the
+	// output of the `set_gas_config` benchmark.
+	fn set_gas_config() -> Weight {
+		(2_500_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: the benchmarked `set_fee`, with a larger argument to decode and a read of the
+	// accounts allowed to feed fee inputs. Replace with the output of the `set_fee_inputs`
+	// benchmark.
+	fn set_fee_inputs() -> Weight {
+		(2_700_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: a single write, plus a read of the message queue when a version without
+	// expiry is set. Replace with the output of the `set_commitment_versions` benchmark.
+	fn set_commitment_versions() -> Weight {
+		(2_803_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// ESTIMATE: the benchmarked weight, plus a write of the latest commitment hashes.
+	// Replace with the output of the `on_initialize` benchmark.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 32_000
@@ -78,7 +109,7 @@ impl WeightInfo for () {
 			// Standard Error: 1_000
 			.saturating_add((393_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn on_initialize_non_interval() -> Weight {
 		(3_469_000 as Weight)
@@ -92,4 +123,25 @@ impl WeightInfo for () {
 		(2_311_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// ESTIMATE: the benchmarked `set_fee`, with a larger argument to decode. Replace with the
+	// output of the `set_gas_config` benchmark.
+	fn set_gas_config() -> Weight {
+		(2_500_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: the benchmarked `set_fee`, with a larger argument to decode and a read of the
+	// accounts allowed to feed fee inputs. Replace with the output of the `set_fee_inputs`
+	// benchmark.
+	fn set_fee_inputs() -> Weight {
+		(2_700_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// ESTIMATE: a single write, plus a read of the message queue when a version without
+	// expiry is set. Replace with the output of the `set_commitment_versions` benchmark.
+	fn set_commitment_versions() -> Weight {
+		(2_803_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
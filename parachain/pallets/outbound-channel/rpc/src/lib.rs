@@ -0,0 +1,94 @@
+//! RPC interface for the outbound channels.
+//!
+//! Exposes messages which are queued but not yet committed, so that a transfer can be tracked
+//! before its commitment appears in a block header.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+
+use snowbridge_core::{ChannelId, OutboundChannelState, QueuedMessage};
+
+pub use snowbridge_outbound_channel_runtime_api::OutboundChannelApi as OutboundChannelRuntimeApi;
+
+#[rpc]
+pub trait OutboundChannelApi<BlockHash, BlockNumber> {
+	/// Get the messages waiting to be committed on a channel, in the order they will be
+	/// committed.
+	///
+	/// Returns `null` if the channel is not registered.
+	#[rpc(name = "outboundChannel_getQueuedMessages")]
+	fn get_queued_messages(
+		&self,
+		channel_id: ChannelId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Vec<QueuedMessage>>>;
+
+	/// Get the next commitment block, and the nonce and hash of the latest commitment, for a
+	/// channel.
+	///
+	/// Returns `null` if the channel is not registered.
+	#[rpc(name = "outboundChannel_getState")]
+	fn get_state(
+		&self,
+		channel_id: ChannelId,
+		at: Option<BlockHash>,
+	) -> Result<Option<OutboundChannelState<BlockNumber>>>;
+}
+
+pub struct OutboundChannel<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> OutboundChannel<C, B> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(1),
+		message: "Unable to query outbound channel".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block> OutboundChannelApi<<Block as BlockT>::Hash, NumberFor<Block>>
+	for OutboundChannel<C, Block>
+where
+	Block: BlockT,
+	NumberFor<Block>: Codec,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: OutboundChannelRuntimeApi<Block, NumberFor<Block>>,
+{
+	fn get_queued_messages(
+		&self,
+		channel_id: ChannelId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Vec<QueuedMessage>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.queued_messages(&at, channel_id)
+			.map_err(runtime_error)
+	}
+
+	fn get_state(
+		&self,
+		channel_id: ChannelId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<OutboundChannelState<NumberFor<Block>>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().channel_state(&at, channel_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,21 @@
+//! Runtime API for querying messages which are waiting to be committed on the outbound channels.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+use snowbridge_core::{ChannelId, OutboundChannelState, QueuedMessage};
+
+sp_api::decl_runtime_apis! {
+	pub trait OutboundChannelApi<BlockNumber> where BlockNumber: Codec {
+		/// Messages waiting to be committed on the channel, in the order they will be committed.
+		///
+		/// Returns `None` if the channel is not registered.
+		fn queued_messages(channel_id: ChannelId) -> Option<Vec<QueuedMessage>>;
+
+		/// Commitment state of the channel.
+		///
+		/// Returns `None` if the channel is not registered.
+		fn channel_state(channel_id: ChannelId) -> Option<OutboundChannelState<BlockNumber>>;
+	}
+}
@@ -1,6 +1,6 @@
 use frame_support::dispatch::DispatchResult;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 
@@ -10,7 +10,9 @@ use serde::{Deserialize, Serialize};
 /// Represents a remote parachain by id with a fee that will be used by
 /// `XcmReserveTransfer::reserve_transfer` to send an asset to a remote
 /// parachain.
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, PartialOrd, RuntimeDebug, TypeInfo)]
+#[derive(
+	Encode, Decode, Copy, Clone, PartialEq, Eq, PartialOrd, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RemoteParachain {
 	/// The parachain id.
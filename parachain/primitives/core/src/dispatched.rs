@@ -0,0 +1,87 @@
+//! Reports of the dispatch of messages by the inbound channels on Ethereum.
+
+use ethabi::{Event, Param, ParamKind, Token};
+use snowbridge_ethereum::{log::Log, H160};
+
+use sp_core::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::*};
+
+// Used to decode a raw Ethereum log into a [`MessageDispatched`].
+static EVENT_ABI: &Event = &Event {
+	signature: "MessageDispatched(uint64,bool)",
+	inputs: &[
+		Param { kind: ParamKind::Uint(64), indexed: false },
+		Param { kind: ParamKind::Bool, indexed: false },
+	],
+	anonymous: false,
+};
+
+/// Report of the dispatch of a message by the inbound channel on Ethereum.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MessageDispatched {
+	/// The address of the inbound channel on Ethereum that dispatched the message.
+	pub channel: H160,
+	/// ID of the message.
+	pub id: u64,
+	/// Whether the message was dispatched successfully to its target application.
+	pub result: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MessageDispatchedDecodeError;
+
+impl TryFrom<Log> for MessageDispatched {
+	type Error = MessageDispatchedDecodeError;
+
+	fn try_from(log: Log) -> Result<Self, Self::Error> {
+		let tokens = EVENT_ABI
+			.decode(log.topics, log.data)
+			.map_err(|_| MessageDispatchedDecodeError)?;
+
+		let mut iter = tokens.into_iter();
+
+		let id = match iter.next().ok_or(MessageDispatchedDecodeError)? {
+			Token::Uint(value) => value.low_u64(),
+			_ => return Err(MessageDispatchedDecodeError),
+		};
+
+		let result = match iter.next().ok_or(MessageDispatchedDecodeError)? {
+			Token::Bool(result) => result,
+			_ => return Err(MessageDispatchedDecodeError),
+		};
+
+		Ok(Self { channel: log.address, id, result })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::{Hash, Keccak256};
+
+	fn log(data: Vec<u8>) -> Log {
+		Log {
+			address: H160::repeat_byte(1),
+			topics: vec![Keccak256::hash(b"MessageDispatched(uint64,bool)")],
+			data,
+		}
+	}
+
+	#[test]
+	fn test_try_from_log() {
+		let data = ethabi::encode(&[Token::Uint(7u64.into()), Token::Bool(false)]);
+
+		assert_eq!(
+			MessageDispatched::try_from(log(data)),
+			Ok(MessageDispatched { channel: H160::repeat_byte(1), id: 7, result: false })
+		);
+	}
+
+	#[test]
+	fn test_try_from_other_log() {
+		let mut other = log(ethabi::encode(&[Token::Uint(7u64.into()), Token::Bool(true)]));
+		other.topics = vec![Keccak256::hash(b"Message(address,uint64,bytes)")];
+
+		assert_eq!(MessageDispatched::try_from(other), Err(MessageDispatchedDecodeError));
+	}
+}
@@ -6,23 +6,44 @@
 #![allow(unused_variables)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	weights::Weight,
+};
 use frame_system::Config;
 use snowbridge_ethereum::{Header, Log, U256};
 use sp_core::H160;
-use sp_std::prelude::*;
+use sp_std::{marker::PhantomData, prelude::*};
 
 pub mod assets;
+pub mod dispatched;
+pub mod limits;
+pub mod merkle;
 pub mod types;
 
-pub use types::{ChannelId, Message, MessageId, MessageNonce, Proof};
+pub use types::{
+	AppId, BridgeComponent, ChannelId, CommitmentVersion, Message, MessageId, MessageNonce,
+	MessageOutcome, OutboundChannelState, Proof, QueuedMessage, ReceiptProof, VerificationInfo,
+};
 
 /// A trait for verifying messages.
 ///
 /// This trait should be implemented by runtime modules that wish to provide message verification
 /// functionality.
 pub trait Verifier {
-	fn verify(message: &Message) -> Result<Log, DispatchError>;
+	/// Verify a message, returning its log and where the log was found.
+	fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError>;
+}
+
+/// Rejects every message.
+impl Verifier for () {
+	fn verify(_: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
+		Err(DispatchError::Other("Verification not supported"))
+	}
+}
+
+/// A trait for bootstrapping a PoW verifier with trusted headers.
+pub trait InitializeVerifier {
 	fn initialize_storage(
 		headers: Vec<Header>,
 		initial_difficulty: U256,
@@ -30,14 +51,87 @@ pub trait Verifier {
 	) -> Result<(), &'static str>;
 }
 
+/// A [`Verifier`] which routes messages to a verifier based on the scheme of their proof.
+///
+/// Allows a runtime to accept messages verified by either scheme, for example while
+/// migrating from the PoW light client to the beacon light client.
+pub struct ProofRouter<PoW, Execution>(PhantomData<(PoW, Execution)>);
+
+impl<PoW: Verifier, Execution: Verifier> Verifier for ProofRouter<PoW, Execution> {
+	fn verify(message: &Message) -> Result<(Log, VerificationInfo), DispatchError> {
+		match message.proof {
+			Proof::PoW(_) => PoW::verify(message),
+			Proof::Execution(_) => Execution::verify(message),
+		}
+	}
+}
+
 /// Outbound submission for applications
 pub trait OutboundRouter<AccountId> {
+	/// Submit a message on any registered channel.
+	///
+	/// Depending on the channel, the message is either submitted from the account of the `app`,
+	/// which must be authorized by the channel, or from the account of the `sender`, which then
+	/// pays the delivery fee.
+	///
+	/// A message with an `expires_at` timestamp is not delivered on Ethereum after that time.
+	/// Returns the ID of the message on its channel.
 	fn submit(
 		channel_id: ChannelId,
-		who: &AccountId,
+		app: &AccountId,
+		sender: &AccountId,
 		target: H160,
 		payload: &[u8],
-	) -> DispatchResult;
+		expires_at: Option<u64>,
+	) -> Result<u64, DispatchError>;
+
+	/// Take the outcome of a message with an expiry, once it is known.
+	///
+	/// Each outcome is only returned once, so that apps act on it at most once.
+	fn take_outcome(
+		channel_id: ChannelId,
+		message_id: u64,
+	) -> Result<Option<MessageOutcome>, DispatchError>;
+}
+
+/// Weight of submitting a message through an [`OutboundRouter`], given the weights of
+/// submitting it on the basic and the incentivized channel.
+///
+/// Any registered channel may be used, so this is the costlier of the two.
+pub fn outbound_submit_weight(basic: Weight, incentivized: Weight) -> Weight {
+	basic.max(incentivized)
+}
+
+/// Time on Ethereum, as known to the parachain
+pub trait EthereumTime {
+	/// Timestamp of the latest finalized Ethereum block, if any.
+	///
+	/// Every block added to Ethereum later has a greater timestamp.
+	fn finalized_timestamp() -> Option<u64>;
+}
+
+impl EthereumTime for () {
+	fn finalized_timestamp() -> Option<u64> {
+		None
+	}
+}
+
+/// Channel contracts on Ethereum which messages on each channel are sent from
+pub trait ChannelSource {
+	/// Address of the outbound channel contract of `channel_id`, if the channel is registered.
+	fn source(channel_id: ChannelId) -> Option<H160>;
+}
+
+/// Emergency pause for parts of the bridge
+pub trait CircuitBreaker {
+	/// Fails if `component` has been paused.
+	fn ensure_active(component: BridgeComponent) -> DispatchResult;
+}
+
+impl CircuitBreaker for () {
+	fn ensure_active(_: BridgeComponent) -> DispatchResult {
+		Ok(())
+	}
 }
 
 /// Add a message to a commitment
@@ -47,7 +141,10 @@ pub trait MessageCommitment {
 
 /// Dispatch a message
 pub trait MessageDispatch<T: Config, MessageId> {
-	fn dispatch(source: H160, id: MessageId, payload: &[u8]);
+	/// Dispatch a message, returning the weight it actually consumed.
+	fn dispatch(source: H160, id: MessageId, payload: &[u8]) -> Weight;
+	/// Maximum weight needed to dispatch a message, or zero if it cannot be dispatched.
+	fn dispatch_weight(payload: &[u8]) -> Weight;
 	#[cfg(feature = "runtime-benchmarks")]
 	fn successful_dispatch_event(id: MessageId) -> Option<<T as Config>::Event>;
 }
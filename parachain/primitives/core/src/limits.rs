@@ -0,0 +1,279 @@
+//! Caps on the amount of an asset which bridge applications release in a period
+
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
+use frame_support::{
+	storage::{IterableStorageMap, StorageMap, StorageValue},
+	weights::{RuntimeDbWeight, Weight},
+};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_runtime::traits::{AtLeast32BitUnsigned, One, Saturating, Zero};
+use sp_std::{convert::TryInto, prelude::*};
+
+/// Number of buckets which the usage over a period is tracked in.
+pub const USAGE_BUCKETS: usize = 8;
+
+/// Max number of usage entries visited for pruning in a block.
+const MAX_PRUNED_PER_BLOCK: u64 = 100;
+
+/// Caps on the amount of an asset which can be transferred in each period.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TransferLimit<Balance, BlockNumber> {
+	/// Length of a period in blocks.
+	pub period: BlockNumber,
+	/// Cap on the amount transferred to all recipients in a period.
+	pub global_cap: Balance,
+	/// Cap on the amount transferred to any one recipient in a period.
+	pub recipient_cap: Balance,
+}
+
+/// Amount of an asset transferred over a rolling period.
+///
+/// Amounts are tracked in buckets of a [`USAGE_BUCKETS`]th of the period, rounded up. An amount
+/// is counted until every block of its bucket is more than a period old, so that no run of blocks
+/// as long as the period sees more than the cap transferred.
+#[derive(
+	Encode, Decode, Copy, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct TransferUsage<Balance, BlockNumber> {
+	/// Length of the buckets in blocks.
+	pub bucket_length: BlockNumber,
+	/// Bucket which the latest amount was added to, counting from block zero.
+	pub latest_bucket: BlockNumber,
+	/// Amount transferred in each bucket up to `latest_bucket`, which is the last one.
+	pub amounts: [Balance; USAGE_BUCKETS + 1],
+}
+
+impl<Balance, BlockNumber> TransferUsage<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Add `amount` to the usage at block `now`, forgetting amounts which are no longer in the
+	/// rolling period.
+	///
+	/// Returns `None` if the amount transferred in the period would exceed `cap`.
+	pub fn checked_add(
+		self,
+		amount: Balance,
+		cap: Balance,
+		period: BlockNumber,
+		now: BlockNumber,
+	) -> Option<Self> {
+		let mut usage = self.rolled(period, now);
+		let total = usage.total().checked_add(&amount)?;
+		if total > cap {
+			return None
+		}
+		usage.amounts[USAGE_BUCKETS] = usage.amounts[USAGE_BUCKETS].saturating_add(amount);
+		Some(usage)
+	}
+
+	/// Whether none of the usage is counted towards a cap at block `now` any more.
+	pub fn is_expired(&self, now: BlockNumber) -> bool {
+		if self.bucket_length.is_zero() {
+			return true
+		}
+		let elapsed = (now / self.bucket_length).saturating_sub(self.latest_bucket);
+		elapsed > BlockNumber::from(USAGE_BUCKETS as u32)
+	}
+
+	fn total(&self) -> Balance {
+		self.amounts
+			.iter()
+			.fold(Zero::zero(), |total, amount| total.saturating_add(*amount))
+	}
+
+	/// The usage with its latest bucket moved to the one containing block `now`.
+	fn rolled(self, period: BlockNumber, now: BlockNumber) -> Self {
+		let buckets = BlockNumber::from(USAGE_BUCKETS as u32);
+		let bucket_length = (period.saturating_add(buckets - One::one()) / buckets).max(One::one());
+		let bucket = now / bucket_length;
+		let mut amounts = [Zero::zero(); USAGE_BUCKETS + 1];
+
+		if self.is_expired(now) {
+			return Self { bucket_length, latest_bucket: bucket, amounts }
+		}
+		// The period changed since the usage was recorded. Its buckets cannot be split into the
+		// new ones, so all of it is counted as transferred in the current bucket.
+		if bucket_length != self.bucket_length {
+			amounts[USAGE_BUCKETS] = self.total();
+			return Self { bucket_length, latest_bucket: bucket, amounts }
+		}
+
+		let shift: usize =
+			bucket.saturating_sub(self.latest_bucket).try_into().unwrap_or(usize::MAX);
+		for (index, amount) in self.amounts.iter().enumerate().skip(shift) {
+			amounts[index - shift] = *amount;
+		}
+		Self { bucket_length, latest_bucket: bucket.max(self.latest_bucket), amounts }
+	}
+}
+
+/// A transfer which exceeded the transfer limit, and is waiting to be executed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingTransfer<Transfer, BlockNumber> {
+	pub transfer: Transfer,
+	/// Block after which anyone can execute the transfer.
+	pub execute_after: BlockNumber,
+}
+
+/// Usage of an asset counted by a [`TransferLimiter`].
+pub type UsageOf<L> =
+	TransferUsage<<L as TransferLimiter>::Balance, <L as TransferLimiter>::BlockNumber>;
+/// Transfer queued by a [`TransferLimiter`].
+pub type PendingTransferOf<L> =
+	PendingTransfer<<L as TransferLimiter>::Transfer, <L as TransferLimiter>::BlockNumber>;
+
+/// Transfer limits of an application, kept in its storage.
+///
+/// Applications implement this to name the storage items holding their limits, and use the
+/// provided methods to count transfers against the limits and to queue transfers over them.
+pub trait TransferLimiter {
+	/// Key of the usage of a recipient.
+	type Recipient: FullCodec;
+	type Balance: AtLeast32BitUnsigned + Copy + FullCodec;
+	type BlockNumber: AtLeast32BitUnsigned + Copy + FullCodec;
+	/// Details of a transfer needed to execute it later.
+	type Transfer: FullCodec;
+
+	/// Usage of each recipient.
+	type RecipientUsage: IterableStorageMap<Self::Recipient, UsageOf<Self>>
+		+ StorageMap<Self::Recipient, UsageOf<Self>, Query = UsageOf<Self>>;
+
+	/// Transfers waiting to be executed, by id.
+	type PendingTransfers: StorageMap<
+		u64,
+		PendingTransferOf<Self>,
+		Query = Option<PendingTransferOf<Self>>,
+	>;
+
+	/// Id of the next pending transfer.
+	type NextPendingId: StorageValue<u64, Query = u64>;
+
+	/// Raw key in `RecipientUsage` after which pruning continues.
+	type PruneCursor: StorageValue<Vec<u8>, Query = Option<Vec<u8>>>;
+
+	/// Count a transfer to `recipient` against `limit` at block `now`, where `global` is the
+	/// usage of the asset by all recipients.
+	///
+	/// Returns `false`, and counts nothing, if the transfer would exceed a cap.
+	fn consume(
+		limit: &TransferLimit<Self::Balance, Self::BlockNumber>,
+		global: &mut UsageOf<Self>,
+		recipient: &Self::Recipient,
+		amount: Self::Balance,
+		now: Self::BlockNumber,
+	) -> bool {
+		let for_global = global.checked_add(amount, limit.global_cap, limit.period, now);
+		let for_recipient = Self::RecipientUsage::get(recipient).checked_add(
+			amount,
+			limit.recipient_cap,
+			limit.period,
+			now,
+		);
+		match (for_global, for_recipient) {
+			(Some(for_global), Some(for_recipient)) => {
+				*global = for_global;
+				Self::RecipientUsage::insert(recipient, for_recipient);
+				true
+			},
+			_ => false,
+		}
+	}
+
+	/// Queue a transfer which can be executed by anyone after block `execute_after`.
+	///
+	/// Returns the id of the pending transfer.
+	fn queue(transfer: Self::Transfer, execute_after: Self::BlockNumber) -> u64 {
+		let id = Self::NextPendingId::mutate(|next| {
+			let id = *next;
+			*next = next.wrapping_add(1);
+			id
+		});
+		Self::PendingTransfers::insert(id, PendingTransfer { transfer, execute_after });
+		id
+	}
+
+	/// Remove a pending transfer from the queue.
+	fn take(id: u64) -> Option<PendingTransferOf<Self>> {
+		Self::PendingTransfers::take(id)
+	}
+
+	/// Remove usage of recipients which is no longer counted towards a cap at block `now`,
+	/// visiting as many entries as fit in `remaining_weight`.
+	///
+	/// Entries are visited in storage order, continuing from where the last call stopped.
+	fn prune(now: Self::BlockNumber, remaining_weight: Weight, db: RuntimeDbWeight) -> Weight {
+		let base_weight = db.reads_writes(1, 1);
+		let entry_weight = db.reads_writes(1, 1);
+		let max_entries = remaining_weight
+			.saturating_sub(base_weight)
+			.checked_div(entry_weight)
+			.unwrap_or(u64::MAX)
+			.min(MAX_PRUNED_PER_BLOCK);
+		if remaining_weight < base_weight || max_entries == 0 {
+			return 0
+		}
+
+		let entries = match Self::PruneCursor::get() {
+			Some(cursor) => Self::RecipientUsage::iter_from(cursor),
+			None => Self::RecipientUsage::iter(),
+		};
+		let mut visited = 0;
+		let mut last = None;
+		let mut expired = Vec::new();
+		for (recipient, usage) in entries.take(max_entries as usize) {
+			visited += 1;
+			if usage.is_expired(now) {
+				expired.push(Self::RecipientUsage::hashed_key_for(&recipient));
+			}
+			last = Some(recipient);
+		}
+
+		match last {
+			Some(recipient) if visited == max_entries =>
+				Self::PruneCursor::put(Self::RecipientUsage::hashed_key_for(&recipient)),
+			// All entries were visited, so start over in the next call
+			_ => Self::PruneCursor::kill(),
+		}
+		for key in expired.iter() {
+			frame_support::storage::unhashed::kill(key);
+		}
+
+		base_weight.saturating_add(entry_weight.saturating_mul(visited))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Usage = TransferUsage<u128, u64>;
+
+	#[test]
+	fn counts_amounts_over_a_rolling_period() {
+		// Buckets of 13 blocks for a period of 100 blocks
+		let usage = Usage::default().checked_add(60, 100, 100, 1).unwrap();
+		let usage = usage.checked_add(40, 100, 100, 90).unwrap();
+
+		// The amount at block 1 is still counted at block 101
+		assert_eq!(usage.checked_add(40, 100, 100, 101), None);
+
+		// It is forgotten once its bucket is more than a period old
+		let usage = usage.checked_add(60, 100, 100, 117).unwrap();
+		assert_eq!(usage.checked_add(1, 100, 100, 117), None);
+		assert!(!usage.is_expired(117));
+		assert!(usage.is_expired(234));
+	}
+
+	#[test]
+	fn counts_all_usage_when_the_period_changes() {
+		let usage = Usage::default().checked_add(60, 100, 100, 1).unwrap();
+
+		assert_eq!(usage.checked_add(41, 100, 50, 60), None);
+		let usage = usage.checked_add(40, 100, 50, 60).unwrap();
+		assert_eq!(usage.amounts[USAGE_BUCKETS], 100);
+		assert_eq!(usage.bucket_length, 7);
+	}
+}
@@ -0,0 +1,129 @@
+//! Binary Merkle trees compatible with `MerkleProof.sol` on the Ethereum side.
+//!
+//! Leaves are hashed before insertion. Inner nodes are the hash of the concatenation of their
+//! children, and the last node of a level with an odd number of nodes is promoted unchanged.
+
+use codec::{Decode, Encode};
+use frame_support::{scale_info::TypeInfo, RuntimeDebug};
+use sp_core::H256;
+use sp_runtime::traits::Hash;
+use sp_std::prelude::*;
+
+/// Proof of inclusion of a leaf in a Merkle tree.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MerkleProof {
+	/// Root of the tree.
+	pub root: H256,
+	/// Sibling hashes, ordered from leaf to root.
+	pub proof: Vec<H256>,
+	/// Number of leaves in the tree.
+	pub number_of_leaves: u64,
+	/// Position of the leaf, starting from 0.
+	pub leaf_index: u64,
+	/// The unhashed leaf.
+	pub leaf: Vec<u8>,
+}
+
+/// Compute the root of the tree over `leaves`, or zero if there are no leaves.
+pub fn merkle_root<H, I>(leaves: I) -> H256
+where
+	H: Hash<Output = H256>,
+	I: IntoIterator,
+	I::Item: AsRef<[u8]>,
+{
+	let mut level: Vec<H256> = leaves.into_iter().map(|leaf| H::hash(leaf.as_ref())).collect();
+	if level.is_empty() {
+		return H256::zero()
+	}
+	while level.len() > 1 {
+		level = next_level::<H>(&level);
+	}
+	level[0]
+}
+
+/// Construct a proof of inclusion for the leaf at `leaf_index`.
+pub fn merkle_proof<H, I>(leaves: I, leaf_index: u64) -> Option<MerkleProof>
+where
+	H: Hash<Output = H256>,
+	I: IntoIterator,
+	I::Item: AsRef<[u8]>,
+{
+	let leaves: Vec<I::Item> = leaves.into_iter().collect();
+	let leaf = leaves.get(leaf_index as usize)?.as_ref().to_vec();
+
+	let mut level: Vec<H256> = leaves.iter().map(|leaf| H::hash(leaf.as_ref())).collect();
+	let mut index = leaf_index as usize;
+	let mut proof = Vec::new();
+	while level.len() > 1 {
+		if let Some(sibling) = level.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		level = next_level::<H>(&level);
+		index /= 2;
+	}
+
+	Some(MerkleProof {
+		root: level[0],
+		proof,
+		number_of_leaves: leaves.len() as u64,
+		leaf_index,
+		leaf,
+	})
+}
+
+/// Check that `leaf_hash` is at `leaf_index` in the tree with the given root.
+pub fn verify_proof<H>(
+	root: H256,
+	proof: &[H256],
+	number_of_leaves: u64,
+	leaf_index: u64,
+	leaf_hash: H256,
+) -> bool
+where
+	H: Hash<Output = H256>,
+{
+	if leaf_index >= number_of_leaves {
+		return false
+	}
+
+	let mut computed = leaf_hash;
+	let mut position = leaf_index;
+	let mut width = number_of_leaves;
+	let mut siblings = proof.iter();
+	while width > 1 {
+		let is_left = position % 2 == 0;
+		if !(is_left && position + 1 == width) {
+			let sibling = match siblings.next() {
+				Some(sibling) => sibling,
+				None => return false,
+			};
+			computed = if is_left {
+				hash_node::<H>(&computed, sibling)
+			} else {
+				hash_node::<H>(sibling, &computed)
+			};
+		}
+		position /= 2;
+		width = (width - 1) / 2 + 1;
+	}
+
+	siblings.next().is_none() && computed == root
+}
+
+fn next_level<H: Hash<Output = H256>>(level: &[H256]) -> Vec<H256> {
+	level
+		.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => hash_node::<H>(left, right),
+			[single] => *single,
+			_ => unreachable!("chunks has at most two elements; qed"),
+		})
+		.collect()
+}
+
+fn hash_node<H: Hash<Output = H256>>(left: &H256, right: &H256) -> H256 {
+	let mut combined = [0u8; 64];
+	combined[..32].copy_from_slice(left.as_bytes());
+	combined[32..].copy_from_slice(right.as_bytes());
+	H::hash(&combined)
+}
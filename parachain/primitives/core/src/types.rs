@@ -1,30 +1,75 @@
 //! Types for representing messages
 
-use codec::{Decode, Encode};
-use enum_iterator::IntoEnumIterator;
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{scale_info::TypeInfo, RuntimeDebug};
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::DigestItem;
 use sp_std::vec::Vec;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct MessageId {
 	pub channel_id: ChannelId,
+	/// The application on Ethereum which sent the message.
+	pub source: H160,
+	/// Nonce of the message, ordered per source application.
 	pub nonce: u64,
 }
 
 impl MessageId {
-	pub fn new(channel_id: ChannelId, nonce: u64) -> Self {
-		Self { channel_id, nonce }
+	pub fn new(channel_id: ChannelId, source: H160, nonce: u64) -> Self {
+		Self { channel_id, source, nonce }
 	}
 }
 
 pub type MessageNonce = u64;
 
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, IntoEnumIterator, RuntimeDebug, TypeInfo)]
-pub enum ChannelId {
-	Basic = 0,
-	Incentivized = 1,
+/// Identifier of a message channel.
+///
+/// Channels are registered at runtime, together with the outbound channel pallet which messages
+/// on the channel are submitted to.
+#[derive(
+	Encode,
+	Decode,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ChannelId(pub u8);
+
+impl ChannelId {
+	/// Channel on which apps submit messages from their own accounts.
+	pub const BASIC: ChannelId = ChannelId(0);
+	/// Channel on which senders pay a fee for the delivery of their messages.
+	pub const INCENTIVIZED: ChannelId = ChannelId(1);
+}
+
+/// A bridge application on the parachain.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AppId {
+	Dot,
+	Eth,
+	Erc20,
+}
+
+/// A part of the bridge which can be paused independently of the others.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BridgeComponent {
+	/// Submission of messages from Ethereum on a channel.
+	InboundChannel(ChannelId),
+	/// Submission of messages to Ethereum on a channel.
+	OutboundChannel(ChannelId),
+	/// All calls of an application.
+	App(AppId),
 }
 
 /// A message relayed from Ethereum.
@@ -38,10 +83,30 @@ pub struct Message {
 
 /// Verification input for the message verifier.
 ///
-/// This data type allows us to support multiple verification schemes. In the near future,
-/// A light-client scheme will be added too.
+/// Each variant corresponds to a verification scheme, so that multiple schemes
+/// can be supported side by side.
+#[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum Proof {
+	/// Receipt proof against a header imported by the PoW light client.
+	#[codec(index = 0)]
+	PoW(ReceiptProof),
+	/// Receipt proof against an execution header imported by the beacon light client.
+	#[codec(index = 1)]
+	Execution(ReceiptProof),
+}
+
+impl Proof {
+	pub fn receipt_proof(&self) -> &ReceiptProof {
+		match self {
+			Proof::PoW(proof) => proof,
+			Proof::Execution(proof) => proof,
+		}
+	}
+}
+
+/// Proof that a transaction receipt is included in a block.
 #[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct Proof {
+pub struct ReceiptProof {
 	// The block hash of the block in which the receipt was included.
 	pub block_hash: H256,
 	// The index of the transaction (and receipt) within the block.
@@ -50,11 +115,118 @@ pub struct Proof {
 	pub data: (Vec<Vec<u8>>, Vec<Vec<u8>>),
 }
 
+/// Information about where a verified message was found on Ethereum.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct VerificationInfo {
+	/// Number of the block containing the log.
+	pub block_number: u64,
+	/// Hash of the block containing the log.
+	pub block_hash: H256,
+	/// Timestamp of the block containing the log.
+	pub timestamp: u64,
+	/// Index of the log within its transaction receipt.
+	pub log_index: u32,
+}
+
+/// A message waiting in the queue of an outbound channel to be committed.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct QueuedMessage {
+	/// Unique message ID
+	pub id: u64,
+	/// Target application on the Ethereum side.
+	pub target: H160,
+	/// Fee paid for the message. Always zero on the basic channel.
+	pub fee: u128,
+	/// Ethereum timestamp after which the message can no longer be delivered, if any.
+	pub expires_at: Option<u64>,
+	/// Payload for target application.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub payload: Vec<u8>,
+}
+
+/// Commitment state of an outbound channel.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OutboundChannelState<BlockNumber> {
+	/// Block in which queued messages will next be committed.
+	pub next_commitment_block: BlockNumber,
+	/// Nonce of the most recent commitment.
+	pub nonce: u64,
+	/// Hash of the most recent commitment in each version it was committed in.
+	pub latest_commitment_hashes: Vec<(CommitmentVersion, H256)>,
+	/// ID which will be assigned to the next message submitted.
+	pub next_message_id: u64,
+}
+
+/// Version of the wire format in which outbound channels commit messages.
+///
+/// The version is encoded as a single byte, both in the committed messages and in the
+/// [`AuxiliaryDigestItem`] for the commitment.
+#[derive(
+	Encode,
+	Decode,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CommitmentVersion {
+	/// Messages are ABI-encoded as `(id, target, payload)`, without a version field.
+	V0 = 0,
+	// 1 is not used. The inbound channels on Ethereum have no method to submit commitments
+	// with a version field but without an expiry.
+	/// Messages are ABI-encoded as `(version, id, target, expiresAt, payload)`, where an
+	/// `expiresAt` of zero means the message does not expire.
+	V2 = 2,
+}
+
+impl CommitmentVersion {
+	/// Whether the expiry of messages is committed, so that it is enforced on Ethereum.
+	pub fn encodes_expiry(self) -> bool {
+		self >= CommitmentVersion::V2
+	}
+}
+
+/// Outcome on Ethereum of an outbound message with an expiry.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MessageOutcome {
+	/// The message was dispatched to its target application.
+	Delivered,
+	/// The message expired, or its dispatch failed, so it had no effect on Ethereum.
+	Undelivered,
+}
+
 /// Auxiliary [`DigestItem`] to include in header digest.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum AuxiliaryDigestItem {
+	/// A batch of messages has been committed in the [`CommitmentVersion::V0`] format.
+	#[codec(index = 0)]
+	LegacyCommitment(ChannelId, H256),
 	/// A batch of messages has been committed.
-	Commitment(ChannelId, H256),
+	#[codec(index = 1)]
+	Commitment(ChannelId, CommitmentVersion, H256),
+}
+
+impl AuxiliaryDigestItem {
+	/// Digest item for a commitment in `version`.
+	///
+	/// V0 commitments keep the unversioned item, so that relayers which predate versioning can
+	/// still find them during a transition.
+	pub fn commitment(channel_id: ChannelId, version: CommitmentVersion, hash: H256) -> Self {
+		match version {
+			CommitmentVersion::V0 => AuxiliaryDigestItem::LegacyCommitment(channel_id, hash),
+			version => AuxiliaryDigestItem::Commitment(channel_id, version, hash),
+		}
+	}
 }
 
 impl Into<DigestItem> for AuxiliaryDigestItem {
@@ -1,4 +1,3 @@
-use crate::header::Header;
 use ethereum_types::U256;
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
@@ -73,37 +72,43 @@ impl DifficultyConfig {
 
 /// This difficulty calculation follows Byzantium rules (https://eips.ethereum.org/EIPS/eip-649)
 /// and shouldn't be used to calculate difficulty prior to the Byzantium fork.
+///
+/// The parent header is described by the only fields the calculation depends on, so that it
+/// can be done for parents which are no longer stored in full.
 pub fn calc_difficulty(
 	config: &DifficultyConfig,
 	time: u64,
-	parent: &Header,
+	parent_number: u64,
+	parent_timestamp: u64,
+	parent_difficulty: U256,
+	parent_has_ommers: bool,
 ) -> Result<U256, &'static str> {
 	let bomb_delay = config
-		.bomb_delay(parent.number + 1)
+		.bomb_delay(parent_number + 1)
 		.ok_or("Cannot calculate difficulty for block number prior to Byzantium")?;
 
 	let block_time_div_9: i64 = time
-		.checked_sub(parent.timestamp)
+		.checked_sub(parent_timestamp)
 		.ok_or("Invalid block time")
 		.and_then(|x| i64::try_from(x / 9).or(Err("Invalid block time")))?;
-	let sigma2: i64 = match parent.has_ommers() {
+	let sigma2: i64 = match parent_has_ommers {
 		true => 2 - block_time_div_9,
 		false => 1 - block_time_div_9,
 	}
 	.max(-99);
 
-	let mut difficulty_without_exp = parent.difficulty;
+	let mut difficulty_without_exp = parent_difficulty;
 	if sigma2 < 0 {
 		difficulty_without_exp -=
-			(parent.difficulty >> DIFFICULTY_BOUND_DIVISOR) * sigma2.abs() as u64;
+			(parent_difficulty >> DIFFICULTY_BOUND_DIVISOR) * sigma2.abs() as u64;
 	} else {
-		difficulty_without_exp += (parent.difficulty >> DIFFICULTY_BOUND_DIVISOR) * sigma2 as u64;
+		difficulty_without_exp += (parent_difficulty >> DIFFICULTY_BOUND_DIVISOR) * sigma2 as u64;
 	}
 
 	difficulty_without_exp = difficulty_without_exp.max(MINIMUM_DIFFICULTY.into());
 
 	// Subtract 1 less since we're using the parent block
-	let fake_block_number = parent.number.saturating_sub(bomb_delay as u64 - 1);
+	let fake_block_number = parent_number.saturating_sub(bomb_delay as u64 - 1);
 	let period_count = fake_block_number / EXP_DIFFICULTY_PERIOD;
 
 	// If period_count < 2, exp is fractional and we can skip adding it
@@ -118,6 +123,7 @@ pub fn calc_difficulty(
 mod tests {
 
 	use super::*;
+	use crate::header::Header;
 	use ethereum_types::H256;
 	use serde::{Deserialize, Deserializer};
 	use sp_std::convert::TryInto;
@@ -199,7 +205,14 @@ mod tests {
 				parent.difficulty = test_case.parent_difficulty;
 				parent.ommers_hash = test_case.parent_uncles;
 
-				let difficulty = calc_difficulty(&$config, test_case.current_timestamp, &parent);
+				let difficulty = calc_difficulty(
+					&$config,
+					test_case.current_timestamp,
+					parent.number,
+					parent.timestamp,
+					parent.difficulty,
+					parent.has_ommers(),
+				);
 				if $config.byzantium_fork_block > test_case.current_block_number {
 					assert_eq!(
 						difficulty,
@@ -17,6 +17,10 @@ impl Receipt {
 		self.logs.iter().find(|&l| l == log).is_some()
 	}
 
+	pub fn log_index(&self, log: &Log) -> Option<usize> {
+		self.logs.iter().position(|l| l == log)
+	}
+
 	fn decode_list(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
 		let mut iter = rlp.iter();
 
@@ -1,37 +1,170 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{dispatch::DispatchResult, parameter_types, PalletId};
-use sp_core::H160;
-use sp_std::marker::PhantomData;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchError, GetCallName},
+	parameter_types,
+	traits::{Get, PalletInfoAccess},
+	PalletId,
+};
+use scale_info::TypeInfo;
+use sp_core::{RuntimeDebug, H160};
+use sp_runtime::traits::AccountIdConversion;
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use snowbridge_core::{ChannelId, MessageOutcome};
+
+/// Outbound channel pallets which channels can be registered with.
+///
+/// Any number of channels can be registered with each of them. Supporting another outbound
+/// channel pallet means adding a variant here and routing it in [`OutboundRouter`].
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum OutboundChannelPallet {
+	/// Messages are submitted by apps, which are principals of the channel.
+	Basic,
+	/// Messages are submitted by senders, who pay the delivery fee.
+	Incentivized,
+}
 
-use snowbridge_core::ChannelId;
 pub struct OutboundRouter<T>(PhantomData<T>);
 
 impl<T> snowbridge_core::OutboundRouter<T::AccountId> for OutboundRouter<T>
 where
-	T: basic_channel::outbound::Config + incentivized_channel::outbound::Config,
+	T: channel_registry::Config<Outbound = OutboundChannelPallet>
+		+ basic_channel::outbound::Config
+		+ incentivized_channel::outbound::Config,
 {
 	fn submit(
 		channel_id: ChannelId,
-		who: &T::AccountId,
+		app: &T::AccountId,
+		sender: &T::AccountId,
 		target: H160,
 		payload: &[u8],
-	) -> DispatchResult {
-		match channel_id {
-			ChannelId::Basic => basic_channel::outbound::Pallet::<T>::submit(who, target, payload),
-			ChannelId::Incentivized => {
-				incentivized_channel::outbound::Pallet::<T>::submit(who, target, payload)
+		expires_at: Option<u64>,
+	) -> Result<u64, DispatchError> {
+		match channel_registry::Pallet::<T>::outbound(channel_id)? {
+			OutboundChannelPallet::Basic => {
+				basic_channel::outbound::Pallet::<T>::submit(app, target, payload, expires_at)
+			},
+			OutboundChannelPallet::Incentivized => {
+				incentivized_channel::outbound::Pallet::<T>::submit(
+					sender, target, payload, expires_at,
+				)
 			},
 		}
 	}
+
+	fn take_outcome(
+		channel_id: ChannelId,
+		message_id: u64,
+	) -> Result<Option<MessageOutcome>, DispatchError> {
+		Ok(match channel_registry::Pallet::<T>::outbound(channel_id)? {
+			OutboundChannelPallet::Basic => {
+				basic_channel::outbound::Pallet::<T>::take_outcome(message_id)
+			},
+			OutboundChannelPallet::Incentivized => {
+				incentivized_channel::outbound::Pallet::<T>::take_outcome(message_id)
+			},
+		})
+	}
+}
+
+/// The built-in channels, with the channel contracts which the inbound channels accepted messages
+/// from before the contracts were looked up in the channel registry.
+pub struct InitialChannels<T>(PhantomData<T>);
+
+impl<T> Get<Vec<(ChannelId, OutboundChannelPallet, H160)>> for InitialChannels<T>
+where
+	T: basic_channel::inbound::Config + incentivized_channel::inbound::Config,
+{
+	fn get() -> Vec<(ChannelId, OutboundChannelPallet, H160)> {
+		vec![
+			(
+				ChannelId::BASIC,
+				OutboundChannelPallet::Basic,
+				basic_channel::inbound::SourceChannel::<T>::get(),
+			),
+			(
+				ChannelId::INCENTIVIZED,
+				OutboundChannelPallet::Incentivized,
+				incentivized_channel::inbound::SourceChannel::<T>::get(),
+			),
+		]
+	}
+}
+
+/// The calls which the app contracts dispatch, by the address of the contract, pallet index and
+/// call index: `EthApp.mint`, `Erc20App.mint`, `Erc20App.create` and `DotApp.unlock`.
+pub fn app_calls<T>(eth_app: H160, erc20_app: H160, dot_app: H160) -> Vec<(H160, u8, u8)>
+where
+	T: eth_app::Config + erc20_app::Config + dot_app::Config,
+{
+	vec![
+		app_call::<eth_app::Pallet<T>, eth_app::Call<T>>(eth_app, "mint"),
+		app_call::<erc20_app::Pallet<T>, erc20_app::Call<T>>(erc20_app, "mint"),
+		app_call::<erc20_app::Pallet<T>, erc20_app::Call<T>>(erc20_app, "create"),
+		app_call::<dot_app::Pallet<T>, dot_app::Call<T>>(dot_app, "unlock"),
+	]
+	.into_iter()
+	.flatten()
+	.collect()
+}
+
+/// Calls are indexed in the order in which they are declared in their pallet.
+fn app_call<P: PalletInfoAccess, C: GetCallName>(
+	source: H160,
+	name: &str,
+) -> Option<(H160, u8, u8)> {
+	let call_index = C::get_call_names().iter().position(|call| *call == name)?;
+	Some((source, P::index() as u8, call_index as u8))
+}
+
+/// The calls which the app contracts, at the addresses stored by the app pallets, dispatch.
+pub struct AppCalls<T>(PhantomData<T>);
+
+impl<T> Get<Vec<(H160, u8, u8)>> for AppCalls<T>
+where
+	T: eth_app::Config + erc20_app::Config + dot_app::Config,
+{
+	fn get() -> Vec<(H160, u8, u8)> {
+		app_calls::<T>(
+			eth_app::Pallet::<T>::address(),
+			erc20_app::Pallet::<T>::address(),
+			dot_app::Pallet::<T>::address(),
+		)
+	}
+}
+
+/// The accounts which submit messages to the basic outbound channel: those of the app pallets,
+/// and the account which the dispatch pallet acknowledges messages from.
+pub struct AppAccounts<T>(PhantomData<T>);
+
+impl<T> Get<Vec<T::AccountId>> for AppAccounts<T>
+where
+	T: eth_app::Config + erc20_app::Config + dot_app::Config,
+{
+	fn get() -> Vec<T::AccountId> {
+		vec![
+			<T as eth_app::Config>::PalletId::get().into_account(),
+			<T as erc20_app::Config>::PalletId::get().into_account(),
+			<T as dot_app::Config>::PalletId::get().into_account(),
+			DispatchPalletId::get().into_account(),
+		]
+	}
 }
 
 parameter_types! {
 	pub const MaxMessagePayloadSize: u32 = 256;
 	pub const MaxMessagesPerCommit: u32 = 20;
+	pub const MaxQueuedMessages: u32 = 100;
 }
 
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"s/treasy");
 	pub const DotPalletId: PalletId = PalletId(*b"s/dotapp");
+	pub const DispatchPalletId: PalletId = PalletId(*b"dispatch");
 }
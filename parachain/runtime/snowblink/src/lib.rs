@@ -12,7 +12,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata, U256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, Keccak256},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult,
 };
 
@@ -37,7 +37,7 @@ pub use frame_support::{
 	},
 	PalletId, StorageValue,
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use pallet_transaction_payment::FeeDetails;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -46,7 +46,9 @@ pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{traits::AccountIdConversion, Perbill, Permill};
 
 use dispatch::EnsureEthereumAccount;
-pub use snowbridge_core::{ChannelId, MessageId};
+pub use snowbridge_core::{
+	BridgeComponent, ChannelId, MessageId, OutboundChannelState, QueuedMessage,
+};
 
 pub use ethereum_light_client::{EthereumDifficultyConfig, EthereumHeader};
 
@@ -66,8 +68,10 @@ use xcm_builder::{
 use snowbridge_xcm_support::XcmAssetTransferer;
 use xcm_executor::{traits::JustTry, Config, XcmExecutor};
 
+pub use runtime_common::{app_calls, DispatchPalletId, DotPalletId, OutboundChannelPallet};
 use runtime_common::{
-	DotPalletId, MaxMessagePayloadSize, MaxMessagesPerCommit, OutboundRouter, TreasuryPalletId,
+	AppAccounts, AppCalls, InitialChannels, MaxMessagePayloadSize, MaxMessagesPerCommit,
+	MaxQueuedMessages, OutboundRouter, TreasuryPalletId,
 };
 
 pub use runtime_primitives::{AccountId, Address, Balance, BlockNumber, Hash, Index, Signature};
@@ -509,6 +513,27 @@ impl pallet_membership::Config<LocalCouncilMembershipInstance> for Runtime {
 	type WeightInfo = pallet_membership::weights::SubstrateWeight<Self>;
 }
 
+parameter_types! {
+	pub const FeeFeederMaxMembers: u32 = 4;
+}
+
+type FeeFeederMembershipInstance = pallet_membership::Instance2;
+impl pallet_membership::Config<FeeFeederMembershipInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfLocalCouncil;
+	type RemoveOrigin = EnsureRootOrHalfLocalCouncil;
+	type SwapOrigin = EnsureRootOrHalfLocalCouncil;
+	type ResetOrigin = EnsureRootOrHalfLocalCouncil;
+	type PrimeOrigin = EnsureRootOrHalfLocalCouncil;
+	type MembershipInitialized = ();
+	type MembershipChanged = ();
+	type MaxMembers = FeeFeederMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Self>;
+}
+
+/// Accounts which feed the gas price and exchange rate used to quote delivery fees.
+type EnsureFeeFeeder = EnsureSignedBy<FeeFeederMembership, AccountId>;
+
 // Assets
 
 parameter_types! {
@@ -543,12 +568,27 @@ impl pallet_assets::Config for Runtime {
 
 impl snowbridge_asset_registry::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxDispatchWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 10;
+	pub const DispatchQueueSize: u32 = 100;
+	pub DispatchAccount: AccountId = DispatchPalletId::get().into_account();
+}
+
 impl dispatch::Config for Runtime {
 	type Origin = Origin;
 	type Event = Event;
 	type MessageId = MessageId;
 	type Call = Call;
 	type CallFilter = Everything;
+	type MaxDispatchWeight = MaxDispatchWeight;
+	type MaxQueueSize = DispatchQueueSize;
+	type DiscardOrigin = EnsureRootOrHalfLocalCouncil;
+	type AllowlistOrigin = EnsureRootOrHalfLocalCouncil;
+	type Acknowledger = dispatch::acknowledgement::OutboundAcknowledger<
+		Runtime,
+		OutboundRouter<Runtime>,
+		DispatchAccount,
+	>;
 }
 
 use basic_channel::{inbound as basic_channel_inbound, outbound as basic_channel_outbound};
@@ -556,10 +596,34 @@ use incentivized_channel::{
 	inbound as incentivized_channel_inbound, outbound as incentivized_channel_outbound,
 };
 
+type EnsureRootOrLocalCouncilMember = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureMember<AccountId, LocalCouncilInstance>,
+>;
+
+impl circuit_breaker::Config for Runtime {
+	type Event = Event;
+	// Any council member can pause the bridge, but resuming requires a council majority
+	type PauseOrigin = EnsureRootOrLocalCouncilMember;
+	type ResumeOrigin = EnsureRootOrHalfLocalCouncil;
+	type WeightInfo = circuit_breaker::weights::SnowbridgeWeight<Self>;
+}
+
+impl channel_registry::Config for Runtime {
+	type Event = Event;
+	type Outbound = OutboundChannelPallet;
+	type InitialChannels = InitialChannels<Runtime>;
+	type RegisterOrigin = EnsureRootOrHalfLocalCouncil;
+	type WeightInfo = channel_registry::weights::SnowbridgeWeight<Self>;
+}
+
 impl basic_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
 	type MessageDispatch = dispatch::Pallet<Runtime>;
+	type CircuitBreaker = CircuitBreaker;
+	type ChannelSource = ChannelRegistry;
+	type EthereumTime = EthereumLightClient;
 	type WeightInfo = ();
 }
 
@@ -568,7 +632,12 @@ impl basic_channel_outbound::Config for Runtime {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type SetPrincipalOrigin = EnsureRootOrHalfLocalCouncil;
+	type SetCommitmentVersionsOrigin = EnsureRootOrHalfLocalCouncil;
+	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type EthereumTime = EthereumLightClient;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = basic_channel::outbound::weights::SnowbridgeWeight<Self>;
 }
 
@@ -577,6 +646,11 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
 }
 
+parameter_types! {
+	pub const MaxExchangeRateAge: BlockNumber = DAYS;
+}
+
+// Fees are paid in wrapped DOT, which converts 1:1 into DOT
 pub struct FeeConverter;
 impl Convert<U256, Option<Balance>> for FeeConverter {
 	fn convert(amount: U256) -> Option<Balance> {
@@ -584,6 +658,15 @@ impl Convert<U256, Option<Balance>> for FeeConverter {
 	}
 }
 
+impl exchange_rate::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type MaxRateAge = MaxExchangeRateAge;
+	type FallbackConverter = FeeConverter;
+	type UpdateOrigin = EnsureRootOrHalfLocalCouncil;
+	type WeightInfo = exchange_rate::weights::SnowbridgeWeight<Self>;
+}
+
 impl incentivized_channel_inbound::Config for Runtime {
 	type Event = Event;
 	type Verifier = ethereum_light_client::Pallet<Runtime>;
@@ -591,8 +674,11 @@ impl incentivized_channel_inbound::Config for Runtime {
 	type Currency = Balances;
 	type SourceAccount = SourceAccount;
 	type TreasuryAccount = TreasuryAccount;
-	type FeeConverter = FeeConverter;
+	type FeeConverter = ExchangeRate;
 	type UpdateOrigin = EnsureRootOrHalfLocalCouncil;
+	type CircuitBreaker = CircuitBreaker;
+	type ChannelSource = ChannelRegistry;
+	type EthereumTime = EthereumLightClient;
 	type WeightInfo = incentivized_channel::inbound::weights::SnowbridgeWeight<Self>;
 }
 
@@ -601,8 +687,14 @@ impl incentivized_channel_outbound::Config for Runtime {
 	type Hashing = Keccak256;
 	type MaxMessagePayloadSize = MaxMessagePayloadSize;
 	type MaxMessagesPerCommit = MaxMessagesPerCommit;
+	type MaxQueuedMessages = MaxQueuedMessages;
 	type FeeCurrency = ItemOf<Assets, EtherAssetId, AccountId>;
 	type SetFeeOrigin = EnsureRootOrHalfLocalCouncil;
+	type SetCommitmentVersionsOrigin = EnsureRootOrHalfLocalCouncil;
+	type FeeFeederOrigin = EnsureFeeFeeder;
+	type Verifier = ethereum_light_client::Pallet<Runtime>;
+	type EthereumTime = EthereumLightClient;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = incentivized_channel::outbound::weights::SnowbridgeWeight<Self>;
 }
 
@@ -611,6 +703,7 @@ parameter_types! {
 	pub const DifficultyConfig: EthereumDifficultyConfig = EthereumDifficultyConfig::ropsten();
 	pub const VerifyPoW: bool = true;
 	pub const MaxHeadersForNumber: u32 = 100;
+	pub const LightClientUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
 
 impl ethereum_light_client::Config for Runtime {
@@ -619,12 +712,14 @@ impl ethereum_light_client::Config for Runtime {
 	type DifficultyConfig = DifficultyConfig;
 	type VerifyPoW = VerifyPoW;
 	type MaxHeadersForNumber = MaxHeadersForNumber;
+	type UnsignedPriority = LightClientUnsignedPriority;
 	type WeightInfo = ethereum_light_client::weights::SnowbridgeWeight<Self>;
 }
 
 parameter_types! {
 	pub const EtherAssetId: u128 = 0;
 	pub const EtherAppPalletId: PalletId = PalletId(*b"etherapp");
+	pub const TransferDelay: BlockNumber = DAYS;
 }
 
 impl eth_app::Config for Runtime {
@@ -633,6 +728,9 @@ impl eth_app::Config for Runtime {
 	type Asset = ItemOf<Assets, EtherAssetId, AccountId>;
 	type OutboundRouter = OutboundRouter<Runtime>;
 	type CallOrigin = EnsureEthereumAccount;
+	type LimitOrigin = EnsureRootOrHalfLocalCouncil;
+	type TransferDelay = TransferDelay;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = eth_app::weights::SnowbridgeWeight<Self>;
 	type XcmReserveTransfer = XcmAssetTransferer<Runtime>;
 }
@@ -646,9 +744,12 @@ impl erc20_app::Config for Runtime {
 	type Assets = Assets;
 	type OutboundRouter = OutboundRouter<Runtime>;
 	type CallOrigin = EnsureEthereumAccount;
+	type LimitOrigin = EnsureRootOrHalfLocalCouncil;
+	type TransferDelay = TransferDelay;
 	type XcmReserveTransfer = XcmAssetTransferer<Runtime>;
 	type PalletId = Erc20AppPalletId;
 	type NextAssetId = AssetRegistry;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = erc20_app::weights::SnowbridgeWeight<Self>;
 }
 
@@ -661,8 +762,11 @@ impl dot_app::Config for Runtime {
 	type Currency = Balances;
 	type OutboundRouter = OutboundRouter<Runtime>;
 	type CallOrigin = EnsureEthereumAccount;
+	type LimitOrigin = EnsureRootOrHalfLocalCouncil;
+	type TransferDelay = TransferDelay;
 	type PalletId = DotPalletId;
 	type Decimals = Decimals;
+	type CircuitBreaker = CircuitBreaker;
 	type WeightInfo = dot_app::weights::SnowbridgeWeight<Self>;
 }
 
@@ -743,16 +847,20 @@ construct_runtime!(
 
 		LocalCouncil: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 10,
 		LocalCouncilMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 11,
+		FeeFeederMembership: pallet_membership::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 34,
 
 		// Bridge Infrastructure
-		BasicInboundChannel: basic_channel_inbound::{Pallet, Call, Config, Storage, Event<T>} = 12,
+		BasicInboundChannel: basic_channel_inbound::{Pallet, Call, Storage, Event<T>} = 12,
 		BasicOutboundChannel: basic_channel_outbound::{Pallet, Call, Config<T>, Storage, Event<T>} = 13,
 		IncentivizedInboundChannel: incentivized_channel_inbound::{Pallet, Call, Config, Storage, Event<T>} = 14,
 		IncentivizedOutboundChannel: incentivized_channel_outbound::{Pallet, Call, Config<T>, Storage, Event<T>} = 15,
-		Dispatch: dispatch::{Pallet, Call, Storage, Event<T>, Origin} = 16,
-		EthereumLightClient: ethereum_light_client::{Pallet, Call, Config, Storage, Event<T>} = 17,
+		Dispatch: dispatch::{Pallet, Call, Config, Storage, Event<T>, Origin} = 16,
+		EthereumLightClient: ethereum_light_client::{Pallet, Call, Config, Storage, Event<T>, ValidateUnsigned} = 17,
 		Assets: pallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>} = 18,
 		AssetRegistry: snowbridge_asset_registry::{Pallet, Storage, Config} = 19,
+		ExchangeRate: exchange_rate::{Pallet, Call, Config<T>, Storage, Event<T>} = 31,
+		CircuitBreaker: circuit_breaker::{Pallet, Call, Storage, Event<T>} = 32,
+		ChannelRegistry: channel_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 33,
 
 		// XCM
 		XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 21,
@@ -809,6 +917,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		dispatch::migration::v1::SeedAllowedCalls<Runtime, AppCalls<Runtime>>,
+		basic_channel_outbound::migration::v1::AddPrincipals<Runtime, AppAccounts<Runtime>>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -912,6 +1024,34 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl circuit_breaker_runtime_api::CircuitBreakerApi<Block> for Runtime {
+		fn is_paused(component: BridgeComponent) -> bool {
+			CircuitBreaker::is_paused(component)
+		}
+
+		fn paused_components() -> Vec<BridgeComponent> {
+			CircuitBreaker::paused_components()
+		}
+	}
+
+	impl outbound_channel_runtime_api::OutboundChannelApi<Block, BlockNumber> for Runtime {
+		fn queued_messages(channel_id: ChannelId) -> Option<Vec<QueuedMessage>> {
+			match ChannelRegistry::outbound(channel_id).ok()? {
+				OutboundChannelPallet::Basic => Some(BasicOutboundChannel::queued_messages()),
+				OutboundChannelPallet::Incentivized =>
+					Some(IncentivizedOutboundChannel::queued_messages()),
+			}
+		}
+
+		fn channel_state(channel_id: ChannelId) -> Option<OutboundChannelState<BlockNumber>> {
+			match ChannelRegistry::outbound(channel_id).ok()? {
+				OutboundChannelPallet::Basic => Some(BasicOutboundChannel::channel_state()),
+				OutboundChannelPallet::Incentivized =>
+					Some(IncentivizedOutboundChannel::channel_state()),
+			}
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -938,6 +1078,9 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, basic_channel::outbound, BasicOutboundChannel);
 			list_benchmark!(list, extra, incentivized_channel::inbound, IncentivizedInboundChannel);
 			list_benchmark!(list, extra, incentivized_channel::outbound, IncentivizedOutboundChannel);
+			list_benchmark!(list, extra, exchange_rate, ExchangeRate);
+			list_benchmark!(list, extra, circuit_breaker, CircuitBreaker);
+			list_benchmark!(list, extra, channel_registry, ChannelRegistry);
 			list_benchmark!(list, extra, dot_app, DotAppBench::<Runtime>);
 			list_benchmark!(list, extra, erc20_app, Erc20AppBench::<Runtime>);
 			list_benchmark!(list, extra, eth_app, EthAppBench::<Runtime>);
@@ -991,6 +1134,9 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, basic_channel::outbound, BasicOutboundChannel);
 			add_benchmark!(params, batches, incentivized_channel::inbound, IncentivizedInboundChannel);
 			add_benchmark!(params, batches, incentivized_channel::outbound, IncentivizedOutboundChannel);
+			add_benchmark!(params, batches, exchange_rate, ExchangeRate);
+			add_benchmark!(params, batches, circuit_breaker, CircuitBreaker);
+			add_benchmark!(params, batches, channel_registry, ChannelRegistry);
 			add_benchmark!(params, batches, dot_app, DotAppBench::<Runtime>);
 			add_benchmark!(params, batches, erc20_app, Erc20AppBench::<Runtime>);
 			add_benchmark!(params, batches, eth_app, EthAppBench::<Runtime>);
@@ -1,8 +1,11 @@
 use cumulus_primitives_core::ParaId;
 use sc_service::ChainType;
-use snowbase_runtime::{AccountId, AuraId, EtherAppPalletId, GenesisConfig, WASM_BINARY};
-use sp_core::sr25519;
-use sp_runtime::{traits::AccountIdConversion, Perbill};
+use snowbase_runtime::{
+	app_calls, AccountId, AuraId, ChannelId, DispatchPalletId, DotPalletId, Erc20AppPalletId,
+	EtherAppPalletId, GenesisConfig, OutboundChannelPallet, Runtime, WASM_BINARY,
+};
+use sp_core::{sr25519, H160};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128, Perbill};
 
 use super::{get_account_id_from_seed, get_collator_keys_from_seed, Extensions};
 
@@ -67,6 +70,12 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	para_id: ParaId,
 ) -> GenesisConfig {
+	// Placeholders for the addresses of the app contracts, which are replaced once the contracts
+	// are deployed.
+	let dot_app = H160::repeat_byte(1);
+	let eth_app = H160::repeat_byte(2);
+	let erc20_app = H160::repeat_byte(3);
+
 	GenesisConfig {
 		system: snowbase_runtime::SystemConfig {
 			// Add Wasm runtime to storage.
@@ -89,20 +98,44 @@ fn testnet_genesis(
 			],
 			phantom: Default::default(),
 		},
-		basic_inbound_channel: snowbase_runtime::BasicInboundChannelConfig {
-			source_channel: Default::default(),
+		fee_feeder_membership: snowbase_runtime::FeeFeederMembershipConfig {
+			members: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+			phantom: Default::default(),
+		},
+		dispatch: snowbase_runtime::DispatchConfig {
+			allowed_calls: app_calls::<Runtime>(eth_app, erc20_app, dot_app),
 		},
 		basic_outbound_channel: snowbase_runtime::BasicOutboundChannelConfig {
-			principal: Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+			// Apps submit messages to the basic channel from their own accounts
+			principals: vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				DotPalletId::get().into_account(),
+				EtherAppPalletId::get().into_account(),
+				Erc20AppPalletId::get().into_account(),
+				DispatchPalletId::get().into_account(),
+			],
 			interval: 1,
+			target_channel: Default::default(),
 		},
 		incentivized_inbound_channel: snowbase_runtime::IncentivizedInboundChannelConfig {
-			source_channel: Default::default(),
 			reward_fraction: Perbill::from_percent(80),
 		},
+		channel_registry: snowbase_runtime::ChannelRegistryConfig {
+			channels: vec![
+				(ChannelId::BASIC, OutboundChannelPallet::Basic, Default::default()),
+				(ChannelId::INCENTIVIZED, OutboundChannelPallet::Incentivized, Default::default()),
+			],
+		},
+		exchange_rate: snowbase_runtime::ExchangeRateConfig {
+			// Fees are paid in wrapped DOT, which converts 1:1 into DOT
+			rate: Some(FixedU128::saturating_from_rational(1, 1_000_000)),
+			feeders: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+		},
 		incentivized_outbound_channel: snowbase_runtime::IncentivizedOutboundChannelConfig {
 			fee: u128::from_str_radix("10000000000000000", 10).unwrap(), // 0.01 SnowEther
 			interval: 1,
+			gas_config: Default::default(),
+			target_channel: Default::default(),
 		},
 		assets: snowbase_runtime::AssetsConfig {
 			// Initialize the wrapped Ether asset
@@ -116,15 +149,9 @@ fn testnet_genesis(
 			initial_difficulty: Default::default(),
 		},
 		ethereum_beacon_client: snowbase_runtime::EthereumBeaconClientConfig {},
-		dot_app: snowbase_runtime::DotAppConfig {
-			address: Default::default(),
-		},
-		eth_app: snowbase_runtime::EthAppConfig {
-			address: Default::default(),
-		},
-		erc_20_app: snowbase_runtime::Erc20AppConfig {
-			address: Default::default(),
-		},
+		dot_app: snowbase_runtime::DotAppConfig { address: dot_app },
+		eth_app: snowbase_runtime::EthAppConfig { address: eth_app },
+		erc_20_app: snowbase_runtime::Erc20AppConfig { address: erc20_app },
 		parachain_info: snowbase_runtime::ParachainInfoConfig { parachain_id: para_id },
 		collator_selection: snowbase_runtime::CollatorSelectionConfig {
 			invulnerables: invulnerables.iter().cloned().map(|(acc, _)| acc).collect(),
@@ -1,8 +1,11 @@
 use cumulus_primitives_core::ParaId;
 use sc_service::ChainType;
-use snowblink_runtime::{AccountId, AuraId, EtherAppPalletId, GenesisConfig, WASM_BINARY};
+use snowblink_runtime::{
+	AccountId, AuraId, ChannelId, DispatchPalletId, DotPalletId, Erc20AppPalletId,
+	EtherAppPalletId, GenesisConfig, OutboundChannelPallet, WASM_BINARY,
+};
 use sp_core::sr25519;
-use sp_runtime::{traits::AccountIdConversion, Perbill};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128, Perbill};
 
 use super::{get_account_id_from_seed, get_collator_keys_from_seed, Extensions};
 
@@ -89,20 +92,42 @@ fn testnet_genesis(
 			],
 			phantom: Default::default(),
 		},
-		basic_inbound_channel: snowblink_runtime::BasicInboundChannelConfig {
-			source_channel: Default::default(),
+		fee_feeder_membership: snowblink_runtime::FeeFeederMembershipConfig {
+			members: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+			phantom: Default::default(),
 		},
+		dispatch: snowblink_runtime::DispatchConfig { allowed_calls: vec![] },
 		basic_outbound_channel: snowblink_runtime::BasicOutboundChannelConfig {
-			principal: Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+			// Apps submit messages to the basic channel from their own accounts
+			principals: vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				DotPalletId::get().into_account(),
+				EtherAppPalletId::get().into_account(),
+				Erc20AppPalletId::get().into_account(),
+				DispatchPalletId::get().into_account(),
+			],
 			interval: 1,
+			target_channel: Default::default(),
 		},
 		incentivized_inbound_channel: snowblink_runtime::IncentivizedInboundChannelConfig {
-			source_channel: Default::default(),
 			reward_fraction: Perbill::from_percent(80),
 		},
+		channel_registry: snowblink_runtime::ChannelRegistryConfig {
+			channels: vec![
+				(ChannelId::BASIC, OutboundChannelPallet::Basic, Default::default()),
+				(ChannelId::INCENTIVIZED, OutboundChannelPallet::Incentivized, Default::default()),
+			],
+		},
+		exchange_rate: snowblink_runtime::ExchangeRateConfig {
+			// Fees are paid in wrapped DOT, which converts 1:1 into DOT
+			rate: Some(FixedU128::saturating_from_rational(1, 1_000_000)),
+			feeders: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+		},
 		incentivized_outbound_channel: snowblink_runtime::IncentivizedOutboundChannelConfig {
 			fee: u128::from_str_radix("10000000000000000", 10).unwrap(), // 0.01 SnowEther
 			interval: 1,
+			gas_config: Default::default(),
+			target_channel: Default::default(),
 		},
 		assets: snowblink_runtime::AssetsConfig {
 			// Initialize the wrapped Ether asset
@@ -1,9 +1,12 @@
 use cumulus_primitives_core::ParaId;
 use hex_literal::hex;
 use sc_service::ChainType;
-use snowbridge_runtime::{AccountId, AuraId, EtherAppPalletId, GenesisConfig, WASM_BINARY};
+use snowbridge_runtime::{
+	AccountId, AuraId, ChannelId, DispatchPalletId, DotPalletId, Erc20AppPalletId,
+	EtherAppPalletId, GenesisConfig, OutboundChannelPallet, WASM_BINARY,
+};
 use sp_core::sr25519;
-use sp_runtime::{traits::AccountIdConversion, Perbill};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128, Perbill};
 
 use super::{get_account_id_from_seed, get_collator_keys_from_seed, Extensions};
 
@@ -90,20 +93,42 @@ fn testnet_genesis(
 			],
 			phantom: Default::default(),
 		},
-		basic_inbound_channel: snowbridge_runtime::BasicInboundChannelConfig {
-			source_channel: Default::default(),
+		fee_feeder_membership: snowbridge_runtime::FeeFeederMembershipConfig {
+			members: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+			phantom: Default::default(),
 		},
+		dispatch: snowbridge_runtime::DispatchConfig { allowed_calls: vec![] },
 		basic_outbound_channel: snowbridge_runtime::BasicOutboundChannelConfig {
-			principal: get_account_id_from_seed::<sr25519::Public>("Alice"),
+			// Apps submit messages to the basic channel from their own accounts
+			principals: vec![
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				DotPalletId::get().into_account(),
+				EtherAppPalletId::get().into_account(),
+				Erc20AppPalletId::get().into_account(),
+				DispatchPalletId::get().into_account(),
+			],
 			interval: 1,
+			target_channel: Default::default(),
 		},
 		incentivized_inbound_channel: snowbridge_runtime::IncentivizedInboundChannelConfig {
-			source_channel: Default::default(),
 			reward_fraction: Perbill::from_percent(80),
 		},
+		channel_registry: snowbridge_runtime::ChannelRegistryConfig {
+			channels: vec![
+				(ChannelId::BASIC, OutboundChannelPallet::Basic, Default::default()),
+				(ChannelId::INCENTIVIZED, OutboundChannelPallet::Incentivized, Default::default()),
+			],
+		},
+		exchange_rate: snowbridge_runtime::ExchangeRateConfig {
+			// Fees are paid in wrapped DOT, which converts 1:1 into DOT
+			rate: Some(FixedU128::saturating_from_rational(1, 100_000_000)),
+			feeders: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+		},
 		incentivized_outbound_channel: snowbridge_runtime::IncentivizedOutboundChannelConfig {
 			fee: u128::from_str_radix("10000000000000000", 10).unwrap(), // 0.01 SnowEther
 			interval: 1,
+			gas_config: Default::default(),
+			target_channel: Default::default(),
 		},
 		assets: snowbridge_runtime::AssetsConfig {
 			// Initialize the wrapped Ether asset
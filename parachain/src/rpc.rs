@@ -7,9 +7,9 @@
 
 use std::sync::Arc;
 
-use snowbridge_runtime_primitives::{Block, AccountId, Balance, Index as Nonce};
+use snowbridge_runtime_primitives::{Block, AccountId, Balance, BlockNumber, Index as Nonce};
 
-use sc_client_api::AuxStore;
+use sc_client_api::{AuxStore, Backend};
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -20,9 +20,11 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
 /// Full client dependencies
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
+	/// The backend instance to use.
+	pub backend: Arc<B>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
@@ -30,7 +32,7 @@ pub struct FullDeps<C, P> {
 }
 
 /// Instantiate all RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> RpcExtension
+pub fn create_full<C, P, B>(deps: FullDeps<C, P, B>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
@@ -41,17 +43,28 @@ where
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: snowbridge_outbound_channel_rpc::OutboundChannelRuntimeApi<Block, BlockNumber>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
+	B: Backend<Block> + Send + Sync + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use snowbridge_basic_channel_rpc::{BasicChannel, BasicChannelApi};
+	use snowbridge_incentivized_channel_rpc::{IncentivizedChannel, IncentivizedChannelApi};
+	use snowbridge_outbound_channel_rpc::{OutboundChannel, OutboundChannelApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, backend, pool, deny_unsafe } = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
-	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client)));
+	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
+	io.extend_with(OutboundChannelApi::to_delegate(OutboundChannel::new(client)));
+
+	if let Some(storage) = backend.offchain_storage() {
+		io.extend_with(BasicChannelApi::to_delegate(BasicChannel::new(storage.clone())));
+		io.extend_with(IncentivizedChannelApi::to_delegate(IncentivizedChannel::new(storage)));
+	}
 
 	io
 }
@@ -24,7 +24,7 @@ use substrate_prometheus_endpoint::Registry;
 
 use polkadot_service::CollatorPair;
 
-use snowbridge_runtime_primitives::{Block, Hash, AccountId, Balance, Index as Nonce};
+use snowbridge_runtime_primitives::{Block, BlockNumber, Hash, AccountId, Balance, Index as Nonce};
 
 #[cfg(feature = "snowbridge-native")]
 pub struct SnowbridgeRuntimeExecutor;
@@ -84,6 +84,7 @@ pub trait RuntimeApiCollection:
 	+ cumulus_primitives_core::CollectCollationInfo<Block>
 	+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 	+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+	+ snowbridge_outbound_channel_rpc::OutboundChannelRuntimeApi<Block, BlockNumber>
 	+ sp_consensus_aura::AuraApi<Block, sp_consensus_aura::sr25519::AuthorityId>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
@@ -103,6 +104,7 @@ where
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ snowbridge_outbound_channel_rpc::OutboundChannelRuntimeApi<Block, BlockNumber>
 		+ sp_consensus_aura::AuraApi<Block, sp_consensus_aura::sr25519::AuthorityId>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -333,11 +335,13 @@ where
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let transaction_pool = transaction_pool.clone();
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
+				backend: backend.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
 			};
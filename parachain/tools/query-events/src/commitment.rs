@@ -0,0 +1,268 @@
+//! Decoding and verification of `Committed` and `VersionedCommitted` events emitted by the
+//! outbound channels.
+use codec::{Decode, Encode};
+use serde::Serialize;
+use serde_hex::{SerHexSeq, StrictPfx};
+use sp_core::{ConstU32, H160, H256};
+use sp_runtime::traits::Keccak256;
+
+type BasicMessageBundle = snowbridge_basic_channel::outbound::MessageBundle<
+	ConstU32<{ u32::MAX }>,
+	ConstU32<{ u32::MAX }>,
+>;
+type BasicLegacyMessageBundle = snowbridge_basic_channel::outbound::LegacyMessageBundle<
+	ConstU32<{ u32::MAX }>,
+	ConstU32<{ u32::MAX }>,
+>;
+type IncentivizedMessageBundle = snowbridge_incentivized_channel::outbound::MessageBundle<
+	ConstU32<{ u32::MAX }>,
+	ConstU32<{ u32::MAX }>,
+>;
+type IncentivizedLegacyMessageBundle =
+	snowbridge_incentivized_channel::outbound::LegacyMessageBundle<
+		ConstU32<{ u32::MAX }>,
+		ConstU32<{ u32::MAX }>,
+	>;
+
+const BASIC_PALLET: &str = "BasicOutboundChannel";
+const INCENTIVIZED_PALLET: &str = "IncentivizedOutboundChannel";
+const COMMITTED_EVENT: &str = "Committed";
+const VERSIONED_COMMITTED_EVENT: &str = "VersionedCommitted";
+
+/// A commitment found in a block, as read by the relayer.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+	/// Channel which emitted the commitment, as in `ChannelId`.
+	pub id: u8,
+	#[serde(with = "SerHexSeq::<StrictPfx>")]
+	pub hash: Vec<u8>,
+	/// SCALE-encoded message bundle, including its version.
+	#[serde(with = "SerHexSeq::<StrictPfx>")]
+	pub data: Vec<u8>,
+	pub version: u8,
+	pub nonce: u64,
+	pub fee: u128,
+	pub messages: Vec<ItemMessage>,
+}
+
+/// A message in a commitment.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemMessage {
+	pub id: u64,
+	pub target: H160,
+	pub fee: u128,
+	/// Ethereum timestamp after which the message is not dispatched, if any.
+	pub expires_at: Option<u64>,
+	#[serde(with = "SerHexSeq::<StrictPfx>")]
+	pub payload: Vec<u8>,
+	/// ABI-encoded message, which is a leaf of the commitment.
+	#[serde(with = "SerHexSeq::<StrictPfx>")]
+	pub leaf: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+	/// The event data is not a commitment hash followed by a message bundle.
+	Decode(&'static str),
+	/// The commitment hash in the event differs from the one computed from its bundle.
+	HashMismatch { channel_id: u8, expected: H256, computed: H256 },
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Error::Decode(what) => write!(f, "could not decode {}", what),
+			Error::HashMismatch { channel_id, expected, computed } => write!(
+				f,
+				"commitment hash {:?} on channel {} does not match computed hash {:?}",
+				expected, channel_id, computed
+			),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// Decode the commitment in an event, verifying its hash.
+///
+/// Returns `None` for events other than the `Committed` and `VersionedCommitted` events of the
+/// outbound channels. Bundles in `Committed` events are in the layout of
+/// [`snowbridge_core::CommitmentVersion::V0`].
+pub fn decode_event(pallet: &str, variant: &str, mut data: &[u8]) -> Result<Option<Item>, Error> {
+	let versioned = match variant {
+		COMMITTED_EVENT => false,
+		VERSIONED_COMMITTED_EVENT => true,
+		_ => return Ok(None),
+	};
+
+	let item = match pallet {
+		BASIC_PALLET => {
+			let (hash, bundle) = if versioned {
+				<(H256, BasicMessageBundle)>::decode(&mut data)
+			} else {
+				<(H256, BasicLegacyMessageBundle)>::decode(&mut data)
+					.map(|(hash, bundle)| (hash, bundle.into()))
+			}
+			.map_err(|_| Error::Decode("basic channel commitment"))?;
+			verify(bundle.source_channel_id, hash, bundle.commitment_hash::<Keccak256>())?;
+			let messages = bundle
+				.messages
+				.iter()
+				.zip(bundle.leaves())
+				.map(|(message, leaf)| ItemMessage {
+					id: message.id,
+					target: message.target,
+					fee: 0,
+					expires_at: message.expires_at,
+					payload: message.payload.to_vec(),
+					leaf,
+				})
+				.collect();
+			Item {
+				id: bundle.source_channel_id,
+				hash: hash.as_bytes().to_vec(),
+				data: bundle.encode(),
+				version: bundle.version as u8,
+				nonce: bundle.nonce,
+				fee: 0,
+				messages,
+			}
+		},
+		INCENTIVIZED_PALLET => {
+			let (hash, bundle) = if versioned {
+				<(H256, IncentivizedMessageBundle)>::decode(&mut data)
+			} else {
+				<(H256, IncentivizedLegacyMessageBundle)>::decode(&mut data)
+					.map(|(hash, bundle)| (hash, bundle.into()))
+			}
+			.map_err(|_| Error::Decode("incentivized channel commitment"))?;
+			verify(bundle.source_channel_id, hash, bundle.commitment_hash::<Keccak256>())?;
+			let messages = bundle
+				.messages
+				.iter()
+				.zip(bundle.leaves())
+				.map(|(message, leaf)| ItemMessage {
+					id: message.id,
+					target: message.target,
+					fee: message.fee,
+					expires_at: message.expires_at,
+					payload: message.payload.to_vec(),
+					leaf,
+				})
+				.collect();
+			Item {
+				id: bundle.source_channel_id,
+				hash: hash.as_bytes().to_vec(),
+				data: bundle.encode(),
+				version: bundle.version as u8,
+				nonce: bundle.nonce,
+				fee: bundle.fee,
+				messages,
+			}
+		},
+		_ => return Ok(None),
+	};
+
+	Ok(Some(item))
+}
+
+/// Check the commitment hash in an event against the one computed from its bundle.
+fn verify(channel_id: u8, expected: H256, computed: H256) -> Result<(), Error> {
+	if computed != expected {
+		return Err(Error::HashMismatch { channel_id, expected, computed })
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use snowbridge_core::CommitmentVersion;
+
+	fn basic_bundle(version: CommitmentVersion) -> BasicMessageBundle {
+		BasicMessageBundle {
+			version,
+			source_channel_id: 0,
+			nonce: 1,
+			messages: vec![snowbridge_basic_channel::outbound::Message {
+				id: 7,
+				target: H160::repeat_byte(1),
+				expires_at: None,
+				payload: vec![1, 2, 3].try_into().unwrap(),
+			}]
+			.try_into()
+			.unwrap(),
+		}
+	}
+
+	#[test]
+	fn decodes_basic_commitment() {
+		let bundle = basic_bundle(CommitmentVersion::V0);
+		let hash = bundle.commitment_hash::<Keccak256>();
+
+		let data = (hash, bundle.to_legacy()).encode();
+		let item = decode_event(BASIC_PALLET, COMMITTED_EVENT, &data).unwrap().unwrap();
+
+		assert_eq!(item.id, 0);
+		assert_eq!(item.hash, hash.as_bytes());
+		assert_eq!(item.data, bundle.encode());
+		assert_eq!(item.nonce, 1);
+		assert_eq!(item.messages.len(), 1);
+		assert_eq!(item.messages[0].id, 7);
+		assert_eq!(item.messages[0].payload, vec![1, 2, 3]);
+		assert_eq!(item.messages[0].leaf, bundle.leaves()[0]);
+	}
+
+	#[test]
+	fn decodes_incentivized_commitment() {
+		let bundle = IncentivizedMessageBundle {
+			version: CommitmentVersion::V2,
+			source_channel_id: 1,
+			nonce: 3,
+			fee: 20,
+			messages: vec![snowbridge_incentivized_channel::outbound::Message {
+				id: 2,
+				target: H160::repeat_byte(2),
+				fee: 20,
+				expires_at: None,
+				payload: vec![4, 5].try_into().unwrap(),
+			}]
+			.try_into()
+			.unwrap(),
+		};
+		let hash = bundle.commitment_hash::<Keccak256>();
+
+		let data = (hash, bundle).encode();
+		let item = decode_event(INCENTIVIZED_PALLET, VERSIONED_COMMITTED_EVENT, &data)
+			.unwrap()
+			.unwrap();
+
+		assert_eq!(item.id, 1);
+		assert_eq!(item.version, 2);
+		assert_eq!(item.fee, 20);
+		assert_eq!(item.messages[0].fee, 20);
+	}
+
+	#[test]
+	fn rejects_mismatched_hash() {
+		let bundle = basic_bundle(CommitmentVersion::V0);
+		let hash = basic_bundle(CommitmentVersion::V2).commitment_hash::<Keccak256>();
+
+		assert_eq!(
+			decode_event(BASIC_PALLET, VERSIONED_COMMITTED_EVENT, &(hash, bundle.clone()).encode()),
+			Err(Error::HashMismatch {
+				channel_id: 0,
+				expected: hash,
+				computed: bundle.commitment_hash::<Keccak256>(),
+			})
+		);
+	}
+
+	#[test]
+	fn ignores_other_events() {
+		assert_eq!(decode_event(BASIC_PALLET, "MessageAccepted", &[]), Ok(None));
+		assert_eq!(decode_event("System", COMMITTED_EVENT, &[]), Ok(None));
+	}
+}
@@ -1,89 +1,163 @@
-#[subxt::subxt(runtime_metadata_path = "metadata.scale")]
-pub mod runtime {}
+//! Exports the commitments of the outbound channels, with their messages.
+//!
+//! Events are decoded using the metadata of the connected node, so the tool works against any
+//! of the Snowbridge runtimes.
+mod commitment;
 
-use codec::Encode;
+use futures::StreamExt;
 use serde::Serialize;
 use serde_hex::{SerHexSeq, StrictPfx};
 use serde_json;
 use std::io::{self, Write};
 use std::str::FromStr;
 use subxt::sp_core::H256;
-use subxt::{ClientBuilder, DefaultConfig, SubstrateExtrinsicParams};
+use subxt::sp_runtime::traits::Header;
+use subxt::{Client, ClientBuilder, DefaultConfig};
 
-#[derive(Debug, Serialize)]
-struct Items {
-	items: Vec<Item>,
-}
+use commitment::{decode_event, Item};
+
+type BlockNumber = <DefaultConfig as subxt::Config>::BlockNumber;
 
 #[derive(Debug, Serialize)]
-struct Item {
-	id: u8,
-	#[serde(with = "SerHexSeq::<StrictPfx>")]
-	hash: Vec<u8>,
+#[serde(rename_all = "camelCase")]
+struct Block {
+	block_number: BlockNumber,
 	#[serde(with = "SerHexSeq::<StrictPfx>")]
-	data: Vec<u8>,
+	block_hash: Vec<u8>,
+	items: Vec<Item>,
 }
 
 use clap::Parser;
 
-/// Simple program to greet a person
+/// Export the commitments of the outbound channels as JSON, one line per block
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-	/// Name of the person to greet
+	/// Websocket endpoint of a parachain node
 	#[clap(short, long)]
 	api: String,
 
-	/// Number of times to greet
-	#[clap(short, long)]
-	block: String,
+	/// Hash of a single block to export
+	#[clap(short, long, conflicts_with_all = &["from", "follow"])]
+	block: Option<String>,
+
+	/// First block of a range to export
+	#[clap(long, conflicts_with = "follow")]
+	from: Option<BlockNumber>,
+
+	/// Last block of the range, defaults to the latest finalized block
+	#[clap(long, requires = "from")]
+	to: Option<BlockNumber>,
+
+	/// Export each block as it is finalized
+	#[clap(long)]
+	follow: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let args = Args::parse();
 
-	let block_hash = H256::from_str(args.block.trim_start_matches("0x"))?;
-
-	let api = ClientBuilder::new()
-		.set_url(args.api)
-		.build()
-		.await?
-		.to_runtime_api::<runtime::RuntimeApi<DefaultConfig, SubstrateExtrinsicParams<DefaultConfig>>>(
-		);
+	let client: Client<DefaultConfig> = ClientBuilder::new().set_url(args.api).build().await?;
 
-	let events = api.storage().system().events(Some(block_hash.into())).await?;
+	if let Some(block) = args.block {
+		let block_hash = H256::from_str(block.trim_start_matches("0x"))?;
+		let header = client
+			.rpc()
+			.header(Some(block_hash))
+			.await?
+			.ok_or_else(|| format!("block {:?} not found", block_hash))?;
+		return write_block(&export_block(&client, header.number, block_hash).await?)
+	}
 
-	let mut items: Vec<Item> = Vec::new();
+	if let Some(from) = args.from {
+		let to = match args.to {
+			Some(to) => to,
+			None => finalized_number(&client).await?,
+		};
+		for number in from..=to {
+			let block_hash = client
+				.rpc()
+				.block_hash(Some(number.into()))
+				.await?
+				.ok_or_else(|| format!("block {} not found", number))?;
+			write_block(&export_block(&client, number, block_hash).await?)?;
+		}
+		return Ok(())
+	}
 
-	for event in events.into_iter() {
-		if let runtime::runtime_types::snowbase_runtime::Event::BasicOutboundChannel(ev) =
-			&event.event
-		{
-			if let runtime::runtime_types::snowbridge_basic_channel::outbound::pallet::Event::Committed { hash, data } = ev {
-				items.push(Item {
-					id: 0,
-					hash: hash.encode(),
-					data: data.encode(),
-				});
-			}
+	if args.follow {
+		let mut headers = client.rpc().subscribe_finalized_blocks().await?;
+		while let Some(header) = headers.next().await {
+			let header = header?;
+			write_block(&export_block(&client, header.number, header.hash()).await?)?;
 		}
-		if let runtime::runtime_types::snowbase_runtime::Event::IncentivizedOutboundChannel(ev) =
-			&event.event
+		return Ok(())
+	}
+
+	Err("one of --block, --from or --follow is required".into())
+}
+
+/// Decode and verify the commitments in a block.
+async fn export_block(
+	client: &Client<DefaultConfig>,
+	block_number: BlockNumber,
+	block_hash: H256,
+) -> Result<Block, Box<dyn std::error::Error>> {
+	let events = subxt::events::at::<DefaultConfig, ()>(client, block_hash).await?;
+
+	let mut items: Vec<Item> = Vec::new();
+	for event in events.iter_raw() {
+		let event = event?;
+		if let Some(item) = decode_event(&event.pallet, &event.variant, &event.data)
+			.map_err(|err| format!("block {:?}: {}", block_hash, err))?
 		{
-			if let runtime::runtime_types::snowbridge_incentivized_channel::outbound::pallet::Event::Committed { hash, data } = ev {
-				items.push(Item {
-					id: 1,
-					hash: hash.encode(),
-					data: data.encode(),
-				});
-			}
+			items.push(item);
 		}
 	}
 
-	let output = &serde_json::to_string(&Items { items })?;
-	io::stdout().write_all(output.as_bytes())?;
-	io::stdout().write(b"\n")?;
+	Ok(Block { block_number, block_hash: block_hash.as_bytes().to_vec(), items })
+}
 
+async fn finalized_number(
+	client: &Client<DefaultConfig>,
+) -> Result<BlockNumber, Box<dyn std::error::Error>> {
+	let block_hash = client.rpc().finalized_head().await?;
+	let header = client
+		.rpc()
+		.header(Some(block_hash))
+		.await?
+		.ok_or_else(|| format!("block {:?} not found", block_hash))?;
+	Ok(header.number)
+}
+
+fn write_block(block: &Block) -> Result<(), Box<dyn std::error::Error>> {
+	let output = &serde_json::to_string(block)?;
+	let mut stdout = io::stdout();
+	stdout.write_all(output.as_bytes())?;
+	stdout.write_all(b"\n")?;
+	stdout.flush()?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Endpoint of the parachain node started by `test/scripts/start-services.sh`.
+	const DEV_NODE: &str = "ws://localhost:11144";
+
+	#[tokio::test]
+	#[ignore = "requires a local dev node"]
+	async fn exports_recent_blocks_from_dev_node() {
+		let client: Client<DefaultConfig> =
+			ClientBuilder::new().set_url(DEV_NODE).build().await.unwrap();
+
+		let to = finalized_number(&client).await.unwrap();
+		for number in to.saturating_sub(10)..=to {
+			let block_hash = client.rpc().block_hash(Some(number.into())).await.unwrap().unwrap();
+			let block = export_block(&client, number, block_hash).await.unwrap();
+			assert_eq!(block.block_number, number);
+		}
+	}
+}
@@ -41,6 +41,7 @@ func MakeMessageFromEvent(mapping map[common.Address]string, event *etypes.Log,
 	m := parachain.Message{
 		Data: buf.Bytes(),
 		Proof: parachain.Proof{
+			Kind:      parachain.ProofKindPoW,
 			BlockHash: types.NewH256(event.BlockHash.Bytes()),
 			TxIndex:   types.NewU32(uint32(event.TxIndex)),
 			Data:      proof,
@@ -15,12 +15,24 @@ type Message struct {
 	Proof Proof
 }
 
+// Proof is SCALE-encoded as the parachain's `Proof` enum, where Kind is the
+// variant index and the remaining fields make up the receipt proof.
 type Proof struct {
+	Kind      ProofKind
 	BlockHash types.H256
 	TxIndex   types.U32
 	Data      *ProofData
 }
 
+type ProofKind = types.U8
+
+const (
+	// Receipt proof against a header imported by the PoW light client
+	ProofKindPoW       ProofKind = 0
+	// Receipt proof against an execution header imported by the beacon light client
+	ProofKindExecution ProofKind = 1
+)
+
 type ProofData struct {
 	Keys   []types.Bytes
 	Values []types.Bytes
@@ -19,6 +19,10 @@ type SourceConfig struct {
 type ContractsConfig struct {
 	BasicOutboundChannel        string `mapstructure:"BasicOutboundChannel"`
 	IncentivizedOutboundChannel string `mapstructure:"IncentivizedOutboundChannel"`
+
+	// Inbound channels whose MessageDispatched logs are reported to the parachain
+	BasicInboundChannel        string `mapstructure:"BasicInboundChannel"`
+	IncentivizedInboundChannel string `mapstructure:"IncentivizedInboundChannel"`
 }
 
 type SinkConfig struct {
@@ -34,6 +34,8 @@ type EthereumListener struct {
 	conn                        *ethereum.Connection
 	basicOutboundChannel        *basic.BasicOutboundChannel
 	incentivizedOutboundChannel *incentivized.IncentivizedOutboundChannel
+	basicInboundChannel         *basic.BasicInboundChannel
+	incentivizedInboundChannel  *incentivized.IncentivizedInboundChannel
 	mapping                     map[common.Address]string
 	payloads                    chan ParachainPayload
 	headerSyncer                *syncer.Syncer
@@ -54,6 +56,8 @@ func NewEthereumListener(
 		conn:                        conn,
 		basicOutboundChannel:        nil,
 		incentivizedOutboundChannel: nil,
+		basicInboundChannel:         nil,
+		incentivizedInboundChannel:  nil,
 		mapping:                     make(map[common.Address]string),
 		headerSyncer:                nil,
 		initBlockHeight:             initBlockHeight,
@@ -111,6 +115,22 @@ func (li *EthereumListener) Start(
 	li.incentivizedOutboundChannel = incentivizedOutboundChannel
 	li.mapping[address] = "IncentivizedInboundChannel.submit"
 
+	address = common.HexToAddress(li.config.Contracts.BasicInboundChannel)
+	basicInboundChannel, err := basic.NewBasicInboundChannel(address, li.conn.Client())
+	if err != nil {
+		return nil, err
+	}
+	li.basicInboundChannel = basicInboundChannel
+	li.mapping[address] = "BasicOutboundChannel.report_dispatch"
+
+	address = common.HexToAddress(li.config.Contracts.IncentivizedInboundChannel)
+	incentivizedInboundChannel, err := incentivized.NewIncentivizedInboundChannel(address, li.conn.Client())
+	if err != nil {
+		return nil, err
+	}
+	li.incentivizedInboundChannel = incentivizedInboundChannel
+	li.mapping[address] = "IncentivizedOutboundChannel.report_dispatch"
+
 	li.headerSyncer = syncer.NewSyncer(
 		li.descendantsUntilFinal,
 		syncer.NewHeaderLoader(li.conn.Client()),
@@ -189,10 +209,15 @@ func (li *EthereumListener) processEventsAndHeaders(
 				return err
 			}
 
+			reports, err := li.makeDispatchReports(ctx, headerCache, &filterOptions)
+			if err != nil {
+				return err
+			}
+
 			select {
 			case <-ctx.Done():
 				return ctx.Err()
-			case li.payloads <- ParachainPayload{Header: preparedHeader, Messages: messages}:
+			case li.payloads <- ParachainPayload{Header: preparedHeader, Messages: messages, Reports: reports}:
 			}
 
 		}
@@ -243,6 +268,55 @@ func (li *EthereumListener) queryIncentivizedEvents(contract *incentivized.Incen
 	return events, nil
 }
 
+// makeDispatchReports reports the MessageDispatched logs of the inbound channels to the
+// outbound channels on the parachain, which refund senders of messages that were not delivered
+func (li *EthereumListener) makeDispatchReports(
+	ctx context.Context,
+	hcs *ethereum.HeaderCache,
+	options *bind.FilterOpts,
+) ([]*DispatchReport, error) {
+	var events []*etypes.Log
+	var reports []*DispatchReport
+
+	basicIter, err := li.basicInboundChannel.FilterMessageDispatched(options)
+	if err != nil {
+		log.WithError(err).Error("Failure fetching event logs")
+		return nil, err
+	}
+	for basicIter.Next() {
+		events = append(events, &basicIter.Event.Raw)
+		reports = append(reports, &DispatchReport{Pallet: "BasicOutboundChannel", MessageID: basicIter.Event.Id})
+	}
+	if err := basicIter.Error(); err != nil {
+		log.WithError(err).Error("Failure fetching event logs")
+		return nil, err
+	}
+
+	incentivizedIter, err := li.incentivizedInboundChannel.FilterMessageDispatched(options)
+	if err != nil {
+		log.WithError(err).Error("Failure fetching event logs")
+		return nil, err
+	}
+	for incentivizedIter.Next() {
+		events = append(events, &incentivizedIter.Event.Raw)
+		reports = append(reports, &DispatchReport{Pallet: "IncentivizedOutboundChannel", MessageID: incentivizedIter.Event.Id})
+	}
+	if err := incentivizedIter.Error(); err != nil {
+		log.WithError(err).Error("Failure fetching event logs")
+		return nil, err
+	}
+
+	messages, err := li.makeOutgoingMessages(ctx, hcs, events)
+	if err != nil {
+		return nil, err
+	}
+	for i, message := range messages {
+		reports[i].Message = message
+	}
+
+	return reports, nil
+}
+
 func (li *EthereumListener) makeOutgoingMessages(
 	ctx context.Context,
 	hcs *ethereum.HeaderCache,
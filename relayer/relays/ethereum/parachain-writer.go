@@ -20,6 +20,15 @@ import (
 type ParachainPayload struct {
 	Header   *chain.Header
 	Messages []*chain.EthereumOutboundMessage
+	Reports  []*DispatchReport
+}
+
+// DispatchReport is a MessageDispatched log of an inbound channel, which is reported to the
+// outbound channel pallet that sent the message
+type DispatchReport struct {
+	Pallet    string
+	MessageID uint64
+	Message   *chain.EthereumOutboundMessage
 }
 
 type ParachainWriter struct {
@@ -187,6 +196,24 @@ func (wr *ParachainWriter) WritePayload(ctx context.Context, payload *ParachainP
 		calls = append(calls, call)
 	}
 
+	for _, report := range payload.Reports {
+		// Only messages with an expiry await a report, and a report for any other
+		// message would fail the whole batch
+		pending, err := wr.queryOutcomePending(report.Pallet, report.MessageID)
+		if err != nil {
+			return err
+		}
+		if !pending {
+			continue
+		}
+
+		call, err := wr.makeMessageSubmitCall(report.Message)
+		if err != nil {
+			return err
+		}
+		calls = append(calls, call)
+	}
+
 	call, err = types.NewCall(wr.conn.Metadata(), "Utility.batch_all", calls)
 	if err != nil {
 		return err
@@ -231,6 +258,26 @@ func (wr *ParachainWriter) makeHeaderImportCall(header *chain.Header) (types.Cal
 	return types.NewCall(wr.conn.Metadata(), "EthereumLightClient.import_header", header.HeaderData, header.ProofData)
 }
 
+func (wr *ParachainWriter) queryOutcomePending(pallet string, messageID uint64) (bool, error) {
+	encodedID, err := types.EncodeToBytes(types.NewU64(messageID))
+	if err != nil {
+		return false, err
+	}
+
+	key, err := types.CreateStorageKey(wr.conn.Metadata(), pallet, "PendingOutcomes", encodedID, nil)
+	if err != nil {
+		return false, fmt.Errorf("create storage key for message %d: %w", messageID, err)
+	}
+
+	var expiresAt types.U64
+	ok, err := wr.conn.API().RPC.State.GetStorageLatest(key, &expiresAt)
+	if err != nil {
+		return false, fmt.Errorf("storage query for key %s: %w", key.Hex(), err)
+	}
+
+	return ok, nil
+}
+
 func (wr *ParachainWriter) queryImportedHeaderExists(hash types.H256) (bool, error) {
 	key, err := types.CreateStorageKey(wr.conn.Metadata(), "EthereumLightClient", "Headers", hash[:], nil)
 	if err != nil {
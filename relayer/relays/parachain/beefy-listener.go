@@ -589,7 +589,7 @@ func (li *BeefyListener) scanForCommitments(
 		}
 
 		for _, digestItem := range digestItems {
-			if !digestItem.IsCommitment {
+			if !digestItem.IsCommitment || !IsRelayableVersion(digestItem.AsCommitment.Version) {
 				continue
 			}
 			channelID := digestItem.AsCommitment.ChannelID
@@ -598,6 +598,11 @@ func (li *BeefyListener) scanForCommitments(
 					return nil, fmt.Errorf("event basicOutboundChannel.Committed not found in block")
 				}
 
+				// Only the commitment in the version chosen by QueryEvents is relayed
+				if events.Basic.Bundle.Version != digestItem.AsCommitment.Version {
+					continue
+				}
+
 				if events.Basic.Hash != digestItem.AsCommitment.Hash {
 					return nil, fmt.Errorf("basic channel commitment hash in digest item does not match the one in the Committed event")
 				}
@@ -626,6 +631,10 @@ func (li *BeefyListener) scanForCommitments(
 					return nil, fmt.Errorf("event basicOutboundChannel.Committed not found in block")
 				}
 
+				if events.Incentivized.Bundle.Version != digestItem.AsCommitment.Version {
+					continue
+				}
+
 				if events.Incentivized.Hash != digestItem.AsCommitment.Hash {
 					return nil, fmt.Errorf("incentivized channel commitment hash in digest item does not match the one in the Committed event")
 				}
@@ -8,6 +8,20 @@ import (
 	"github.com/snowfork/snowbridge/relayer/contracts/incentivized"
 )
 
+// Versions of the commitment wire format, as in CommitmentVersion on the parachain
+const (
+	// Messages without an expiry, accepted by the submit method of the inbound channels
+	CommitmentVersionUnversioned uint8 = 0
+	// Messages with an expiry, accepted by the submitExpiring method of the inbound channels
+	CommitmentVersionExpiring uint8 = 2
+)
+
+// IsRelayableVersion returns whether commitments in a version can be submitted to the inbound
+// channels on Ethereum.
+func IsRelayableVersion(version uint8) bool {
+	return version == CommitmentVersionUnversioned || version == CommitmentVersionExpiring
+}
+
 func (b BasicOutboundChannelMessageBundle) IntoInboundMessageBundle() basic.BasicInboundChannelMessageBundle {
 	var messages []basic.BasicInboundChannelMessage
 	for _, m := range b.Messages {
@@ -24,16 +38,35 @@ func (b BasicOutboundChannelMessageBundle) IntoInboundMessageBundle() basic.Basi
 	}
 }
 
+func (b BasicOutboundChannelMessageBundle) IntoInboundExpiringMessageBundle() basic.BasicInboundChannelExpiringMessageBundle {
+	var messages []basic.BasicInboundChannelExpiringMessage
+	for _, m := range b.Messages {
+		messages = append(messages, basic.BasicInboundChannelExpiringMessage{
+			Id:        (*big.Int)(&m.ID).Uint64(),
+			Target:    m.Target,
+			ExpiresAt: expiresAt(m.ExpiresAt),
+			Payload:   m.Payload,
+		})
+	}
+	return basic.BasicInboundChannelExpiringMessageBundle{
+		SourceChannelID: b.SourceChannelID,
+		Nonce:           (*big.Int)(&b.Nonce).Uint64(),
+		Messages:        messages,
+	}
+}
+
 type BasicOutboundChannelMessageBundle struct {
+	Version         uint8
 	SourceChannelID uint8
 	Nonce           types.UCompact
 	Messages        []BasicOutboundChannelMessage
 }
 
 type BasicOutboundChannelMessage struct {
-	ID      types.UCompact
-	Target  [20]byte
-	Payload []byte
+	ID        types.UCompact
+	Target    [20]byte
+	ExpiresAt types.OptionU64
+	Payload   []byte
 }
 
 func (b IncentivizedOutboundChannelMessageBundle) IntoInboundMessageBundle() incentivized.IncentivizedInboundChannelMessageBundle {
@@ -53,7 +86,26 @@ func (b IncentivizedOutboundChannelMessageBundle) IntoInboundMessageBundle() inc
 	}
 }
 
+func (b IncentivizedOutboundChannelMessageBundle) IntoInboundExpiringMessageBundle() incentivized.IncentivizedInboundChannelExpiringMessageBundle {
+	var messages []incentivized.IncentivizedInboundChannelExpiringMessage
+	for _, m := range b.Messages {
+		messages = append(messages, incentivized.IncentivizedInboundChannelExpiringMessage{
+			Id:        (*big.Int)(&m.ID).Uint64(),
+			Target:    m.Target,
+			ExpiresAt: expiresAt(m.ExpiresAt),
+			Payload:   m.Payload,
+		})
+	}
+	return incentivized.IncentivizedInboundChannelExpiringMessageBundle{
+		SourceChannelID: b.SourceChannelID,
+		Nonce:           (*big.Int)(&b.Nonce).Uint64(),
+		Fee:             (*big.Int)(&b.Fee),
+		Messages:        messages,
+	}
+}
+
 type IncentivizedOutboundChannelMessageBundle struct {
+	Version         uint8
 	SourceChannelID uint8
 	Nonce           types.UCompact
 	Fee             types.UCompact
@@ -61,7 +113,18 @@ type IncentivizedOutboundChannelMessageBundle struct {
 }
 
 type IncentivizedOutboundChannelMessage struct {
-	ID      types.UCompact
-	Target  [20]byte
-	Payload []byte
+	ID        types.UCompact
+	Target    [20]byte
+	Fee       types.UCompact
+	ExpiresAt types.OptionU64
+	Payload   []byte
+}
+
+// Expiry of a message as committed, where zero means that the message does not expire
+func expiresAt(expiry types.OptionU64) uint64 {
+	ok, value := expiry.Unwrap()
+	if !ok {
+		return 0
+	}
+	return uint64(value)
 }
@@ -0,0 +1,152 @@
+package parachain
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/crypto"
+	"github.com/snowfork/go-substrate-rpc-client/v4/types"
+	"github.com/snowfork/snowbridge/relayer/contracts/basic"
+	"github.com/snowfork/snowbridge/relayer/contracts/incentivized"
+)
+
+var (
+	uint8Type, _   = abi.NewType("uint8", "", nil)
+	uint64Type, _  = abi.NewType("uint64", "", nil)
+	uint128Type, _ = abi.NewType("uint128", "", nil)
+	bytes32Type, _ = abi.NewType("bytes32", "", nil)
+	messageType, _ = abi.NewType("tuple", "", []abi.ArgumentMarshaling{
+		{Name: "id", Type: "uint64"},
+		{Name: "target", Type: "address"},
+		{Name: "payload", Type: "bytes"},
+	})
+	expiringMessageType, _ = abi.NewType("tuple", "", []abi.ArgumentMarshaling{
+		{Name: "version", Type: "uint8"},
+		{Name: "id", Type: "uint64"},
+		{Name: "target", Type: "address"},
+		{Name: "expiresAt", Type: "uint64"},
+		{Name: "payload", Type: "bytes"},
+	})
+)
+
+// A message with an expiry as ABI-encoded in a commitment
+type expiringMessageLeaf struct {
+	Version   uint8
+	Id        uint64
+	Target    common.Address
+	ExpiresAt uint64
+	Payload   []byte
+}
+
+// BasicCommitmentHash computes the commitment hash of a bundle in the same way as
+// BasicInboundChannel.submit, so that bundles which would be rejected are not submitted.
+func BasicCommitmentHash(bundle basic.BasicInboundChannelMessageBundle) (types.H256, error) {
+	var messages []interface{}
+	for _, message := range bundle.Messages {
+		messages = append(messages, message)
+	}
+	root, err := messagesRoot(messageType, messages)
+	if err != nil {
+		return types.H256{}, err
+	}
+	return commitmentHash(bundle.SourceChannelID, bundle.Nonce, nil, root)
+}
+
+// BasicExpiringCommitmentHash computes the commitment hash of a bundle in the same way as
+// BasicInboundChannel.submitExpiring.
+func BasicExpiringCommitmentHash(bundle basic.BasicInboundChannelExpiringMessageBundle) (types.H256, error) {
+	var messages []interface{}
+	for _, message := range bundle.Messages {
+		messages = append(messages, expiringMessageLeaf{
+			CommitmentVersionExpiring, message.Id, message.Target, message.ExpiresAt, message.Payload,
+		})
+	}
+	root, err := messagesRoot(expiringMessageType, messages)
+	if err != nil {
+		return types.H256{}, err
+	}
+	return commitmentHash(bundle.SourceChannelID, bundle.Nonce, nil, root)
+}
+
+// IncentivizedCommitmentHash computes the commitment hash of a bundle in the same way as
+// IncentivizedInboundChannel.submit, so that bundles which would be rejected are not submitted.
+func IncentivizedCommitmentHash(bundle incentivized.IncentivizedInboundChannelMessageBundle) (types.H256, error) {
+	var messages []interface{}
+	for _, message := range bundle.Messages {
+		messages = append(messages, message)
+	}
+	root, err := messagesRoot(messageType, messages)
+	if err != nil {
+		return types.H256{}, err
+	}
+	return commitmentHash(bundle.SourceChannelID, bundle.Nonce, bundle.Fee, root)
+}
+
+// IncentivizedExpiringCommitmentHash computes the commitment hash of a bundle in the same way as
+// IncentivizedInboundChannel.submitExpiring.
+func IncentivizedExpiringCommitmentHash(bundle incentivized.IncentivizedInboundChannelExpiringMessageBundle) (types.H256, error) {
+	var messages []interface{}
+	for _, message := range bundle.Messages {
+		messages = append(messages, expiringMessageLeaf{
+			CommitmentVersionExpiring, message.Id, message.Target, message.ExpiresAt, message.Payload,
+		})
+	}
+	root, err := messagesRoot(expiringMessageType, messages)
+	if err != nil {
+		return types.H256{}, err
+	}
+	return commitmentHash(bundle.SourceChannelID, bundle.Nonce, bundle.Fee, root)
+}
+
+// Hash of the ABI-encoded source channel, nonce and fee of a bundle, followed by the root of the
+// Merkle tree over its messages. Bundles of the basic channel have no fee.
+func commitmentHash(sourceChannelID uint8, nonce uint64, fee *big.Int, root [32]byte) (types.H256, error) {
+	arguments := abi.Arguments{{Type: uint8Type}, {Type: uint64Type}}
+	values := []interface{}{sourceChannelID, nonce}
+	if fee != nil {
+		arguments = append(arguments, abi.Argument{Type: uint128Type})
+		values = append(values, fee)
+	}
+	arguments = append(arguments, abi.Argument{Type: bytes32Type})
+	values = append(values, root)
+
+	encoded, err := arguments.Pack(values...)
+	if err != nil {
+		return types.H256{}, fmt.Errorf("encode bundle: %w", err)
+	}
+
+	return types.H256(crypto.Keccak256Hash(encoded)), nil
+}
+
+// Root of the Merkle tree over the ABI-encoded messages, in which the last node of a level with
+// an odd number of nodes is promoted unchanged.
+func messagesRoot(leafType abi.Type, messages []interface{}) ([32]byte, error) {
+	var level []common.Hash
+	for _, message := range messages {
+		leaf, err := abi.Arguments{{Type: leafType}}.Pack(message)
+		if err != nil {
+			return [32]byte{}, fmt.Errorf("encode message: %w", err)
+		}
+		level = append(level, crypto.Keccak256Hash(leaf))
+	}
+
+	if len(level) == 0 {
+		return [32]byte{}, nil
+	}
+
+	for len(level) > 1 {
+		var next []common.Hash
+		for i := 0; i < len(level); i += 2 {
+			if i+1 < len(level) {
+				next = append(next, crypto.Keccak256Hash(level[i].Bytes(), level[i+1].Bytes()))
+			} else {
+				next = append(next, level[i])
+			}
+		}
+		level = next
+	}
+
+	return level[0], nil
+}
@@ -0,0 +1,145 @@
+package parachain
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/snowfork/snowbridge/relayer/contracts/basic"
+	"github.com/snowfork/snowbridge/relayer/contracts/incentivized"
+	"github.com/stretchr/testify/assert"
+)
+
+// The expected hashes are the same as in the tests of the outbound channels on the parachain.
+
+func TestBasicCommitmentHash(t *testing.T) {
+	bundle := basic.BasicInboundChannelMessageBundle{
+		SourceChannelID: 0,
+		Nonce:           1,
+		Messages: []basic.BasicInboundChannelMessage{
+			{
+				Id:      7,
+				Target:  common.HexToAddress("0x0101010101010101010101010101010101010101"),
+				Payload: []byte{1, 2, 3},
+			},
+		},
+	}
+
+	hash, err := BasicCommitmentHash(bundle)
+	if err != nil {
+		panic(err)
+	}
+
+	assert.Equal(t,
+		common.HexToHash("0xe8258a92c9bc87626f4598b84794437220ae33db4574e5a6b1cbc7f1f5fd4152").Bytes(),
+		hash[:],
+	)
+
+	bundle.Nonce = 2
+	replayed, err := BasicCommitmentHash(bundle)
+	if err != nil {
+		panic(err)
+	}
+	assert.NotEqual(t, hash, replayed)
+}
+
+func TestBasicExpiringCommitmentHash(t *testing.T) {
+	bundle := basic.BasicInboundChannelExpiringMessageBundle{
+		SourceChannelID: 0,
+		Nonce:           1,
+		Messages: []basic.BasicInboundChannelExpiringMessage{
+			{
+				Id:        7,
+				Target:    common.HexToAddress("0x0101010101010101010101010101010101010101"),
+				ExpiresAt: 250,
+				Payload:   []byte{1, 2, 3},
+			},
+		},
+	}
+
+	hash, err := BasicExpiringCommitmentHash(bundle)
+	if err != nil {
+		panic(err)
+	}
+
+	assert.Equal(t,
+		common.HexToHash("0x9a3fe4838c30f448c80f1e9a031bfea284171a891bfcac923e4e185a3011a2e5").Bytes(),
+		hash[:],
+	)
+}
+
+func TestIncentivizedCommitmentHash(t *testing.T) {
+	bundle := incentivized.IncentivizedInboundChannelMessageBundle{
+		SourceChannelID: 1,
+		Nonce:           3,
+		Fee:             big.NewInt(20),
+		Messages: []incentivized.IncentivizedInboundChannelMessage{
+			{
+				Id:      2,
+				Target:  common.HexToAddress("0x0202020202020202020202020202020202020202"),
+				Payload: []byte{4, 5},
+			},
+		},
+	}
+
+	hash, err := IncentivizedCommitmentHash(bundle)
+	if err != nil {
+		panic(err)
+	}
+
+	assert.Equal(t,
+		common.HexToHash("0x8e8db1a4456aa81b6b56126f28986624023f3df6ba9bc28bbcbd2a4f77624a5e").Bytes(),
+		hash[:],
+	)
+}
+
+func TestIncentivizedExpiringCommitmentHash(t *testing.T) {
+	bundle := incentivized.IncentivizedInboundChannelExpiringMessageBundle{
+		SourceChannelID: 1,
+		Nonce:           3,
+		Fee:             big.NewInt(20),
+		Messages: []incentivized.IncentivizedInboundChannelExpiringMessage{
+			{
+				Id:        2,
+				Target:    common.HexToAddress("0x0202020202020202020202020202020202020202"),
+				ExpiresAt: 250,
+				Payload:   []byte{4, 5},
+			},
+		},
+	}
+
+	hash, err := IncentivizedExpiringCommitmentHash(bundle)
+	if err != nil {
+		panic(err)
+	}
+
+	assert.Equal(t,
+		common.HexToHash("0xf4d31e2ed5d158021b3b43b9243687213e40893ec1ffe4f04bba35599fbe3576").Bytes(),
+		hash[:],
+	)
+}
+
+func TestMessagesRootOfOddNumberOfMessages(t *testing.T) {
+	var messages []interface{}
+	for i := byte(0); i < 3; i++ {
+		var target common.Address
+		for j := range target {
+			target[j] = i
+		}
+		messages = append(messages, basic.BasicInboundChannelMessage{
+			Id:      uint64(i),
+			Target:  target,
+			Payload: []byte{i, i, i},
+		})
+	}
+
+	root, err := messagesRoot(messageType, messages)
+	if err != nil {
+		panic(err)
+	}
+
+	assert.Equal(t,
+		common.HexToHash("0xafc5fe8301af5f01cd90080987b2258e1037251fea9fd0cc362f573f8ac5a43b").Bytes(),
+		root[:],
+	)
+}
@@ -14,7 +14,9 @@ type AuxiliaryDigestItem struct {
 
 type AuxiliaryDigestItemCommitment struct {
 	ChannelID ChannelID
-	Hash      types.H256
+	// Version of the commitment wire format. Zero for unversioned (legacy) commitments.
+	Version uint8
+	Hash    types.H256
 }
 
 func (a *AuxiliaryDigestItem) Decode(decoder scale.Decoder) error {
@@ -26,7 +28,22 @@ func (a *AuxiliaryDigestItem) Decode(decoder scale.Decoder) error {
 	switch tag {
 	case 0:
 		a.IsCommitment = true
-		err = decoder.Decode(&a.AsCommitment)
+		err = decoder.Decode(&a.AsCommitment.ChannelID)
+		if err != nil {
+			return err
+		}
+		err = decoder.Decode(&a.AsCommitment.Hash)
+	case 1:
+		a.IsCommitment = true
+		err = decoder.Decode(&a.AsCommitment.ChannelID)
+		if err != nil {
+			return err
+		}
+		err = decoder.Decode(&a.AsCommitment.Version)
+		if err != nil {
+			return err
+		}
+		err = decoder.Decode(&a.AsCommitment.Hash)
 	default:
 		return fmt.Errorf("No such variant for DigestItem")
 	}
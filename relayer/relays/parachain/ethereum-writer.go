@@ -199,6 +199,24 @@ func (wr *EthereumWriter) WriteBasicChannel(
 	proof *ProofOutput,
 ) error {
 	bundle := commitmentData.IntoInboundMessageBundle()
+	expiringBundle := commitmentData.IntoInboundExpiringMessageBundle()
+
+	var computedHash gsrpcTypes.H256
+	var err error
+	if commitmentData.Version == CommitmentVersionExpiring {
+		computedHash, err = BasicExpiringCommitmentHash(expiringBundle)
+	} else {
+		computedHash, err = BasicCommitmentHash(bundle)
+	}
+	if err != nil {
+		return fmt.Errorf("compute commitment hash: %w", err)
+	}
+	if computedHash != commitmentHash {
+		return fmt.Errorf(
+			"commitment hash %#x does not match hash %#x computed from its bundle",
+			commitmentHash[:], computedHash[:],
+		)
+	}
 
 	paraHeadProof := opaqueproof.ParachainClientHeadProof{
 		Pos:   big.NewInt(int64(proof.MerkleProofData.ProvenLeafIndex)),
@@ -255,9 +273,12 @@ func (wr *EthereumWriter) WriteBasicChannel(
 		return fmt.Errorf("pack proof: %w", err)
 	}
 
-	tx, err := wr.basicInboundChannel.Submit(
-		options, bundle, opaqueProof,
-	)
+	var tx *types.Transaction
+	if commitmentData.Version == CommitmentVersionExpiring {
+		tx, err = wr.basicInboundChannel.SubmitExpiring(options, expiringBundle, opaqueProof)
+	} else {
+		tx, err = wr.basicInboundChannel.Submit(options, bundle, opaqueProof)
+	}
 	if err != nil {
 		return fmt.Errorf("send transaction BasicInboundChannel.submit: %w", err)
 	}
@@ -291,6 +312,24 @@ func (wr *EthereumWriter) WriteIncentivizedChannel(
 	proof *ProofOutput,
 ) error {
 	bundle := commitmentData.IntoInboundMessageBundle()
+	expiringBundle := commitmentData.IntoInboundExpiringMessageBundle()
+
+	var computedHash gsrpcTypes.H256
+	var err error
+	if commitmentData.Version == CommitmentVersionExpiring {
+		computedHash, err = IncentivizedExpiringCommitmentHash(expiringBundle)
+	} else {
+		computedHash, err = IncentivizedCommitmentHash(bundle)
+	}
+	if err != nil {
+		return fmt.Errorf("compute commitment hash: %w", err)
+	}
+	if computedHash != commitmentHash {
+		return fmt.Errorf(
+			"commitment hash %#x does not match hash %#x computed from its bundle",
+			commitmentHash[:], computedHash[:],
+		)
+	}
 
 	paraHeadProof := opaqueproof.ParachainClientHeadProof{
 		Pos:   big.NewInt(int64(proof.MerkleProofData.ProvenLeafIndex)),
@@ -347,9 +386,12 @@ func (wr *EthereumWriter) WriteIncentivizedChannel(
 		return fmt.Errorf("pack proof: %w", err)
 	}
 
-	tx, err := wr.incentivizedInboundChannel.Submit(
-		options, bundle, opaqueProof,
-	)
+	var tx *types.Transaction
+	if commitmentData.Version == CommitmentVersionExpiring {
+		tx, err = wr.incentivizedInboundChannel.SubmitExpiring(options, expiringBundle, opaqueProof)
+	} else {
+		tx, err = wr.incentivizedInboundChannel.Submit(options, bundle, opaqueProof)
+	}
 	if err != nil {
 		return fmt.Errorf("send transaction IncentivizedInboundChannel.submit: %w", err)
 	}
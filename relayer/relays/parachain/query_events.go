@@ -89,6 +89,12 @@ func (q *QueryClient) QueryEvents(ctx context.Context, api string, blockHash typ
 			if err != nil {
 				return nil, err
 			}
+			// The unversioned commitment is relayed if there is one, as it is accepted by
+			// the peer contracts on either side of an upgrade.
+			if !IsRelayableVersion(bundle.Version) ||
+				(events.Basic != nil && events.Basic.Bundle.Version == CommitmentVersionUnversioned) {
+				continue
+			}
 			events.Basic = &BasicChannelEvent{
 				Hash:   hash,
 				Bundle: bundle,
@@ -99,6 +105,10 @@ func (q *QueryClient) QueryEvents(ctx context.Context, api string, blockHash typ
 			if err != nil {
 				return nil, err
 			}
+			if !IsRelayableVersion(bundle.Version) ||
+				(events.Incentivized != nil && events.Incentivized.Bundle.Version == CommitmentVersionUnversioned) {
+				continue
+			}
 			events.Incentivized = &IncentivizedChannelEvent{
 				Hash:   hash,
 				Bundle: bundle,
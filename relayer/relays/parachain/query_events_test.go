@@ -16,7 +16,7 @@ var mock = `{
     {
       "id": 0,
 	  "hash": "0x82a6824ed57e7bb78b51673803f49de2e8373dbaf6da12e0f3f99f4d9779459a",
-	  "data": "0x040400b8ea8cb425d85536b158d661da1ef0895bb92f1d91017ed9db59d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d00000000000000000000000089b4ab1ef20763630df9743acf155865600daff20000000000000000000000000000000000000000000000056bc75e2d63100000"
+	  "data": "0x0000040400b8ea8cb425d85536b158d661da1ef0895bb92f1d0091017ed9db59d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d00000000000000000000000089b4ab1ef20763630df9743acf155865600daff20000000000000000000000000000000000000000000000056bc75e2d63100000"
 	}
   ]
 }
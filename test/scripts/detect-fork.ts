@@ -109,33 +109,41 @@ const getContracts = (url: string): Promise<any> => {
   });
 };
 
+// Messages from Ethereum to the parachain have a nonce per source app, kept by the
+// outbound channel contracts and by the inbound channels on the parachain. Messages from
+// the parachain to Ethereum have a single nonce per channel.
 const NONCES = [
   {
     name: "BasicInboundChannel",
     event: "MessageDispatched",
-    storageKey:
-      "0x684b82bef882079feeabe54a5bd7b94a718368a0ace36e2b1b8b6dbd7f8093c0",
+    perSource: false,
   },
   {
     name: "IncentivizedInboundChannel",
     event: "MessageDispatched",
-    storageKey:
-      "0xf0f4d0b91e760c07da58bc0498033acb718368a0ace36e2b1b8b6dbd7f8093c0",
+    perSource: false,
   },
   {
     name: "BasicOutboundChannel",
     event: "Message",
-    storageKey:
-      "0x664ff6e369f56e1c7deca5487e631a5c718368a0ace36e2b1b8b6dbd7f8093c0",
+    perSource: true,
   },
   {
     name: "IncentivizedOutboundChannel",
     event: "Message",
-    storageKey:
-      "0x557df379daaf1cd514a7452dcbf6fccc718368a0ace36e2b1b8b6dbd7f8093c0",
+    perSource: true,
   },
 ];
 
+// App contracts which send messages to the parachain.
+const SOURCE_APPS = ["ETHApp", "ERC20App", "DOTApp"];
+
+const sourceAddresses = (contractsConfig: any): string[] =>
+  SOURCE_APPS.map((app) => contractsConfig[app].address);
+
+const palletName = (name: string): string =>
+  `${name[0].toLowerCase()}${name.substring(1)}`;
+
 const fetchEthNonces = async (
   contractsConfig: any,
   ethApi: Web3,
@@ -154,60 +162,82 @@ const fetchEthNonces = async (
       fromBlock: commonAnscestorBlockNumber - descendantsUntilFinalized,
       toBlock: "latest",
     });
-    nonces[nonce.name] = contract.methods.nonce().call();
+    if (nonce.perSource) {
+      nonces[nonce.name] = {};
+      for (const source of sourceAddresses(contractsConfig)) {
+        nonces[nonce.name][source] = contract.methods.nonce(source).call();
+      }
+    } else {
+      nonces[nonce.name] = contract.methods.nonce().call();
+    }
   }
 
-  await Promise.all(Object.values(pastEvents).concat(Object.values(nonces)));
+  await Promise.all(Object.values(pastEvents));
+
+  // take the first event if there are any else take the current nonce.
+  const nonceBefore = async (events: any[], current: Promise<any>) =>
+    events.length > 0
+      ? Number(events[0].returnValues["nonce"]) - 1
+      : Number(await current);
 
   const result = {};
   for (const nonce of NONCES) {
     const events = await pastEvents[nonce.name];
-    // take the first event if there are any else get take the current nonce.
-    if (events.length > 0) {
-      result[nonce.name] = Number(events[0].returnValues["nonce"]) - 1;
+    if (nonce.perSource) {
+      result[nonce.name] = {};
+      for (const source of sourceAddresses(contractsConfig)) {
+        const sourceEvents = events.filter(
+          (event) =>
+            event.returnValues["source"].toLowerCase() === source.toLowerCase()
+        );
+        result[nonce.name][source] = await nonceBefore(
+          sourceEvents,
+          nonces[nonce.name][source]
+        );
+      }
     } else {
-      result[nonce.name] = Number(await nonces[nonce.name]);
+      result[nonce.name] = await nonceBefore(events, nonces[nonce.name]);
     }
   }
   return result;
 };
 
-const fetchParachainNonces = async (parachainApi: ApiPromise): Promise<any> => {
-  const promises = {};
-  for (const nonce of NONCES) {
-    promises[nonce.name] =
-      parachainApi.query[
-        `${nonce.name[0].toLowerCase()}${nonce.name.substring(1)}`
-      ].nonce();
-  }
-
-  await Promise.all(Object.values(promises));
-
+const fetchParachainNonces = async (
+  parachainApi: ApiPromise,
+  contractsConfig: any
+): Promise<any> => {
   const result = {};
   for (const nonce of NONCES) {
-    result[nonce.name] = (await promises[nonce.name]).toNumber();
+    const query = parachainApi.query[palletName(nonce.name)].nonce;
+    // The parachain's inbound channels track the nonces of the apps which send
+    // through the outbound channel contracts with per-source nonces.
+    if (nonce.perSource) {
+      result[nonce.name] = (await query()).toNumber();
+    } else {
+      result[nonce.name] = {};
+      for (const source of sourceAddresses(contractsConfig)) {
+        result[nonce.name][source] = (await query(source)).toNumber();
+      }
+    }
   }
   return result;
 };
 
-const generateUpdates = (ethNonces, parachainNonces) => {
+const generateUpdates = (parachainApi: ApiPromise, ethNonces, parachainNonces) => {
   const result = [];
-  if (parachainNonces.BasicInboundChannel !== ethNonces.BasicOutboundChannel) {
-    result.push({
-      name: NONCES[0].name,
-      storageKey: NONCES[0].storageKey,
-      nonce: ethNonces.BasicOutboundChannel,
-    });
-  }
-  if (
-    parachainNonces.IncentivizedInboundChannel !==
-    ethNonces.IncentivizedOutboundChannel
-  ) {
-    result.push({
-      name: NONCES[1].name,
-      storageKey: NONCES[1].storageKey,
-      nonce: ethNonces.IncentivizedOutboundChannel,
-    });
+  for (const [inbound, outbound] of [
+    ["BasicInboundChannel", "BasicOutboundChannel"],
+    ["IncentivizedInboundChannel", "IncentivizedOutboundChannel"],
+  ]) {
+    for (const source of Object.keys(ethNonces[outbound])) {
+      if (parachainNonces[inbound][source] !== ethNonces[outbound][source]) {
+        result.push({
+          name: `${inbound} (${source})`,
+          storageKey: parachainApi.query[palletName(inbound)].nonce.key(source),
+          nonce: ethNonces[outbound][source],
+        });
+      }
+    }
   }
   return result;
 };
@@ -320,7 +350,10 @@ const main = async () => {
     console.log(`Ethereum Total Difficulty:  ${ethBlock.totalDifficulty}`);
 
     console.log("Checking nonces.");
-    const parachainNonces = await fetchParachainNonces(parachainApi);
+    const parachainNonces = await fetchParachainNonces(
+      parachainApi,
+      contractsConfig.contracts
+    );
     const ethNonces = await fetchEthNonces(
       contractsConfig.contracts,
       ethApi,
@@ -337,17 +370,20 @@ const main = async () => {
     );
 
     console.log("Nonces                Parachain <- ETH");
-    console.log(
-      `Basic Channel:        ${parachainNonces.BasicInboundChannel} <- ${ethNonces.BasicOutboundChannel}`
-    );
-    console.log(
-      `Incentivized Channel: ${parachainNonces.IncentivizedInboundChannel} <- ${ethNonces.IncentivizedOutboundChannel}`
-    );
+    for (const source of sourceAddresses(contractsConfig.contracts)) {
+      console.log(`Source ${source}:`);
+      console.log(
+        `Basic Channel:        ${parachainNonces.BasicInboundChannel[source]} <- ${ethNonces.BasicOutboundChannel[source]}`
+      );
+      console.log(
+        `Incentivized Channel: ${parachainNonces.IncentivizedInboundChannel[source]} <- ${ethNonces.IncentivizedOutboundChannel[source]}`
+      );
+    }
 
     let fixWithUser: string = argv["fix"];
     if (fixWithUser !== null && fixWithUser !== "") {
       console.log(`Fixing fork`);
-      const updates = generateUpdates(ethNonces, parachainNonces);
+      const updates = generateUpdates(parachainApi, ethNonces, parachainNonces);
       console.log(
         `Going to force reset to number ${ethBlock.number} hash ${ethBlock.hash} with user ${fixWithUser}.`
       );
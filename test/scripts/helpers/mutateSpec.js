@@ -24,11 +24,23 @@ function run() {
     data['genesis']['runtime']['parachainInfo']['parachainId'] = 1000;
     data['para_id'] = 1000;
 
-    data['genesis']['runtime']['dotApp']['address'] = contracts['contracts']['DOTApp']['address'];
-    data['genesis']['runtime']['ethApp']['address'] = contracts['contracts']['ETHApp']['address'];
-    data['genesis']['runtime']['erc20App']['address'] = contracts['contracts']['ERC20App']['address'];
-    data['genesis']['runtime']['incentivizedInboundChannel']['sourceChannel'] = contracts['contracts']['IncentivizedOutboundChannel']['address'];
-    data['genesis']['runtime']['basicInboundChannel']['sourceChannel'] = contracts['contracts']['BasicOutboundChannel']['address'];
+    // The chain spec allows the placeholder addresses of the app contracts to dispatch the calls
+    // of their apps, with the pallet and call indices of the runtime. Allow the deployed
+    // contracts instead.
+    let apps = {};
+    for (const [app, contract] of [['dotApp', 'DOTApp'], ['ethApp', 'ETHApp'], ['erc20App', 'ERC20App']]) {
+      apps[data['genesis']['runtime'][app]['address'].toLowerCase()] = contracts['contracts'][contract]['address'];
+      data['genesis']['runtime'][app]['address'] = contracts['contracts'][contract]['address'];
+    }
+    data['genesis']['runtime']['dispatch']['allowedCalls'] = data['genesis']['runtime']['dispatch']['allowedCalls']
+      .map(([source, palletIndex, callIndex]) => [apps[source.toLowerCase()], palletIndex, callIndex]);
+    // Inbound channel contracts report the dispatch of messages with an expiry
+    data['genesis']['runtime']['incentivizedOutboundChannel']['targetChannel'] = contracts['contracts']['IncentivizedInboundChannel']['address'];
+    data['genesis']['runtime']['basicOutboundChannel']['targetChannel'] = contracts['contracts']['BasicInboundChannel']['address'];
+    data['genesis']['runtime']['channelRegistry']['channels'] = [
+      [0, "Basic", contracts['contracts']['BasicOutboundChannel']['address']],
+      [1, "Incentivized", contracts['contracts']['IncentivizedOutboundChannel']['address']],
+    ];
 
     console.log(JSON.stringify(
       data,
@@ -170,9 +170,13 @@ start_relayer()
     jq \
         --arg k1 "$(address_for BasicOutboundChannel)" \
         --arg k2 "$(address_for IncentivizedOutboundChannel)" \
+        --arg k3 "$(address_for BasicInboundChannel)" \
+        --arg k4 "$(address_for IncentivizedInboundChannel)" \
     '
       .source.contracts.BasicOutboundChannel = $k1
     | .source.contracts.IncentivizedOutboundChannel = $k2
+    | .source.contracts.BasicInboundChannel = $k3
+    | .source.contracts.IncentivizedInboundChannel = $k4
     ' \
     config/ethereum-relay.json > $output_dir/ethereum-relay.json
 
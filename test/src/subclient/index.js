@@ -101,15 +101,15 @@ class SubClient {
   }
 
   async burnETH(account, recipient, amount, channelId) {
-    return await this.api.tx.ethApp.burn(channelId, recipient, amount).signAndSend(account);
+    return await this.api.tx.ethApp.burn(channelId, recipient, amount, null).signAndSend(account);
   }
 
   async burnERC20(account, assetId, recipient, amount, channelId) {
-    return await this.api.tx.erc20App.burn(channelId, assetId, recipient, amount).signAndSend(account);
+    return await this.api.tx.erc20App.burn(channelId, assetId, recipient, amount, null).signAndSend(account);
   }
 
   async lockDOT(account, recipient, amount, channelId) {
-    return await this.api.tx.dotApp.lock(channelId, recipient, amount).signAndSend(account);
+    return await this.api.tx.dotApp.lock(channelId, recipient, amount, null).signAndSend(account);
   }
 
   async waitForNextBlock() {
@@ -7,6 +7,56 @@ import { alias, types as typesV1 } from "./v1";
 
 export const definition: OverrideBundleDefinition = {
   alias: alias,
+  rpc: {
+    basicOutboundChannel: {
+      getCommitment: {
+        description: "Get the SCALE-encoded message bundle for a commitment",
+        params: [{ name: "commitmentHash", type: "H256" }],
+        type: "Option<Bytes>",
+      },
+      getMerkleProof: {
+        description: "Get a SCALE-encoded Merkle proof for a message in a commitment",
+        params: [
+          { name: "commitmentHash", type: "H256" },
+          { name: "leafIndex", type: "u64" },
+        ],
+        type: "Option<Bytes>",
+      },
+    },
+    incentivizedOutboundChannel: {
+      getCommitment: {
+        description: "Get the SCALE-encoded message bundle for a commitment",
+        params: [{ name: "commitmentHash", type: "H256" }],
+        type: "Option<Bytes>",
+      },
+      getMerkleProof: {
+        description: "Get a SCALE-encoded Merkle proof for a message in a commitment",
+        params: [
+          { name: "commitmentHash", type: "H256" },
+          { name: "leafIndex", type: "u64" },
+        ],
+        type: "Option<Bytes>",
+      },
+    },
+    outboundChannel: {
+      getQueuedMessages: {
+        description: "Get the messages waiting to be committed on a channel",
+        params: [
+          { name: "channelId", type: "ChannelId" },
+          { name: "at", type: "BlockHash", isOptional: true },
+        ],
+        type: "Option<Vec<QueuedMessage>>",
+      },
+      getState: {
+        description: "Get the commitment state of a channel",
+        params: [
+          { name: "channelId", type: "ChannelId" },
+          { name: "at", type: "BlockHash", isOptional: true },
+        ],
+        type: "Option<OutboundChannelState>",
+      },
+    },
+  },
   types: [
     {
       minmax: [0, undefined],
@@ -11,17 +11,63 @@ export const types: RegistryTypes = {
   LookupSource: "MultiAddress",
   DispatchMessageId: {
     channelId: "ChannelId",
+    source: "H160",
     nonce: "u64",
   },
-  ChannelId: {
+  ChannelId: "u8",
+  OutboundChannelPallet: {
     _enum: ["Basic", "Incentivized"],
   },
+  ChannelInfo: {
+    outbound: "OutboundChannelPallet",
+    source: "H160",
+  },
+  CommitmentVersion: {
+    _enum: { V0: 0, V2: 2 },
+  },
+  MessageOutcome: {
+    _enum: ["Delivered", "Undelivered"],
+  },
+  AppId: {
+    _enum: ["Dot", "Eth", "Erc20"],
+  },
+  BridgeComponent: {
+    _enum: {
+      InboundChannel: "ChannelId",
+      OutboundChannel: "ChannelId",
+      App: "AppId",
+    },
+  },
+  QueuedMessage: {
+    id: "u64",
+    target: "H160",
+    fee: "u128",
+    expiresAt: "Option<u64>",
+    payload: "Bytes",
+  },
+  OutboundChannelState: {
+    nextCommitmentBlock: "BlockNumber",
+    nonce: "u64",
+    latestCommitmentHashes: "Vec<(CommitmentVersion, H256)>",
+    nextMessageId: "u64",
+  },
+  TransferLimit: {
+    period: "BlockNumber",
+    globalCap: "u128",
+    recipientCap: "u128",
+  },
   MessageNonce: "u64",
   Message: {
     data: "Vec<u8>",
     proof: "Proof",
   },
   Proof: {
+    _enum: {
+      PoW: "ReceiptProof",
+      Execution: "ReceiptProof",
+    },
+  },
+  ReceiptProof: {
     blockHash: "H256",
     txIndex: "u32",
     data: "(Vec<Vec<u8>>, Vec<Vec<u8>>)",